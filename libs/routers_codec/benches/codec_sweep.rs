@@ -12,7 +12,7 @@ use std::any::Any;
 
 fn block_iter_count() {
     let path = fixture_path(DISTRICT_OF_COLUMBIA);
-    let mut iter = BlockIterator::new(path).expect("Could not create iterator");
+    let iter = BlockIterator::new(path).expect("Could not create iterator");
 
     iter.par_iter().for_each(|item| {
         info!("Block: {:?}", item.type_id());
@@ -23,16 +23,18 @@ fn element_iter_count() {
     let path = fixture_path(DISTRICT_OF_COLUMBIA);
     let iter = ElementIterator::new(path).expect("Could not create iterator");
 
-    let nodes = iter.map_red(
-        |item| match item {
-            Element::Way(_) => 0,
-            Element::Node(_) => 1,
-            Element::Relation(_) => 0,
-            Element::DenseNodes(_) => 0,
-        },
-        |a, b| a + b,
-        || 0,
-    );
+    let nodes = iter
+        .map_red(
+            |item| match item {
+                Element::Way(_) => 0,
+                Element::Node(_) => 1,
+                Element::Relation(_) => 0,
+                Element::DenseNodes(_) => 0,
+            },
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("!");
 
     info!("There are {nodes} nodes");
 }
@@ -41,18 +43,41 @@ fn processed_iter_count() {
     let path = fixture_path(DISTRICT_OF_COLUMBIA);
     let iter = ProcessedElementIterator::new(path).expect("Could not create iterator");
 
-    let nodes = iter.map_red(
-        |item| match item {
-            ProcessedElement::Node(_) => 1,
-            _ => 0,
-        },
-        |a, b| a + b,
-        || 0,
-    );
+    let nodes = iter
+        .map_red(
+            |item| match item {
+                ProcessedElement::Node(..) => 1,
+                _ => 0,
+            },
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("!");
 
     info!("There are {nodes} nodes");
 }
 
+fn processed_iter_tags() {
+    let path = fixture_path(DISTRICT_OF_COLUMBIA);
+    let iter = ProcessedElementIterator::new(path)
+        .expect("Could not create iterator")
+        .node_tags(true);
+
+    let tagged = iter
+        .map_red(
+            |item| match item {
+                ProcessedElement::Way(way) => way.tags().len(),
+                ProcessedElement::Node(_, tags) => tags.len(),
+                _ => 0,
+            },
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("!");
+
+    info!("There are {tagged} tags");
+}
+
 fn sweep_benchmark(c: &mut criterion::Criterion) {
     let mut group = c.benchmark_group("iterator_sweep");
     group.significance_level(0.1).sample_size(30);
@@ -60,6 +85,7 @@ fn sweep_benchmark(c: &mut criterion::Criterion) {
     group.bench_function("block_iter_count", |b| b.iter(block_iter_count));
     group.bench_function("element_iter_count", |b| b.iter(element_iter_count));
     group.bench_function("processed_iter_count", |b| b.iter(processed_iter_count));
+    group.bench_function("processed_iter_tags", |b| b.iter(processed_iter_tags));
     group.finish();
 }
 
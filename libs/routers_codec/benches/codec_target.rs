@@ -19,7 +19,7 @@ fn iterate_blocks_each() {
     match iterator {
         Ok(iter) => {
             for block in iter {
-                match block {
+                match block.expect("!") {
                     BlockItem::HeaderBlock(_) => header_blocks += 1,
                     BlockItem::PrimitiveBlock(_) => primitive_blocks += 1,
                 }
@@ -27,7 +27,7 @@ fn iterate_blocks_each() {
         }
         Err(err) => {
             error!(
-                "Failed to load file, {:?}. Got error: {err}",
+                "Failed to load file, {:?}. Got error: {err:?}",
                 path.as_os_str().to_str()
             );
         }
@@ -40,11 +40,11 @@ fn iterate_blocks_each() {
 fn parallel_iterate_blocks_each() {
     let path = fixture_path(DISTRICT_OF_COLUMBIA);
 
-    let mut block_iter = BlockIterator::new(path).unwrap();
+    let block_iter = BlockIterator::new(path).unwrap();
 
     let elements = block_iter
         .par_iter()
-        .map(|block| match block {
+        .map(|block| match block.expect("!") {
             BlockItem::HeaderBlock(_) => (0, 1),
             BlockItem::PrimitiveBlock(_) => (1, 0),
         })
@@ -64,16 +64,8 @@ fn compare_to_osmpbf() {
 
     for blob in reader.flatten() {
         match blob.get_type() {
-            BlobType::OsmHeader => {
-                if blob.to_headerblock().is_ok() {
-                    header_blocks += 1;
-                }
-            }
-            BlobType::OsmData => {
-                if blob.to_primitiveblock().is_ok() {
-                    primitive_blocks += 1;
-                }
-            }
+            BlobType::OsmHeader if blob.to_headerblock().is_ok() => header_blocks += 1,
+            BlobType::OsmData if blob.to_primitiveblock().is_ok() => primitive_blocks += 1,
             _ => {}
         }
     }
@@ -87,15 +79,17 @@ fn ingest_and_count() {
     let path = fixture_path(DISTRICT_OF_COLUMBIA);
     let reader = ProcessedElementIterator::new(path).expect("!");
 
-    let (ways, nodes) = reader.par_red(
-        |(ways, nodes), element| match element {
-            ProcessedElement::Way(_) => (ways + 1, nodes),
-            ProcessedElement::Node(_) => (ways, nodes + 1),
-            _ => (ways, nodes),
-        },
-        |(ways, nodes), (ways2, nodes2)| (ways + ways2, nodes + nodes2),
-        || (0u64, 0u64),
-    );
+    let (ways, nodes) = reader
+        .par_red(
+            |(ways, nodes), element| match element {
+                ProcessedElement::Way(_) => (ways + 1, nodes),
+                ProcessedElement::Node(..) => (ways, nodes + 1),
+                _ => (ways, nodes),
+            },
+            |(ways, nodes), (ways2, nodes2)| (ways + ways2, nodes + nodes2),
+            || (0u64, 0u64),
+        )
+        .expect("!");
 
     info!(
         "Got {} ways and {} nodes in {}ms",
@@ -105,6 +99,27 @@ fn ingest_and_count() {
     );
 }
 
+fn ingest_and_lookup_tags() {
+    let path = fixture_path(DISTRICT_OF_COLUMBIA);
+    let reader = ProcessedElementIterator::new(path).expect("!");
+
+    let (roads, limited) = reader
+        .par_red(
+            |(roads, limited), element| match element {
+                ProcessedElement::Way(way) => (
+                    roads + way.tags().road_tag().is_some() as u64,
+                    limited + way.tags().contains_key("maxspeed") as u64,
+                ),
+                _ => (roads, limited),
+            },
+            |(roads, limited), (roads2, limited2)| (roads + roads2, limited + limited2),
+            || (0u64, 0u64),
+        )
+        .expect("!");
+
+    info!("Got {roads} roads, of which {limited} have a speed limit");
+}
+
 fn target_benchmark(c: &mut criterion::Criterion) {
     let mut group = c.benchmark_group("iterator_target");
     group.significance_level(0.1).sample_size(30);
@@ -115,6 +130,7 @@ fn target_benchmark(c: &mut criterion::Criterion) {
     });
     group.bench_function("compared_to_osmpbf", |b| b.iter(compare_to_osmpbf));
     group.bench_function("ingest_and_count", |b| b.iter(ingest_and_count));
+    group.bench_function("ingest_and_lookup_tags", |b| b.iter(ingest_and_lookup_tags));
 
     group.finish();
 }
@@ -0,0 +1,79 @@
+//! Cuts an extract of a bounding box or polygon from a `.osm.pbf` file.
+//!
+//! ```text
+//! extract <input.osm.pbf> <output.osm.pbf> --bbox <left,bottom,right,top>
+//! extract <input.osm.pbf> <output.osm.pbf> --polygon <region.wkt>
+//! ```
+
+use geo::{Polygon, Rect, coord};
+use routers_codec::osm::extract::{Extract, Region};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+use wkt::TryFromWkt;
+
+const USAGE: &str = "Usage: extract <input.osm.pbf> <output.osm.pbf> (--bbox <left,bottom,right,top> | --polygon <region.wkt>)";
+
+fn region(kind: &str, value: &str) -> Result<Region, String> {
+    match kind {
+        "--bbox" => {
+            let bounds = value
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| format!("Invalid bounding box: {err}"))?;
+
+            let [left, bottom, right, top] = bounds[..] else {
+                return Err("A bounding box requires four bounds".to_string());
+            };
+
+            Ok(Region::from(Rect::new(
+                coord! { x: left, y: bottom },
+                coord! { x: right, y: top },
+            )))
+        }
+        "--polygon" => {
+            let wkt = std::fs::read_to_string(value)
+                .map_err(|err| format!("Could not read polygon: {err}"))?;
+
+            Polygon::<f64>::try_from_wkt_str(&wkt)
+                .map(Region::from)
+                .map_err(|err| format!("Invalid polygon: {err}"))
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [input, output, kind, value] = &args[..] else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let region = match region(kind, value) {
+        Ok(region) => region,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let start = Instant::now();
+    match Extract::new(region).to_file(PathBuf::from(input), PathBuf::from(output)) {
+        Ok(summary) => {
+            println!(
+                "Extracted {} nodes, {} ways and {} relations in {:?}",
+                summary.nodes,
+                summary.ways,
+                summary.relations,
+                start.elapsed()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Could not extract region: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
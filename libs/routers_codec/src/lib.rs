@@ -13,3 +13,4 @@ pub use primitive::Edge;
 pub use primitive::Entry;
 pub use primitive::Metadata;
 pub use primitive::Node;
+pub use primitive::NodeMetadata;
@@ -1,21 +1,44 @@
-//! Describes the `BlobItem`, which holds the file reference for an `Element`
+//! Describes the `BlobItem`, which holds the raw (encoded) data of a `Blob`
 
-use crate::osm::BlobHeader;
+use crate::osm::{BlobHeader, HeaderBlock};
+use bytes::Bytes;
 use std::ops::Range;
 
 pub struct BlobItem {
+    /// The position of the blob within the file, counting from zero.
+    pub(crate) index: u64,
+    /// The offset within the file at which the blob, including its header, begins.
+    pub(crate) offset: u64,
+    /// The position of the blob data within the file.
     pub(crate) range: Range<usize>,
     pub header: BlobHeader,
+
+    /// The encoded blob. This is either owned, if streamed from the file,
+    /// or a reference-counted view into the shared (or memory-mapped) file.
+    pub(crate) data: Bytes,
+
+    /// The decoded block of the leading header blob, which is decoded
+    /// upon reading the file, such that it is not decoded again.
+    pub(crate) header_block: Option<HeaderBlock>,
 }
 
 impl BlobItem {
     #[inline]
-    pub(crate) fn new(start: usize, header: BlobHeader) -> Option<BlobItem> {
-        let end = start + (header.datasize as u64) as usize;
+    pub(crate) fn new(
+        (index, offset): (u64, u64),
+        start: usize,
+        header: BlobHeader,
+        data: Bytes,
+    ) -> BlobItem {
+        let end = start + data.len();
 
-        Some(BlobItem {
+        BlobItem {
+            index,
+            offset,
             range: start..end,
             header,
-        })
+            data,
+            header_block: None,
+        }
     }
 }
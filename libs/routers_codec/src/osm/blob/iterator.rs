@@ -1,108 +1,240 @@
 //! The file blob iterator
-//! Supports `mmap` reading through the optional feature
+//!
+//! Blobs are streamed from the file through a buffered reader, such that
+//! only the blobs being decoded are held in memory. Alternatively, the file
+//! may be memory-mapped through the optional `mmap` feature.
 
 use crate::osm::BlobHeader;
 use crate::osm::BlockItem;
 use crate::osm::blob::item::BlobItem;
+use crate::osm::error::CodecError;
+use crate::osm::header::PbfHeader;
 
+use bytes::Bytes;
 use log::trace;
 use prost::Message;
 use std::fs::File;
-use std::io;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
-use std::sync::Arc;
 
 const HEADER_LEN_SIZE: usize = 4;
 
+/// The maximum size of a `BlobHeader`, as defined by the specification.
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+
+/// The maximum size of a `Blob`, as defined by the specification.
+pub(crate) const MAX_BLOB_SIZE: usize = 32 * 1024 * 1024;
+
+/// The source from which blobs are read.
+enum BlobSource {
+    /// Reads the file sequentially, holding only the current blob.
+    Stream(BufReader<File>),
+
+    /// A view over the entire file, either held in memory or memory-mapped.
+    Shared(Bytes),
+}
+
 pub struct BlobIterator {
-    pub(crate) buf: Arc<Vec<u8>>,
+    source: BlobSource,
+
+    /// The header of the file, read upon construction.
+    header: Option<PbfHeader>,
+    /// The blob read ahead to determine the header, yet to be yielded.
+    pending: Option<BlobItem>,
+    /// Whether the source has ended, or could not be read further.
+    exhausted: bool,
 
     pub(crate) index: u64,
     offset: u64,
 }
 
 impl BlobIterator {
-    pub fn new(path: PathBuf) -> Result<BlobIterator, io::Error> {
+    /// Streams the blobs of the file at the given path.
+    pub fn new(path: PathBuf) -> Result<BlobIterator, CodecError> {
         let file = File::open(path)?;
+        Self::from_source(BlobSource::Stream(BufReader::new(file)))
+    }
 
-        let mut buf = Vec::new(); // vec![0; file.metadata()?.size() as usize];
-        let mut reader = BufReader::new(file);
-        reader.read_to_end(&mut buf)?;
-        let buf = Arc::new(buf);
+    /// Reads the blobs of the file at the given path by memory-mapping it.
+    ///
+    /// Blobs are views into the mapping, so are not copied until decoded.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process,
+    /// until the iterator and every blob it yields are dropped. Otherwise, reading
+    /// the mapping is undefined behaviour.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn mmap(path: PathBuf) -> Result<BlobIterator, CodecError> {
+        let file = File::open(path)?;
 
-        Ok(BlobIterator {
-            buf,
-            offset: 0,
-            index: 0,
-        })
-    }
+        // SAFETY: The caller guarantees the file is neither modified nor truncated
+        // whilst mapped, which the mapping requires to remain sound.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
 
-    pub fn with_existing(buf: Arc<Vec<u8>>) -> Result<BlobIterator, io::Error> {
-        // let file = File::open(path)?;
+        Self::with_existing(Bytes::from_owner(map))
+    }
 
-        // let mut buf = Vec::new(); // vec![0; file.metadata()?.size() as usize];
-        // let mut reader = BufReader::new(file);
-        // reader.read_to_end(&mut buf)?;
+    /// Reads the blobs of a file already held in memory.
+    pub fn with_existing(buf: Bytes) -> Result<BlobIterator, CodecError> {
+        Self::from_source(BlobSource::Shared(buf))
+    }
 
-        Ok(BlobIterator {
-            buf,
+    /// Reads the header of the file from its leading blob,
+    /// rejecting files which require unsupported features.
+    ///
+    /// The header blob is always decoded strictly, regardless of the
+    /// [`DecodeMode`](crate::osm::DecodeMode), since whether the file
+    /// may be read at all depends upon the features it requires.
+    fn from_source(source: BlobSource) -> Result<BlobIterator, CodecError> {
+        let mut iterator = BlobIterator {
+            source,
+            header: None,
+            pending: None,
+            exhausted: false,
             offset: 0,
             index: 0,
-        })
+        };
+
+        iterator.pending = iterator.next().transpose()?;
+        if let Some(blob) = iterator
+            .pending
+            .as_mut()
+            .filter(|blob| blob.header.r#type == "OSMHeader")
+            && let Some(BlockItem::HeaderBlock(block)) = BlockItem::from_blob_item(blob)?
+        {
+            let header = PbfHeader::from(block.clone());
+            header.validate()?;
+
+            blob.header_block = Some(block);
+            iterator.header = Some(header);
+        }
+
+        Ok(iterator)
     }
 
-    pub fn make_block(&self, blob: &BlobItem) -> Option<BlockItem> {
-        BlockItem::from_blob_item(blob, &self.buf)
+    /// The header of the file, if it has one.
+    pub fn header(&self) -> Option<&PbfHeader> {
+        self.header.as_ref()
+    }
+
+    pub fn make_block(&self, blob: &BlobItem) -> Result<Option<BlockItem>, CodecError> {
+        BlockItem::from_blob_item(blob)
     }
 }
 
 impl BlobIterator {
-    fn take_next(&mut self) -> Option<BlobItem> {
-        if self.buf.len() < self.offset as usize + HEADER_LEN_SIZE {
-            return None;
+    /// Determines if the source has ended at the boundary of a blob.
+    fn at_end(&mut self) -> Result<bool, CodecError> {
+        Ok(match &mut self.source {
+            BlobSource::Stream(reader) => reader.fill_buf()?.is_empty(),
+            BlobSource::Shared(buf) => buf.len() <= self.offset as usize,
+        })
+    }
+
+    /// Reads the next `len` bytes from the source, failing should it end beforehand.
+    fn read(&mut self, len: usize) -> Result<Bytes, CodecError> {
+        let bytes = match &mut self.source {
+            BlobSource::Stream(reader) => {
+                let mut buf = Vec::with_capacity(len);
+                reader.by_ref().take(len as u64).read_to_end(&mut buf)?;
+
+                if buf.len() < len {
+                    return Err(CodecError::Truncated {
+                        expected: len,
+                        available: buf.len(),
+                    });
+                }
+
+                Bytes::from(buf)
+            }
+            BlobSource::Shared(buf) => {
+                let start = self.offset as usize;
+                if buf.len() < start + len {
+                    return Err(CodecError::Truncated {
+                        expected: len,
+                        available: buf.len().saturating_sub(start),
+                    });
+                }
+
+                buf.slice(start..start + len)
+            }
+        };
+
+        self.offset += len as u64;
+        Ok(bytes)
+    }
+
+    /// Reads the next blob, or `None` should the source have ended at the end of a blob.
+    fn take_next(&mut self) -> Result<Option<BlobItem>, CodecError> {
+        if self.at_end()? {
+            return Ok(None);
         }
 
-        let header_len_buffer =
-            unsafe { self.buf.as_ptr().add(self.offset as usize) as *const [u8; HEADER_LEN_SIZE] };
-        self.offset += HEADER_LEN_SIZE as u64;
+        let offset = self.offset;
+        let header_len_buffer = self.read(HEADER_LEN_SIZE)?;
 
         // Translate to i32 (Big Endian)
-        let blob_header_length = u32::from_be_bytes(unsafe { *header_len_buffer }) as usize;
+        let blob_header_length = u32::from_be_bytes([
+            header_len_buffer[0],
+            header_len_buffer[1],
+            header_len_buffer[2],
+            header_len_buffer[3],
+        ]);
         trace!("Header length: {blob_header_length}. Buffer: {header_len_buffer:?}");
 
-        if self.buf.len() < self.offset as usize + blob_header_length {
-            return None;
+        let blob_header_length = blob_header_length as usize;
+        if blob_header_length > MAX_HEADER_SIZE {
+            return Err(CodecError::OversizedBlob {
+                kind: "blob header",
+                size: blob_header_length,
+            });
         }
 
-        let blob_header_buffer =
-            &self.buf[self.offset as usize..self.offset as usize + blob_header_length];
-        self.offset += blob_header_length as u64;
+        let blob_header_buffer = self.read(blob_header_length)?;
+        let header = BlobHeader::decode(blob_header_buffer)?;
+
+        let datasize = usize::try_from(header.datasize).unwrap_or(usize::MAX);
+        if datasize > MAX_BLOB_SIZE {
+            return Err(CodecError::OversizedBlob {
+                kind: "blob",
+                size: datasize,
+            });
+        }
 
         let start = self.offset;
-        let header = BlobHeader::decode(blob_header_buffer).ok()?;
-        self.offset += header.datasize as u64;
+        let data = self.read(datasize)?;
 
-        let blob = BlobItem::new(start as usize, header)?;
+        let blob = BlobItem::new((self.index, offset), start as usize, header, data);
         self.index += 1;
 
-        Some(blob)
+        Ok(Some(blob))
     }
 }
 
 impl Iterator for BlobIterator {
-    type Item = BlobItem;
+    type Item = Result<BlobItem, CodecError>;
 
+    /// Yields the next blob, or the error which prevented it being read.
+    ///
+    /// As the position of the following blob cannot be known should a blob
+    /// not be read, iteration ends after the first error.
     fn next(&mut self) -> Option<Self::Item> {
-        self.take_next()
+        if let Some(blob) = self.pending.take() {
+            return Some(Ok(blob));
+        }
+
+        if self.exhausted {
+            return None;
+        }
+
+        let offset = self.offset;
+        let blob = self
+            .take_next()
+            .map_err(|err| err.at_blob(self.index, offset))
+            .transpose();
+
+        self.exhausted = !matches!(blob, Some(Ok(_)));
+        blob
     }
 }
-
-// #[gat]
-// impl LendingIterator for BlobIterator {
-//     type Item<'next> where Self: 'next = BlobItem<'next>;
-//
-//     fn next(self: &mut Self) -> Option<Item<'_, Self>> {
-//         self.take_next()
-//     }
-// }
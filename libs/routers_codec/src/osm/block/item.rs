@@ -2,7 +2,7 @@
 //! providing distinction for header and primitive elements, as well
 //! as decoding fully, to element level.
 
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use either::Either;
 use flate2::read::ZlibDecoder;
 use log::{trace, warn};
@@ -11,8 +11,11 @@ use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::io::Read;
 
 use crate::osm::blob::item::BlobItem;
+use crate::osm::blob::iterator::MAX_BLOB_SIZE;
+use crate::osm::element::BlockStrings;
 use crate::osm::element::item::Element;
 use crate::osm::element::item::ProcessedElement;
+use crate::osm::error::CodecError;
 use crate::osm::{Blob, HeaderBlock, PrimitiveBlock, model::blob::Data};
 
 pub enum BlockItem {
@@ -20,56 +23,198 @@ pub enum BlockItem {
     PrimitiveBlock(PrimitiveBlock),
 }
 
+/// A writer of decompressed data which fails once the data exceeds the declared
+/// size of the blob, for decoders which write their output rather than being read.
+#[cfg(feature = "lzma")]
+struct BoundedWriter {
+    decoded: Vec<u8>,
+    limit: usize,
+}
+
+#[cfg(feature = "lzma")]
+impl BoundedWriter {
+    fn new(limit: usize) -> Self {
+        Self {
+            decoded: Vec::with_capacity(limit),
+            limit,
+        }
+    }
+
+    fn into_inner(self) -> Vec<u8> {
+        self.decoded
+    }
+}
+
+#[cfg(feature = "lzma")]
+impl std::io::Write for BoundedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.decoded.len() + buf.len() > self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("decompressed beyond the declared {} bytes", self.limit),
+            ));
+        }
+
+        self.decoded.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 impl BlockItem {
+    /// Decodes the blob into its block, if it is of a known type.
+    ///
+    /// Blobs of an unknown type are skipped, as permitted by the specification,
+    /// whereas blobs which cannot be decompressed or decoded are an error,
+    /// attributed to the [blob](CodecError::Blob) in question.
     #[inline]
-    pub(crate) fn from_blob_item(blob: &BlobItem, buf: &[u8]) -> Option<Self> {
+    pub(crate) fn from_blob_item(blob: &BlobItem) -> Result<Option<Self>, CodecError> {
         trace!(
             "Decoding blob: {:?}. Size: {}",
             blob.range, blob.header.datasize
         );
 
-        BlockItem::from_raw(blob, buf)
+        if let Some(block) = &blob.header_block {
+            return Ok(Some(BlockItem::HeaderBlock(block.clone())));
+        }
+
+        BlockItem::from_raw(blob).map_err(|err| err.at_blob(blob.index, blob.offset))
     }
 
     #[inline]
-    fn from_raw(blob_item: &BlobItem, buf: &[u8]) -> Option<Self> {
-        let data = buf.get(blob_item.range.clone())?;
-        let blob = Blob::decode(data).ok()?;
+    fn from_raw(blob_item: &BlobItem) -> Result<Option<Self>, CodecError> {
+        let blob = Blob::decode(blob_item.data.clone())?;
 
-        // Convert raw into actual. Handles ZLIB encoding.
+        // Convert raw into actual, decompressing as required.
         let data = BlockItem::from_blob(blob)?;
-        BlockItem::from_data(data.as_slice(), blob_item)
+        BlockItem::from_data(data, blob_item)
     }
 
+    /// The uncompressed size of a compressed blob, which must be declared, and
+    /// within the maximum size, before any memory is allocated to decompress it.
     #[inline]
-    fn from_blob(blob: Blob) -> Option<Vec<u8>> {
-        if let Some(Data::ZlibData(data)) = blob.data {
-            return BlockItem::zlib_decode(data, blob.raw_size.unwrap_or(0) as usize);
+    fn raw_size(raw_size: Option<i32>) -> Result<usize, CodecError> {
+        let size = raw_size
+            .and_then(|size| usize::try_from(size).ok())
+            .ok_or(CodecError::InvalidRawSize(raw_size))?;
+
+        if size > MAX_BLOB_SIZE {
+            return Err(CodecError::OversizedBlob {
+                kind: "uncompressed blob",
+                size,
+            });
         }
 
-        warn!("Type {:?} not yet supported.", blob.data);
-        None
+        Ok(size)
     }
 
     #[inline]
-    fn from_data(data: &[u8], blob: &BlobItem) -> Option<Self> {
-        match blob.header.r#type.as_str() {
-            "OSMData" => Some(BlockItem::PrimitiveBlock(
-                PrimitiveBlock::decode(data).ok()?,
-            )),
-            "OSMHeader" => Some(BlockItem::HeaderBlock(HeaderBlock::decode(data).ok()?)),
-            _ => None,
+    fn from_blob(blob: Blob) -> Result<Bytes, CodecError> {
+        let raw_size = || BlockItem::raw_size(blob.raw_size);
+
+        match blob.data.ok_or(CodecError::EmptyBlob)? {
+            Data::Raw(data) => Ok(data),
+            Data::ZlibData(data) => BlockItem::zlib_decode(data, raw_size()?),
+            #[cfg(feature = "lzma")]
+            Data::LzmaData(data) => BlockItem::lzma_decode(data, raw_size()?),
+            #[cfg(not(feature = "lzma"))]
+            Data::LzmaData(_) => Err(CodecError::UnsupportedCompression("lzma")),
+            #[cfg(feature = "lz4")]
+            Data::Lz4Data(data) => BlockItem::lz4_decode(data, raw_size()?),
+            #[cfg(not(feature = "lz4"))]
+            Data::Lz4Data(_) => Err(CodecError::UnsupportedCompression("lz4")),
+            #[cfg(feature = "zstd")]
+            Data::ZstdData(data) => BlockItem::zstd_decode(data, raw_size()?),
+            #[cfg(not(feature = "zstd"))]
+            Data::ZstdData(_) => Err(CodecError::UnsupportedCompression("zstd")),
+            #[allow(deprecated)]
+            Data::ObsoleteBzip2Data(_) => Err(CodecError::UnsupportedCompression("bzip2")),
         }
     }
 
     #[inline]
-    fn zlib_decode(data: prost::bytes::Bytes, raw_size: usize) -> Option<Vec<u8>> {
-        let mut decoded = vec![0_u8; raw_size];
-        ZlibDecoder::new(data.reader())
-            .read_exact(&mut decoded)
-            .ok()?;
+    fn from_data(data: Bytes, blob: &BlobItem) -> Result<Option<Self>, CodecError> {
+        Ok(match blob.header.r#type.as_str() {
+            "OSMData" => Some(BlockItem::PrimitiveBlock(PrimitiveBlock::decode(data)?)),
+            "OSMHeader" => Some(BlockItem::HeaderBlock(HeaderBlock::decode(data)?)),
+            other => {
+                warn!("Skipping blob of unknown type {other:?}.");
+                None
+            }
+        })
+    }
+
+    /// Ensures the decompressed data is of the size the blob declared.
+    #[inline]
+    fn exact_size(
+        compression: &'static str,
+        decoded: Vec<u8>,
+        raw_size: usize,
+    ) -> Result<Bytes, CodecError> {
+        if decoded.len() != raw_size {
+            return Err(CodecError::DecompressionError(
+                compression,
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("decompressed beyond or short of the declared {raw_size} bytes"),
+                ),
+            ));
+        }
 
-        Some(decoded)
+        Ok(Bytes::from(decoded))
+    }
+
+    /// Reads the decompressed data, stopping a byte past the declared size of the
+    /// blob, such that a blob understating its size is never decompressed in full.
+    #[inline]
+    fn read_bounded(
+        compression: &'static str,
+        reader: impl Read,
+        raw_size: usize,
+    ) -> Result<Bytes, CodecError> {
+        let mut decoded = Vec::with_capacity(raw_size);
+        reader
+            .take(raw_size as u64 + 1)
+            .read_to_end(&mut decoded)
+            .map_err(|err| CodecError::DecompressionError(compression, err))?;
+
+        BlockItem::exact_size(compression, decoded, raw_size)
+    }
+
+    #[inline]
+    fn zlib_decode(data: Bytes, raw_size: usize) -> Result<Bytes, CodecError> {
+        BlockItem::read_bounded("zlib", ZlibDecoder::new(data.reader()), raw_size)
+    }
+
+    #[cfg(feature = "lzma")]
+    #[inline]
+    fn lzma_decode(data: Bytes, raw_size: usize) -> Result<Bytes, CodecError> {
+        let mut decoded = BoundedWriter::new(raw_size);
+        lzma_rs::lzma_decompress(&mut data.reader(), &mut decoded)
+            .map_err(|err| CodecError::DecompressionError("lzma", std::io::Error::other(err)))?;
+
+        BlockItem::exact_size("lzma", decoded.into_inner(), raw_size)
+    }
+
+    #[cfg(feature = "lz4")]
+    #[inline]
+    fn lz4_decode(data: Bytes, raw_size: usize) -> Result<Bytes, CodecError> {
+        let decoded = lz4_flex::block::decompress(&data, raw_size)
+            .map_err(|err| CodecError::DecompressionError("lz4", std::io::Error::other(err)))?;
+
+        BlockItem::exact_size("lz4", decoded, raw_size)
+    }
+
+    #[cfg(feature = "zstd")]
+    #[inline]
+    fn zstd_decode(data: Bytes, raw_size: usize) -> Result<Bytes, CodecError> {
+        let decoder = ruzstd::decoding::StreamingDecoder::new(data.reader())
+            .map_err(|err| CodecError::DecompressionError("zstd", std::io::Error::other(err)))?;
+
+        BlockItem::read_bounded("zstd", decoder, raw_size)
     }
 
     pub fn r#type(&self) -> &str {
@@ -80,7 +225,7 @@ impl BlockItem {
     }
 
     #[inline]
-    pub fn raw_element_iter(&self) -> impl Iterator<Item = Element> {
+    pub fn raw_element_iter(&self) -> impl Iterator<Item = Element<'_>> {
         match self {
             BlockItem::PrimitiveBlock(primitive) => Either::Left(
                 primitive
@@ -94,20 +239,36 @@ impl BlockItem {
 
     #[inline]
     pub fn element_iter(&self) -> impl Iterator<Item = ProcessedElement> + '_ {
+        self.element_iter_with(false)
+    }
+
+    /// Iterates over the processed elements, decoding the tags
+    /// of nodes should `node_tags` be set.
+    #[inline]
+    pub fn element_iter_with(
+        &self,
+        node_tags: bool,
+    ) -> impl Iterator<Item = ProcessedElement> + '_ {
         match self {
-            BlockItem::PrimitiveBlock(primitive) => Either::Left(
-                primitive
-                    .primitivegroup
-                    .iter()
-                    .flat_map(Element::from_group)
-                    .flat_map(|element| ProcessedElement::from_raw(element, primitive)),
-            ),
+            BlockItem::PrimitiveBlock(primitive) => {
+                let strings = BlockStrings::new(primitive);
+
+                Either::Left(
+                    primitive
+                        .primitivegroup
+                        .iter()
+                        .flat_map(Element::from_group)
+                        .flat_map(move |element| {
+                            ProcessedElement::from_raw(element, &strings, node_tags)
+                        }),
+                )
+            }
             BlockItem::HeaderBlock(_) => Either::Right(std::iter::empty()),
         }
     }
 
     #[inline]
-    pub fn raw_par_iter(&mut self) -> impl ParallelIterator<Item = Element> + '_ {
+    pub fn raw_par_iter(&mut self) -> impl ParallelIterator<Item = Element<'_>> + '_ {
         match self {
             BlockItem::PrimitiveBlock(primitive) => Either::Left(
                 primitive
@@ -121,14 +282,30 @@ impl BlockItem {
 
     #[inline]
     pub fn par_iter(&mut self) -> impl ParallelIterator<Item = ProcessedElement> + '_ {
-        match self {
-            BlockItem::PrimitiveBlock(primitive) => Either::Left(
-                primitive
-                    .primitivegroup
-                    .par_iter()
-                    .flat_map(Element::from_group)
-                    .flat_map(|element| ProcessedElement::from_raw(element, primitive)),
-            ),
+        self.par_iter_with(false)
+    }
+
+    /// Iterates over the processed elements in parallel, decoding
+    /// the tags of nodes should `node_tags` be set.
+    #[inline]
+    pub fn par_iter_with(
+        &mut self,
+        node_tags: bool,
+    ) -> impl ParallelIterator<Item = ProcessedElement> + '_ {
+        match &*self {
+            BlockItem::PrimitiveBlock(primitive) => {
+                let strings = BlockStrings::new(primitive);
+
+                Either::Left(
+                    primitive
+                        .primitivegroup
+                        .par_iter()
+                        .flat_map(Element::from_group)
+                        .flat_map(move |element| {
+                            ProcessedElement::from_raw(element, &strings, node_tags)
+                        }),
+                )
+            }
             BlockItem::HeaderBlock(_) => Either::Right(rayon::iter::empty()),
         }
     }
@@ -1,107 +1,163 @@
 //! Iterates over `BlockItem`s in the file
 
-use crate::osm::BlobItem;
 use crate::osm::blob::iterator::BlobIterator;
 use crate::osm::block::item::BlockItem;
+use crate::osm::error::CodecError;
+use crate::osm::header::PbfHeader;
 
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::fs::File;
-use std::io;
-use std::io::{BufReader, Read};
+use log::warn;
+use rayon::iter::{ParallelBridge, ParallelIterator};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// How blobs which cannot be read or decoded are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Fails upon the first corrupt blob, yielding its [`CodecError`].
+    #[default]
+    Strict,
+    /// Skips corrupt blobs, logging and [counting](SkippedBlobs) each.
+    ///
+    /// Since the position of the following blob is unknown should a blob
+    /// not be read (such as within a truncated file), reading ends there.
+    ///
+    /// The leading header blob is nonetheless decoded strictly, upon opening the file,
+    /// as the features it requires determine whether the file may be read at all.
+    Lenient,
+}
+
+/// The number of blobs skipped whilst decoding in [`DecodeMode::Lenient`],
+/// shared with the iterator, such that it may be read once iteration completes.
+#[derive(Debug, Clone, Default)]
+pub struct SkippedBlobs(Arc<AtomicUsize>);
+
+impl SkippedBlobs {
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
 
 pub struct BlockIterator {
-    blobs: Vec<BlobItem>,
-    buf: Arc<Vec<u8>>,
-    index: usize,
+    blobs: BlobIterator,
+
+    /// The maximum number of blobs read ahead of those being decoded.
+    readahead: usize,
+
+    mode: DecodeMode,
+    skipped: SkippedBlobs,
 }
 
 impl BlockIterator {
+    /// Streams the blocks of the file at the given path.
     #[inline]
-    pub fn new(path: PathBuf) -> Result<BlockIterator, io::Error> {
-        let file = File::open(path)?;
+    pub fn new(path: PathBuf) -> Result<BlockIterator, CodecError> {
+        Ok(Self::from_blobs(BlobIterator::new(path)?))
+    }
 
-        let mut buf = Vec::new();
-        let mut reader = BufReader::new(file);
-        reader.read_to_end(&mut buf)?;
+    /// Reads the blocks of the file at the given path by memory-mapping it.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process,
+    /// whilst it is mapped, as [`BlobIterator::mmap`] requires.
+    #[cfg(feature = "mmap")]
+    #[inline]
+    pub unsafe fn mmap(path: PathBuf) -> Result<BlockIterator, CodecError> {
+        Ok(Self::from_blobs(unsafe { BlobIterator::mmap(path) }?))
+    }
 
-        let buf = Arc::new(buf);
+    /// Reads the blocks of the given blobs.
+    pub fn from_blobs(blobs: BlobIterator) -> BlockIterator {
+        BlockIterator {
+            blobs,
+            readahead: rayon::current_num_threads() * 2,
+            mode: DecodeMode::default(),
+            skipped: SkippedBlobs::default(),
+        }
+    }
 
-        let iter = BlobIterator::with_existing(Arc::clone(&buf))?;
-        let blobs = iter.collect::<Vec<_>>();
+    /// The header of the file, if it has one.
+    pub fn header(&self) -> Option<&PbfHeader> {
+        self.blobs.header()
+    }
 
-        Ok(BlockIterator {
-            index: 0,
-            blobs,
-            buf,
-        })
+    /// Sets the maximum number of blobs read ahead of those being decoded,
+    /// bounding the memory held by blobs awaiting a worker.
+    pub fn readahead(self, readahead: usize) -> Self {
+        Self {
+            readahead: readahead.max(1),
+            ..self
+        }
+    }
+
+    /// Sets how blobs which cannot be read or decoded are handled.
+    pub fn mode(self, mode: DecodeMode) -> Self {
+        Self { mode, ..self }
     }
 
+    /// The blobs skipped in [`DecodeMode::Lenient`], counted as they are decoded.
+    pub fn skipped(&self) -> SkippedBlobs {
+        self.skipped.clone()
+    }
+
+    /// Yields the decoded block, or the error which prevented it being decoded
+    /// unless it is to be skipped under the [mode](DecodeMode).
+    fn admit(
+        mode: DecodeMode,
+        skipped: &SkippedBlobs,
+        block: Result<Option<BlockItem>, CodecError>,
+    ) -> Option<Result<BlockItem, CodecError>> {
+        match (block, mode) {
+            (Err(err), DecodeMode::Lenient) => {
+                warn!("Skipping corrupt {err}");
+                skipped.0.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            (block, _) => block.transpose(),
+        }
+    }
+
+    /// Decodes the blocks in parallel.
+    ///
+    /// Blocks which cannot be decoded, such as those using an unsupported
+    /// compression, are yielded as an error rather than silently skipped,
+    /// unless decoding in [`DecodeMode::Lenient`].
+    ///
+    /// Blobs are read on a dedicated thread, at most the [readahead](Self::readahead)
+    /// ahead of the rayon workers decoding them, such that reading the file is
+    /// overlapped with decoding it without holding the entire file in memory.
     #[inline]
-    pub fn par_iter(&mut self) -> impl ParallelIterator<Item = BlockItem> + '_ {
-        self.blobs
-            .par_iter()
-            .filter_map(|blob| BlockItem::from_blob_item(blob, self.buf.as_slice()))
+    pub fn par_iter(self) -> impl ParallelIterator<Item = Result<BlockItem, CodecError>> {
+        let (sender, receiver) = mpsc::sync_channel(self.readahead);
+        let (blobs, mode, skipped) = (self.blobs, self.mode, self.skipped);
+
+        std::thread::spawn(move || {
+            for blob in blobs {
+                // The receiver has been dropped, so no more blobs are required.
+                if sender.send(blob).is_err() {
+                    break;
+                }
+            }
+        });
+
+        receiver.into_iter().par_bridge().filter_map(move |blob| {
+            let block = blob.and_then(|blob| BlockItem::from_blob_item(&blob));
+            Self::admit(mode, &skipped, block)
+        })
     }
 }
 
 impl Iterator for BlockIterator {
-    type Item = BlockItem;
+    type Item = Result<BlockItem, CodecError>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let blob = self.blobs.get(self.index)?;
-        let block = BlockItem::from_blob_item(blob, self.buf.as_slice());
-        self.index += 1;
-        block
+        let (mode, skipped) = (self.mode, &self.skipped);
+        self.blobs.by_ref().find_map(|blob| {
+            let block = blob.and_then(|blob| BlockItem::from_blob_item(&blob));
+            Self::admit(mode, skipped, block)
+        })
     }
 }
-
-// impl ParallelIterator for BlockIterator {
-//     fn next(&mut self) -> Option<Self::Item> {
-//
-//     }
-// }
-
-// impl ParallelIterator for BlockIterator {
-//     type Item = BlockItem;
-//
-//     fn drive_unindexed<C>(self, consumer: C) -> C::Result
-//     where
-//         C: UnindexedConsumer<BlockItem> + Consumer<BlockItem>,
-//     {
-//         let (sender, receiver) = channel::unbounded();
-//
-//         // Spawn a thread to feed items into the channel
-//         std::thread::spawn(move || {
-//             let mut raw = self.into_iter();
-//
-//             for item in raw.by_ref() {
-//                 if sender.send(item).is_err() {
-//                     break;
-//                 }
-//             }
-//         });
-//
-//         receiver.into_iter().par_bridge().drive_unindexed(consumer)
-//     }
-// }
-
-// impl<'a> Iterator for BlockIterator {
-//     type Item = BlockItem;
-//
-//     fn next(&mut self) -> Option<Self::Item> {
-//         match self.iter.next() {
-//             Some(blob) => BlockItem::from_blob_item(&blob),
-//             None => None
-//         }
-//     }
-//
-//     // #[cfg(not(feature = "mmap"))]
-//     // fn next(&mut self) -> Option<Self::Item> {
-//     //     let blob_desc = &self.blobs[self.index];
-//     //     self.index += 1;
-//     //     BlockItem::from_blob_item(blob_desc, &mut self.file)
-//     // }
-// }
@@ -0,0 +1,164 @@
+//! Reads OsmChange (`.osc`) files, the replication diffs which describe
+//! the elements created, modified and deleted between two points in time.
+//!
+//! Source: https://wiki.openstreetmap.org/wiki/OsmChange
+
+#[doc(hidden)]
+mod test;
+
+use std::io::BufRead;
+use std::path::Path;
+use std::str::FromStr;
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::{OsmEntryId, Relation, Tags, Way};
+use crate::osm::error::CodecError;
+use crate::osm::xml::{open, read_element};
+use crate::primitive::Node;
+
+/// The action taken upon an element within a change file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeAction {
+    Create,
+    Modify,
+    Delete,
+}
+
+impl ChangeAction {
+    fn from_tag(tag: &[u8]) -> Option<Self> {
+        match tag {
+            b"create" => Some(ChangeAction::Create),
+            b"modify" => Some(ChangeAction::Modify),
+            b"delete" => Some(ChangeAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// An element, in its state following the action taken upon it.
+#[derive(Clone)]
+pub struct Change {
+    pub action: ChangeAction,
+    pub element: ProcessedElement,
+}
+
+/// The changes within an OsmChange file, in the order they are to be applied.
+///
+/// ```rust,ignore
+/// use routers_codec::osm::OsmChange;
+///
+/// let change = OsmChange::from_path("000/001/234.osc.gz")?;
+/// for (action, way) in change.ways() {
+///     println!("{action:?} {:?}", way.id());
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct OsmChange {
+    pub changes: Vec<Change>,
+}
+
+impl OsmChange {
+    /// Reads the change file at the path, which is decompressed
+    /// should it be gzipped (such as `.osc.gz`), as replication diffs are.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, CodecError> {
+        Self::from_reader(open(path.as_ref())?)
+    }
+
+    /// Reads the changes from an uncompressed OsmChange document.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, CodecError> {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+
+        let mut buf = Vec::new();
+        let mut action = None;
+        let mut changes = vec![];
+
+        loop {
+            let (start, empty) = match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => (start, false),
+                Event::Empty(start) => (start, true),
+                Event::End(end) => {
+                    if ChangeAction::from_tag(end.name().as_ref()).is_some() {
+                        action = None;
+                    }
+
+                    buf.clear();
+                    continue;
+                }
+                Event::Eof => break,
+                _ => {
+                    buf.clear();
+                    continue;
+                }
+            };
+
+            match start.name().as_ref() {
+                b"osmChange" => {}
+                tag if ChangeAction::from_tag(tag).is_some() => {
+                    action = ChangeAction::from_tag(tag).filter(|_| !empty);
+                }
+                _ => {
+                    let positioned = action != Some(ChangeAction::Delete);
+                    let Some(element) = read_element(&mut reader, &start, empty, positioned)?
+                    else {
+                        buf.clear();
+                        continue;
+                    };
+
+                    let action = action.ok_or_else(|| {
+                        CodecError::MalformedXml(
+                            "element is not within a create, modify or delete action".to_string(),
+                        )
+                    })?;
+
+                    changes.push(Change { action, element });
+                }
+            }
+
+            buf.clear();
+        }
+
+        Ok(OsmChange { changes })
+    }
+
+    /// The nodes changed, alongside their action and tags.
+    pub fn nodes(&self) -> impl Iterator<Item = (ChangeAction, &Node<OsmEntryId>, &Tags)> {
+        self.changes
+            .iter()
+            .filter_map(|change| match &change.element {
+                ProcessedElement::Node(node, tags) => Some((change.action, node, tags)),
+                _ => None,
+            })
+    }
+
+    /// The ways changed, alongside their action.
+    pub fn ways(&self) -> impl Iterator<Item = (ChangeAction, &Way)> {
+        self.changes
+            .iter()
+            .filter_map(|change| match &change.element {
+                ProcessedElement::Way(way) => Some((change.action, way)),
+                _ => None,
+            })
+    }
+
+    /// The relations changed, alongside their action.
+    pub fn relations(&self) -> impl Iterator<Item = (ChangeAction, &Relation)> {
+        self.changes
+            .iter()
+            .filter_map(|change| match &change.element {
+                ProcessedElement::Relation(relation) => Some((change.action, relation)),
+                _ => None,
+            })
+    }
+}
+
+impl FromStr for OsmChange {
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_reader(s.as_bytes())
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(test)]
+
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::osm::element::variants::OsmEntryId;
+use crate::osm::relation::MemberType;
+use crate::osm::{ChangeAction, OsmChange};
+
+const CHANGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osmChange version="0.6" generator="osmosis">
+  <create>
+    <node id="10" version="1" lat="38.8977" lon="-77.0365">
+      <tag k="highway" v="traffic_signals"/>
+    </node>
+    <node id="11" version="1" lat="38.8990" lon="-77.0300"/>
+    <way id="20" version="1">
+      <nd ref="10"/>
+      <nd ref="11"/>
+      <tag k="highway" v="residential"/>
+      <tag k="name" v="Tom &amp; Jerry Lane"/>
+    </way>
+  </create>
+  <modify>
+    <node id="3" version="4" lat="38.9000" lon="-77.0400"/>
+    <relation id="30" version="2">
+      <member type="way" ref="20" role="forward"/>
+      <member type="node" ref="10" role=""/>
+      <tag k="type" v="route"/>
+    </relation>
+  </modify>
+  <delete>
+    <way id="21" version="3"/>
+    <node id="4" version="2"/>
+  </delete>
+</osmChange>
+"#;
+
+#[test]
+fn read_change() {
+    let change: OsmChange = CHANGE.parse().expect("could not read change");
+    assert_eq!(change.changes.len(), 7);
+
+    let nodes = change
+        .nodes()
+        .map(|(action, node, tags)| (action, node.id, tags.len()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        nodes,
+        vec![
+            (ChangeAction::Create, OsmEntryId::node(10), 1),
+            (ChangeAction::Create, OsmEntryId::node(11), 0),
+            (ChangeAction::Modify, OsmEntryId::node(3), 0),
+            (ChangeAction::Delete, OsmEntryId::node(4), 0),
+        ]
+    );
+
+    let (_, node, _) = change.nodes().next().unwrap();
+    assert_eq!(node.position.x(), -77.0365);
+    assert_eq!(node.position.y(), 38.8977);
+
+    let ways = change.ways().collect::<Vec<_>>();
+    assert_eq!(ways.len(), 2);
+
+    let (action, way) = ways[0];
+    assert_eq!(action, ChangeAction::Create);
+    assert_eq!(
+        way.refs().iter().map(|r| r.id).collect::<Vec<_>>(),
+        vec![OsmEntryId::node(10), OsmEntryId::node(11)]
+    );
+    assert_eq!(
+        way.tags().get("name").map(|name| name.as_str()),
+        Some("Tom & Jerry Lane")
+    );
+
+    let (action, way) = ways[1];
+    assert_eq!(action, ChangeAction::Delete);
+    assert_eq!(way.id(), OsmEntryId::way(21));
+    assert!(way.refs().is_empty());
+
+    let (action, relation) = change.relations().next().unwrap();
+    assert_eq!(action, ChangeAction::Modify);
+    assert_eq!(relation.route(), None);
+    assert_eq!(
+        relation
+            .refs
+            .iter()
+            .map(|r| (r.member_type, r.role.as_ref().map(|role| role.0.as_str())))
+            .collect::<Vec<_>>(),
+        vec![
+            (MemberType::Way, Some("forward")),
+            (MemberType::Node, Some(""))
+        ]
+    );
+}
+
+#[test]
+fn read_gzipped_change() {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(CHANGE.as_bytes()).unwrap();
+
+    let path = std::env::temp_dir().join("routers_codec_change.osc.gz");
+    std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+    let change = OsmChange::from_path(&path).expect("could not read change");
+    assert_eq!(change.changes.len(), 7);
+}
+
+#[test]
+fn reject_malformed_change() {
+    let missing_id = r#"<osmChange><create><node lat="1" lon="2"/></create></osmChange>"#;
+    assert!(missing_id.parse::<OsmChange>().is_err());
+
+    let outside_action = r#"<osmChange><node id="1" lat="1" lon="2"/></osmChange>"#;
+    assert!(outside_action.parse::<OsmChange>().is_err());
+
+    let truncated = r#"<osmChange><create><way id="1"><nd ref="2"/>"#;
+    assert!(truncated.parse::<OsmChange>().is_err());
+
+    // Only a deleted node may omit its position.
+    let unpositioned = r#"<osmChange><modify><node id="1" lat="1"/></modify></osmChange>"#;
+    assert!(unpositioned.parse::<OsmChange>().is_err());
+
+    let unpositioned = r#"<osmChange><create><node id="1"/></create></osmChange>"#;
+    assert!(unpositioned.parse::<OsmChange>().is_err());
+
+    let deleted = r#"<osmChange><delete><node id="1"/></delete></osmChange>"#;
+    assert_eq!(deleted.parse::<OsmChange>().unwrap().changes.len(), 1);
+}
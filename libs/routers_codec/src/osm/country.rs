@@ -0,0 +1,411 @@
+//! Resolution of the country in which a way lies, upon which
+//! implicit properties such as its default speed limit depend.
+
+use crate::osm::primitives::CountryCode;
+use geo::{Contains, MultiPolygon, Point, Rect, coord};
+
+/// Resolves the country containing a position, given as longitude and latitude.
+pub trait CountryResolver {
+    fn country(&self, point: Point) -> Option<CountryCode>;
+}
+
+/// A rectangle of `(min_lon, min_lat, max_lon, max_lat)`.
+type Bounds = (f64, f64, f64, f64);
+
+/// The coarse boundaries of each country, as rectangles lying within their interior.
+///
+/// The rectangles are drawn by hand, inset from the national borders of OpenStreetMap,
+/// and are checked against places either side of each border. Positions near a border
+/// are deliberately left unresolved, rather than risk attributing a way to its neighbour,
+/// in which case the way keeps only its explicit speed limits. Enclaves lying within a
+/// rectangle, such as the Vatican, resolve to the country surrounding them.
+const BOUNDARIES: &[(CountryCode, &[Bounds])] = &[
+    (
+        CountryCode::new(b"DE"),
+        &[
+            (7.3, 49.5, 12.0, 53.5),
+            (8.25, 47.85, 12.0, 49.5),
+            (12.0, 51.3, 14.0, 53.5),
+            (8.0, 53.5, 13.5, 54.5),
+        ],
+    ),
+    (
+        CountryCode::new(b"AT"),
+        &[
+            (13.5, 46.8, 16.0, 48.3),
+            // West of the March (Morava), which is Slovak on its eastern bank.
+            (16.0, 47.8, 16.75, 48.5),
+            // North of the Ahrntal, which reaches 47.09°N, being South Tyrol (IT).
+            (10.6, 47.15, 12.9, 47.35),
+        ],
+    ),
+    (CountryCode::new(b"CH"), &[(7.1, 46.5, 9.2, 47.35)]),
+    (
+        CountryCode::new(b"NL"),
+        &[(4.5, 51.6, 5.9, 53.2), (5.9, 52.0, 6.6, 53.2)],
+    ),
+    (
+        CountryCode::new(b"BE"),
+        &[
+            // South of Zeeuws-Vlaanderen, which is Dutch.
+            (3.3, 50.8, 5.4, 51.15),
+            (4.35, 51.15, 5.2, 51.25),
+            (4.2, 50.2, 5.6, 50.8),
+        ],
+    ),
+    (
+        CountryCode::new(b"FR"),
+        &[
+            (-1.0, 43.6, 4.0, 49.5),
+            (-4.6, 46.5, -1.0, 48.8),
+            (4.0, 43.1, 6.5, 46.0),
+            (4.0, 46.0, 5.8, 49.3),
+            (1.6, 49.5, 3.9, 50.3),
+        ],
+    ),
+    (
+        CountryCode::new(b"IT"),
+        &[
+            (8.5, 44.5, 12.3, 45.75),
+            (11.0, 40.0, 15.0, 43.7),
+            (15.0, 38.0, 17.5, 42.2),
+            (17.5, 39.8, 18.6, 41.0),
+            (12.4, 36.6, 15.7, 38.3),
+        ],
+    ),
+    (
+        CountryCode::new(b"ES"),
+        &[
+            (-6.1, 37.5, -1.0, 42.5),
+            (-8.8, 42.2, -1.9, 43.6),
+            (-1.0, 37.5, 0.5, 42.2),
+            (0.5, 40.5, 3.3, 42.2),
+        ],
+    ),
+    (CountryCode::new(b"PT"), &[(-8.8, 37.3, -7.5, 41.8)]),
+    (
+        CountryCode::new(b"PL"),
+        &[(15.2, 51.1, 23.0, 54.3), (18.9, 49.65, 22.5, 51.1)],
+    ),
+    (
+        CountryCode::new(b"CZ"),
+        &[(13.1, 49.3, 16.0, 50.3), (16.0, 49.1, 17.5, 49.9)],
+    ),
+    (
+        CountryCode::new(b"DK"),
+        &[(8.3, 55.0, 10.5, 57.5), (11.1, 54.6, 12.55, 56.1)],
+    ),
+    (
+        CountryCode::new(b"GB"),
+        &[
+            (-5.3, 50.0, 1.3, 51.0),
+            // Either side of the Isle of Man, which has no national speed limit.
+            (-5.0, 51.0, 1.8, 53.9),
+            (-4.1, 53.9, 1.8, 55.0),
+            (-6.0, 55.0, -1.5, 58.7),
+            // Northern Ireland
+            (-7.0, 54.5, -5.5, 55.2),
+        ],
+    ),
+    (
+        CountryCode::new(b"IE"),
+        &[(-10.0, 51.4, -6.0, 53.9), (-10.3, 53.9, -8.2, 55.4)],
+    ),
+    (
+        CountryCode::new(b"RU"),
+        &[
+            (33.0, 52.6, 48.0, 60.0),
+            (28.5, 56.3, 33.0, 59.8),
+            // Kaliningrad
+            (20.0, 54.5, 22.5, 55.0),
+        ],
+    ),
+    (CountryCode::new(b"AU"), &[(112.5, -44.0, 154.0, -11.5)]),
+    (CountryCode::new(b"NZ"), &[(166.0, -47.5, 179.0, -34.0)]),
+];
+
+/// The built-in, coarse boundaries of the countries for which
+/// [implicit speed limits](crate::osm::speed_limit::implicit) are known.
+///
+/// These are Austria, Belgium, Czechia, Denmark, France, Germany, Ireland, Italy,
+/// the Netherlands, Poland, Portugal, Russia, Spain, Switzerland, the United Kingdom,
+/// Australia and New Zealand. Every other country resolves to `None`.
+///
+/// This favours size over precision. Should an exact resolution be
+/// required, supply the boundaries through [`CountryPolygons`] instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CoarseBoundaries;
+
+impl CountryResolver for CoarseBoundaries {
+    fn country(&self, point: Point) -> Option<CountryCode> {
+        BOUNDARIES.iter().find_map(|(country, bounds)| {
+            bounds
+                .iter()
+                .any(|&(min_x, min_y, max_x, max_y)| {
+                    Rect::new(coord! { x: min_x, y: min_y }, coord! { x: max_x, y: max_y })
+                        .contains(&point)
+                })
+                .then_some(*country)
+        })
+    }
+}
+
+/// User-provided boundaries of each country, where the first to contain a position resolves it.
+#[derive(Debug, Clone, Default)]
+pub struct CountryPolygons(pub Vec<(CountryCode, MultiPolygon)>);
+
+impl CountryPolygons {
+    pub fn new(countries: impl IntoIterator<Item = (CountryCode, MultiPolygon)>) -> Self {
+        Self(countries.into_iter().collect())
+    }
+}
+
+impl CountryResolver for CountryPolygons {
+    fn country(&self, point: Point) -> Option<CountryCode> {
+        self.0
+            .iter()
+            .find(|(_, boundary)| boundary.contains(&point))
+            .map(|(country, _)| *country)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::polygon;
+
+    #[test]
+    fn test_coarse_boundaries() {
+        let resolve = |x, y| CoarseBoundaries.country(Point::new(x, y));
+
+        assert_eq!(resolve(13.40, 52.52), Some(CountryCode::new(b"DE"))); // Berlin
+        assert_eq!(resolve(-0.12, 51.50), Some(CountryCode::new(b"GB"))); // London
+        assert_eq!(resolve(2.35, 48.85), Some(CountryCode::new(b"FR"))); // Paris
+        assert_eq!(resolve(151.2, -33.87), Some(CountryCode::new(b"AU"))); // Sydney
+        assert_eq!(resolve(-40.0, 30.0), None); // Atlantic Ocean
+    }
+
+    #[test]
+    fn test_coarse_exclusions() {
+        let resolve = |x, y| CoarseBoundaries.country(Point::new(x, y));
+
+        assert_eq!(resolve(-2.97, 54.89), Some(CountryCode::new(b"GB"))); // Carlisle
+        assert_eq!(resolve(-4.48, 54.15), None); // Douglas, Isle of Man
+        assert_eq!(resolve(-4.55, 54.35), None); // Ramsey Bay, Isle of Man
+
+        assert_eq!(resolve(4.40, 51.21), Some(CountryCode::new(b"BE"))); // Antwerp
+        assert_eq!(resolve(3.80, 51.23), None); // Sas van Gent, Zeeuws-Vlaanderen
+        assert_eq!(resolve(3.53, 51.24), None); // Aardenburg, Zeeuws-Vlaanderen
+        assert_eq!(resolve(5.58, 51.27), None); // Budel, Noord-Brabant
+    }
+
+    #[test]
+    fn test_coarse_borders() {
+        // Places either side of a border, as the country they lie within,
+        // their position, and whether they are resolved or left unresolved.
+        let places = [
+            // Switzerland and Germany, whose border reaches 47.81°N at Bargen.
+            ("Bargen", *b"CH", (8.61, 47.79), false),
+            ("Blumberg", *b"DE", (8.53, 47.84), false),
+            ("Donaueschingen", *b"DE", (8.50, 47.95), true),
+            ("Konstanz", *b"DE", (9.18, 47.66), false),
+            ("Kreuzlingen", *b"CH", (9.17, 47.65), false),
+            ("Basel", *b"CH", (7.59, 47.56), false),
+            ("Lörrach", *b"DE", (7.66, 47.61), false),
+            // Austria and South Tyrol, whose Ahrntal reaches 47.09°N.
+            ("Westliche Zinkenspitze", *b"IT", (12.24, 47.09), false),
+            ("Kasern", *b"IT", (12.15, 47.06), false),
+            ("Brenner", *b"IT", (11.51, 47.00), false),
+            ("Gries am Brenner", *b"AT", (11.48, 47.04), false),
+            ("Sterzing", *b"IT", (11.43, 46.89), false),
+            ("Mayrhofen", *b"AT", (11.86, 47.17), true),
+            ("Gerlos", *b"AT", (12.03, 47.22), true),
+            ("Innsbruck", *b"AT", (11.39, 47.27), true),
+            // Austria and Germany.
+            ("Mittenwald", *b"DE", (11.26, 47.44), false),
+            ("Scharnitz", *b"AT", (11.26, 47.39), false),
+            ("Salzburg", *b"AT", (13.04, 47.80), false),
+            ("Freilassing", *b"DE", (12.98, 47.84), false),
+            ("Passau", *b"DE", (13.46, 48.57), false),
+            ("Schärding", *b"AT", (13.43, 48.46), false),
+            // Austria and its eastern neighbours.
+            ("Záhorská Ves", *b"SK", (16.85, 48.38), false),
+            ("Vienna", *b"AT", (16.37, 48.21), true),
+            ("Sopron", *b"HU", (16.59, 47.68), false),
+            ("Eisenstadt", *b"AT", (16.52, 47.85), true),
+            ("Bad Radkersburg", *b"AT", (15.99, 46.69), false),
+            ("Gornja Radgona", *b"SI", (15.99, 46.68), false),
+            ("Graz", *b"AT", (15.44, 47.07), true),
+            ("Maribor", *b"SI", (15.65, 46.56), false),
+            ("Mikulov", *b"CZ", (16.64, 48.81), false),
+            ("Laa an der Thaya", *b"AT", (16.39, 48.72), false),
+            // France, Luxembourg, Belgium and Germany.
+            ("Strasbourg", *b"FR", (7.75, 48.58), false),
+            ("Kehl", *b"DE", (7.81, 48.57), false),
+            ("Lauterbourg", *b"FR", (8.18, 48.97), false),
+            ("Karlsruhe", *b"DE", (8.40, 49.01), true),
+            ("Saarbrücken", *b"DE", (6.99, 49.23), false),
+            ("Forbach", *b"FR", (6.90, 49.19), false),
+            ("Trier", *b"DE", (6.64, 49.75), false),
+            ("Echternach", *b"LU", (6.42, 49.81), false),
+            ("Aachen", *b"DE", (6.08, 50.78), false),
+            ("Eupen", *b"BE", (6.03, 50.63), false),
+            // The Netherlands and Germany.
+            ("Bad Nieuweschans", *b"NL", (7.21, 53.18), false),
+            ("Emden", *b"DE", (7.21, 53.37), false),
+            ("Leer", *b"DE", (7.45, 53.23), true),
+            ("Venlo", *b"NL", (6.17, 51.37), false),
+            ("Enschede", *b"NL", (6.89, 52.22), false),
+            ("Gronau", *b"DE", (7.04, 52.21), false),
+            ("Groningen", *b"NL", (6.57, 53.22), false),
+            // Belgium and the Netherlands, including Zeeuws-Vlaanderen and Baarle.
+            ("Gent", *b"BE", (3.72, 51.05), true),
+            ("Eeklo", *b"BE", (3.56, 51.19), false),
+            ("Sluis", *b"NL", (3.39, 51.31), false),
+            ("Hulst", *b"NL", (4.05, 51.28), false),
+            ("Baarle-Hertog", *b"BE", (4.93, 51.44), false),
+            ("Maastricht", *b"NL", (5.69, 50.85), false),
+            ("Lanaken", *b"BE", (5.65, 50.89), false),
+            ("Tilburg", *b"NL", (5.09, 51.56), false),
+            // Belgium and France.
+            ("Mouscron", *b"BE", (3.21, 50.74), false),
+            ("Tourcoing", *b"FR", (3.16, 50.72), false),
+            ("Roisin", *b"BE", (3.69, 50.33), false),
+            ("Bavay", *b"FR", (3.79, 50.29), true),
+            ("Erquelinnes", *b"BE", (4.11, 50.31), false),
+            ("Jeumont", *b"FR", (4.10, 50.30), false),
+            // Germany, Poland, Czechia and Denmark.
+            ("Görlitz", *b"DE", (14.99, 51.15), false),
+            ("Zgorzelec", *b"PL", (15.00, 51.15), false),
+            ("Szczecin", *b"PL", (14.55, 53.43), false),
+            ("Cheb", *b"CZ", (12.37, 50.08), false),
+            ("Selb", *b"DE", (12.13, 50.17), false),
+            ("Zittau", *b"DE", (14.81, 50.90), false),
+            ("Náchod", *b"CZ", (16.16, 50.42), false),
+            ("Kudowa-Zdrój", *b"PL", (16.24, 50.44), false),
+            ("Międzylesie", *b"PL", (16.67, 50.15), false),
+            ("Padborg", *b"DK", (9.36, 54.83), false),
+            ("Flensburg", *b"DE", (9.44, 54.78), false),
+            ("Helsingør", *b"DK", (12.59, 56.03), false),
+            ("Helsingborg", *b"SE", (12.69, 56.05), false),
+            // Poland and its eastern and southern neighbours.
+            ("Kaliningrad", *b"RU", (20.51, 54.71), true),
+            ("Braniewo", *b"PL", (19.82, 54.38), false),
+            ("Elbląg", *b"PL", (19.40, 54.16), true),
+            ("Puńsk", *b"PL", (23.19, 54.25), false),
+            ("Suwałki", *b"PL", (22.93, 54.10), true),
+            ("Kalvarija", *b"LT", (23.22, 54.41), false),
+            ("Terespol", *b"PL", (23.62, 52.07), false),
+            ("Brest", *b"BY", (23.70, 52.10), false),
+            ("Przemyśl", *b"PL", (22.77, 49.78), false),
+            ("Lublin", *b"PL", (22.57, 51.25), true),
+            ("Zakopane", *b"PL", (19.95, 49.30), false),
+            ("Trstená", *b"SK", (19.61, 49.36), false),
+            ("Skalica", *b"SK", (17.23, 48.85), false),
+            // Russia and its western neighbours.
+            ("Sovetsk", *b"RU", (21.87, 55.08), false),
+            ("Panemunė", *b"LT", (21.88, 55.09), false),
+            ("Narva", *b"EE", (28.19, 59.38), false),
+            ("Ivangorod", *b"RU", (28.21, 59.37), false),
+            ("Vyborg", *b"RU", (28.75, 60.71), false),
+            ("Lappeenranta", *b"FI", (28.19, 61.06), false),
+            ("Zilupe", *b"LV", (28.12, 56.39), false),
+            ("Vitebsk", *b"BY", (30.20, 55.19), false),
+            ("Smolensk", *b"RU", (32.04, 54.78), false),
+            ("Chernihiv", *b"UA", (31.29, 51.49), false),
+            ("Bryansk", *b"RU", (34.36, 53.24), true),
+            // The Iberian Peninsula.
+            ("Hendaye", *b"FR", (-1.77, 43.36), false),
+            ("Irun", *b"ES", (-1.79, 43.34), false),
+            ("Puigcerdà", *b"ES", (1.93, 42.43), false),
+            ("Bourg-Madame", *b"FR", (1.95, 42.43), false),
+            ("Llívia", *b"ES", (1.98, 42.46), false),
+            ("Figueres", *b"ES", (2.96, 42.27), false),
+            ("Girona", *b"ES", (2.82, 41.98), true),
+            ("Andorra la Vella", *b"AD", (1.52, 42.51), false),
+            ("Miranda do Douro", *b"PT", (-6.27, 41.50), false),
+            ("Badajoz", *b"ES", (-6.97, 38.88), false),
+            ("Elvas", *b"PT", (-7.16, 38.88), false),
+            ("Ayamonte", *b"ES", (-7.40, 37.21), false),
+            ("Alcoutim", *b"PT", (-7.47, 37.47), false),
+            ("Tui", *b"ES", (-8.64, 42.05), false),
+            ("Valença", *b"PT", (-8.64, 42.03), false),
+            ("Vigo", *b"ES", (-8.72, 42.24), true),
+            ("Porto", *b"PT", (-8.61, 41.15), true),
+            // France, Switzerland, Italy and Slovenia.
+            ("Menton", *b"FR", (7.50, 43.78), false),
+            ("Ventimiglia", *b"IT", (7.61, 43.79), false),
+            ("Briançon", *b"FR", (6.64, 44.90), false),
+            ("Claviere", *b"IT", (6.75, 44.94), false),
+            ("Grenoble", *b"FR", (5.72, 45.19), true),
+            ("Geneva", *b"CH", (6.14, 46.20), false),
+            ("Annemasse", *b"FR", (6.24, 46.19), false),
+            ("Chancy", *b"CH", (5.97, 46.15), false),
+            ("Chiasso", *b"CH", (9.03, 45.83), false),
+            ("Como", *b"IT", (9.08, 45.81), false),
+            ("Milan", *b"IT", (9.19, 45.46), true),
+            ("Splügen", *b"CH", (9.32, 46.55), false),
+            ("Madesimo", *b"IT", (9.36, 46.43), false),
+            ("Buchs", *b"CH", (9.47, 47.17), false),
+            ("Feldkirch", *b"AT", (9.60, 47.24), false),
+            ("Gorizia", *b"IT", (13.62, 45.94), false),
+            ("Nova Gorica", *b"SI", (13.65, 45.96), false),
+            ("San Marino", *b"SM", (12.45, 43.94), false),
+            // The island of Ireland and the Channel.
+            ("Derry", *b"GB", (-7.32, 55.00), false),
+            ("Moville", *b"IE", (-7.04, 55.19), false),
+            ("Muff", *b"IE", (-7.27, 55.07), false),
+            ("Newry", *b"GB", (-6.34, 54.18), false),
+            ("Dundalk", *b"IE", (-6.40, 54.00), false),
+            ("Belleek", *b"GB", (-8.09, 54.48), false),
+            ("Ballyshannon", *b"IE", (-8.19, 54.50), false),
+            ("Belfast", *b"GB", (-5.93, 54.60), true),
+            ("Dublin", *b"IE", (-6.26, 53.35), true),
+            ("Calais", *b"FR", (1.85, 50.95), false),
+            ("Dover", *b"GB", (1.31, 51.13), true),
+            ("Boulogne-sur-Mer", *b"FR", (1.61, 50.73), false),
+            // Australia and Papua New Guinea.
+            ("Daru", *b"PG", (143.21, -9.07), false),
+            ("Thursday Island", *b"AU", (142.22, -10.58), false),
+        ];
+
+        for (place, country, (x, y), resolved) in places {
+            let expected = resolved.then_some(CountryCode::new(&country));
+            assert_eq!(
+                CoarseBoundaries.country(Point::new(x, y)),
+                expected,
+                "{place}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_coarse_uncovered() {
+        let resolve = |x, y| CoarseBoundaries.country(Point::new(x, y));
+
+        assert_eq!(resolve(6.13, 49.61), None); // Luxembourg
+        assert_eq!(resolve(17.11, 48.15), None); // Bratislava
+        assert_eq!(resolve(19.04, 47.50), None); // Budapest
+        assert_eq!(resolve(18.07, 59.33), None); // Stockholm
+        assert_eq!(resolve(-74.00, 40.71), None); // New York
+    }
+
+    #[test]
+    fn test_polygons() {
+        let resolver = CountryPolygons::new([(
+            CountryCode::new(b"LU"),
+            MultiPolygon::new(vec![polygon![
+                (x: 5.7, y: 49.4),
+                (x: 6.5, y: 49.4),
+                (x: 6.5, y: 50.2),
+                (x: 5.7, y: 50.2),
+            ]]),
+        )]);
+
+        assert_eq!(
+            resolver.country(Point::new(6.13, 49.61)),
+            Some(CountryCode::new(b"LU"))
+        );
+        assert_eq!(resolver.country(Point::new(7.0, 49.61)), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Deduplication of strings which recur across many elements,
+//! such as the names of the ways along a long road.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+/// The number of independently locked shards, such that
+/// elements processed in parallel rarely contend.
+const SHARDS: usize = 16;
+
+/// The number of hashes a shard holds before its dropped strings are first removed.
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// A thread-safe store of shared strings, where interning an equal
+/// string twice yields the same allocation.
+///
+/// Only weak references are held, such that a string is freed once the last
+/// of its holders, such as the metadata of a graph, is dropped, rather than
+/// living for as long as the interner.
+#[derive(Debug, Default)]
+pub struct Interner {
+    hasher: RandomState,
+    shards: [Mutex<Shard>; SHARDS],
+}
+
+#[derive(Debug, Default)]
+struct Shard {
+    /// The strings by their hash, of which there is rarely more than one.
+    strings: HashMap<u64, Vec<Weak<str>>>,
+    /// The number of hashes held once dropped strings were last removed.
+    swept: usize,
+}
+
+impl Shard {
+    /// Removes the strings which have since been dropped, once the shard has doubled
+    /// in size since this was last done, such that the cost is amortised over insertions.
+    fn sweep(&mut self) {
+        if self.strings.len() < SWEEP_THRESHOLD.max(self.swept * 2) {
+            return;
+        }
+
+        self.strings.retain(|_, strings| {
+            strings.retain(|string| string.strong_count() > 0);
+            !strings.is_empty()
+        });
+        self.swept = self.strings.len();
+    }
+}
+
+impl Interner {
+    /// The shared allocation of the string, allocating it should it not be interned.
+    pub fn intern(&self, value: &str) -> Arc<str> {
+        let hash = self.hasher.hash_one(value);
+        let mut shard = self.shards[hash as usize % SHARDS]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let strings = shard.strings.entry(hash).or_default();
+        if let Some(interned) = strings
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|interned| **interned == *value)
+        {
+            return interned;
+        }
+
+        let interned = Arc::<str>::from(value);
+        strings.retain(|string| string.strong_count() > 0);
+        strings.push(Arc::downgrade(&interned));
+
+        shard.sweep();
+        interned
+    }
+
+    /// The number of distinct strings interned which are still held.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                shard
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .strings
+                    .values()
+                    .flatten()
+                    .filter(|string| string.strong_count() > 0)
+                    .count()
+            })
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deduplicates() {
+        let interner = Interner::default();
+
+        let first = interner.intern("High Street");
+        let second = interner.intern("High Street");
+        let other = interner.intern("Low Street");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn frees_dropped() {
+        let interner = Interner::default();
+
+        let kept = interner.intern("High Street");
+        drop(interner.intern("Low Street"));
+        assert_eq!(interner.len(), 1);
+
+        // Strings interned in passing do not accumulate.
+        for index in 0..SHARDS * SWEEP_THRESHOLD * 4 {
+            interner.intern(&index.to_string());
+        }
+
+        let held = interner
+            .shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().strings.len())
+            .sum::<usize>();
+
+        assert!(held <= SHARDS * SWEEP_THRESHOLD * 2);
+        assert!(Arc::ptr_eq(&kept, &interner.intern("High Street")));
+    }
+}
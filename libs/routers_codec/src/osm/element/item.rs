@@ -7,7 +7,8 @@ use std::vec;
 use tracing::debug;
 
 use crate::osm;
-use crate::osm::element::variants::{OsmEntryId, Relation, Way};
+use crate::osm::element::BlockStrings;
+use crate::osm::element::variants::{OsmEntryId, Relation, Taggable, Tags, Way};
 use crate::primitive::Node;
 
 #[derive(Clone)]
@@ -20,14 +21,22 @@ pub enum Element<'a> {
 
 #[derive(Clone)]
 pub enum ProcessedElement {
-    Node(Node<OsmEntryId>),
+    /// A node, and its tags, which are only decoded upon request
+    /// as few nodes are tagged, and are otherwise left empty.
+    Node(Node<OsmEntryId>, Tags),
     Way(Way),
     Relation(Relation),
 }
 
 impl ProcessedElement {
     #[inline]
-    pub(crate) fn from_raw(element: Element, block: &osm::PrimitiveBlock) -> Vec<ProcessedElement> {
+    pub(crate) fn from_raw(
+        element: Element,
+        strings: &BlockStrings,
+        node_tags: bool,
+    ) -> Vec<ProcessedElement> {
+        let block = strings.block();
+
         #[cfg(feature = "tracing")]
         if block.lat_offset.is_some() || block.lon_offset.is_some() || block.granularity.is_some() {
             debug!(
@@ -40,16 +49,29 @@ impl ProcessedElement {
         let granularity = block.granularity.unwrap_or(100);
 
         match element {
+            Element::DenseNodes(dense_nodes) if node_tags => {
+                Node::from_dense(dense_nodes, granularity)
+                    .zip(Tags::from_dense(dense_nodes, strings))
+                    .map(|(node, tags)| ProcessedElement::Node(node, tags))
+                    .collect()
+            }
             Element::DenseNodes(dense_nodes) => Node::from_dense(dense_nodes, granularity)
-                .map(ProcessedElement::Node)
+                .map(|node| ProcessedElement::Node(node, Tags::default()))
                 .collect(),
-            Element::Node(node) => vec![ProcessedElement::Node(Node::from(node))],
-            Element::Way(way) => vec![ProcessedElement::Way(Way::from_raw(way, block))],
-            Element::Relation(_relation) => {
-                vec![]
-                // ProcessedElement::Relation(Relation::from_raw(
-                //     relation, block,
-                // ))
+            Element::Node(node) => {
+                let tags = if node_tags {
+                    node.tags(strings)
+                } else {
+                    Tags::default()
+                };
+
+                vec![ProcessedElement::Node(Node::from(node), tags)]
+            }
+            Element::Way(way) => vec![ProcessedElement::Way(Way::from_raw(way, strings))],
+            Element::Relation(relation) => {
+                vec![ProcessedElement::Relation(Relation::from_raw(
+                    relation, strings,
+                ))]
             }
         }
     }
@@ -4,9 +4,10 @@
 use rayon::iter::ParallelIterator;
 use std::path::PathBuf;
 
-use crate::osm::block::iterator::BlockIterator;
+use crate::osm::block::iterator::{BlockIterator, DecodeMode, SkippedBlobs};
 use crate::osm::element::item::Element;
 use crate::osm::error::CodecError;
+use crate::osm::header::PbfHeader;
 use crate::osm::parallel::Parallel;
 
 pub struct ElementIterator {
@@ -14,31 +15,68 @@ pub struct ElementIterator {
 }
 
 impl ElementIterator {
+    /// Streams the elements of the file at the given path.
     pub fn new(path: PathBuf) -> Result<ElementIterator, CodecError> {
         Ok(ElementIterator {
             iter: BlockIterator::new(path)?,
         })
     }
+
+    /// Reads the elements of the file at the given path by memory-mapping it.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process,
+    /// whilst it is mapped, as [`BlobIterator::mmap`](crate::osm::BlobIterator::mmap) requires.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn mmap(path: PathBuf) -> Result<ElementIterator, CodecError> {
+        Ok(ElementIterator {
+            iter: unsafe { BlockIterator::mmap(path) }?,
+        })
+    }
+
+    /// The header of the file, if it has one.
+    pub fn header(&self) -> Option<&PbfHeader> {
+        self.iter.header()
+    }
+
+    /// Sets how blobs which cannot be read or decoded are handled.
+    pub fn mode(self, mode: DecodeMode) -> Self {
+        ElementIterator {
+            iter: self.iter.mode(mode),
+        }
+    }
+
+    /// The blobs skipped in [`DecodeMode::Lenient`], counted as they are decoded.
+    pub fn skipped(&self) -> SkippedBlobs {
+        self.iter.skipped()
+    }
+
+    /// Reads the elements of the given blocks.
+    pub fn from_blocks(iter: BlockIterator) -> ElementIterator {
+        ElementIterator { iter }
+    }
 }
 
 impl Parallel for ElementIterator {
     type Item<'a> = Element<'a>;
 
-    fn for_each<F>(mut self, f: F)
+    fn for_each<F>(self, f: F) -> Result<(), CodecError>
     where
         F: for<'a> Fn(Element<'a>) + Send + Sync,
     {
-        self.iter.par_iter().for_each(|mut block| {
-            block.raw_par_iter().for_each(&f);
+        self.iter.par_iter().try_for_each(|block| {
+            block?.raw_par_iter().for_each(&f);
+            Ok(())
         })
     }
 
     fn map_red<Map, Reduce, Identity, T>(
-        mut self,
+        self,
         map_op: Map,
         red_op: Reduce,
         ident: Identity,
-    ) -> T
+    ) -> Result<T, CodecError>
     where
         Map: for<'a> Fn(Element<'a>) -> T + Send + Sync,
         Reduce: Fn(T, T) -> T + Send + Sync,
@@ -47,16 +85,16 @@ impl Parallel for ElementIterator {
     {
         self.iter
             .par_iter()
-            .map(|mut block| block.raw_par_iter().map(&map_op).reduce(&ident, &red_op))
-            .reduce(&ident, &red_op)
+            .map(|block| Ok(block?.raw_par_iter().map(&map_op).reduce(&ident, &red_op)))
+            .try_reduce(&ident, |a, b| Ok(red_op(a, b)))
     }
 
     fn par_red<Reduce, Identity, Combine, T>(
-        mut self,
+        self,
         fold_op: Reduce,
         combine: Combine,
         ident: Identity,
-    ) -> T
+    ) -> Result<T, CodecError>
     where
         Reduce: for<'a> Fn(T, Element<'a>) -> T + Send + Sync,
         Identity: Fn() -> T + Send + Sync,
@@ -65,12 +103,12 @@ impl Parallel for ElementIterator {
     {
         self.iter
             .par_iter()
-            .map(|mut block| {
-                block
+            .map(|block| {
+                Ok(block?
                     .raw_par_iter()
                     .fold(&ident, &fold_op)
-                    .reduce(&ident, &combine)
+                    .reduce(&ident, &combine))
             })
-            .reduce(&ident, &combine)
+            .try_reduce(&ident, |a, b| Ok(combine(a, b)))
     }
 }
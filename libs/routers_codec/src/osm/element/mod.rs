@@ -1,14 +1,18 @@
 //! Element and ProcessedElement iterator, and item definitions
 
+pub mod intern;
 pub mod item;
 pub mod iterator;
 pub mod processed_iterator;
+pub mod strings;
 
 #[doc(hidden)]
 mod test;
 #[doc(hidden)]
 pub mod variants;
 
+#[doc(inline)]
+pub use intern::Interner;
 #[doc(inline)]
 pub use item::Element;
 #[doc(inline)]
@@ -18,6 +22,8 @@ pub use iterator::ElementIterator;
 #[doc(inline)]
 pub use processed_iterator::ProcessedElementIterator;
 #[doc(inline)]
+pub use strings::BlockStrings;
+#[doc(inline)]
 pub use variants::OsmEntryId;
 #[doc(inline)]
 pub use variants::common::*;
@@ -4,41 +4,90 @@
 use rayon::iter::ParallelIterator;
 use std::path::PathBuf;
 
-use crate::osm::block::iterator::BlockIterator;
+use crate::osm::block::iterator::{BlockIterator, DecodeMode, SkippedBlobs};
 use crate::osm::element::item::ProcessedElement;
 use crate::osm::error::CodecError;
+use crate::osm::header::PbfHeader;
 use crate::osm::parallel::Parallel;
 
 pub struct ProcessedElementIterator {
     iter: BlockIterator,
+
+    /// Whether the tags of nodes are decoded.
+    node_tags: bool,
 }
 
 impl ProcessedElementIterator {
+    /// Streams the elements of the file at the given path.
     pub fn new(path: PathBuf) -> Result<ProcessedElementIterator, CodecError> {
-        Ok(ProcessedElementIterator {
-            iter: BlockIterator::new(path)?,
-        })
+        Ok(Self::from_blocks(BlockIterator::new(path)?))
+    }
+
+    /// Reads the elements of the file at the given path by memory-mapping it.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process,
+    /// whilst it is mapped, as [`BlobIterator::mmap`](crate::osm::BlobIterator::mmap) requires.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn mmap(path: PathBuf) -> Result<ProcessedElementIterator, CodecError> {
+        Ok(Self::from_blocks(unsafe { BlockIterator::mmap(path) }?))
+    }
+
+    /// The header of the file, if it has one.
+    pub fn header(&self) -> Option<&PbfHeader> {
+        self.iter.header()
+    }
+
+    /// Sets how blobs which cannot be read or decoded are handled.
+    pub fn mode(self, mode: DecodeMode) -> Self {
+        Self {
+            iter: self.iter.mode(mode),
+            ..self
+        }
+    }
+
+    /// The blobs skipped in [`DecodeMode::Lenient`], counted as they are decoded.
+    pub fn skipped(&self) -> SkippedBlobs {
+        self.iter.skipped()
+    }
+
+    /// Reads the elements of the given blocks.
+    pub fn from_blocks(iter: BlockIterator) -> ProcessedElementIterator {
+        ProcessedElementIterator {
+            iter,
+            node_tags: false,
+        }
+    }
+
+    /// Sets whether the tags of nodes are decoded.
+    ///
+    /// Few nodes are tagged, such as barriers and traffic signals,
+    /// so their tags are not decoded by default to save memory.
+    pub fn node_tags(self, node_tags: bool) -> Self {
+        Self { node_tags, ..self }
     }
 }
 
 impl Parallel for ProcessedElementIterator {
     type Item<'a> = ProcessedElement;
 
-    fn for_each<F>(mut self, f: F)
+    fn for_each<F>(self, f: F) -> Result<(), CodecError>
     where
         F: Fn(ProcessedElement) + Send + Sync,
     {
-        self.iter.par_iter().for_each(|mut block| {
-            block.par_iter().for_each(&f);
+        self.iter.par_iter().try_for_each(|block| {
+            block?.par_iter_with(self.node_tags).for_each(&f);
+            Ok(())
         })
     }
 
     fn map_red<Map, Reduce, Identity, T>(
-        mut self,
+        self,
         map_op: Map,
         red_op: Reduce,
         ident: Identity,
-    ) -> T
+    ) -> Result<T, CodecError>
     where
         Map: Fn(ProcessedElement) -> T + Send + Sync,
         Reduce: Fn(T, T) -> T + Send + Sync,
@@ -47,16 +96,21 @@ impl Parallel for ProcessedElementIterator {
     {
         self.iter
             .par_iter()
-            .map(|mut block| block.par_iter().map(&map_op).reduce(&ident, &red_op))
-            .reduce(&ident, &red_op)
+            .map(|block| {
+                Ok(block?
+                    .par_iter_with(self.node_tags)
+                    .map(&map_op)
+                    .reduce(&ident, &red_op))
+            })
+            .try_reduce(&ident, |a, b| Ok(red_op(a, b)))
     }
 
     fn par_red<Reduce, Identity, Combine, T>(
-        mut self,
+        self,
         fold_op: Reduce,
         combine: Combine,
         ident: Identity,
-    ) -> T
+    ) -> Result<T, CodecError>
     where
         Reduce: Fn(T, ProcessedElement) -> T + Send + Sync,
         Identity: Fn() -> T + Send + Sync,
@@ -65,12 +119,12 @@ impl Parallel for ProcessedElementIterator {
     {
         self.iter
             .par_iter()
-            .map(|mut block| {
-                block
-                    .par_iter()
+            .map(|block| {
+                Ok(block?
+                    .par_iter_with(self.node_tags)
                     .fold(&ident, &fold_op)
-                    .reduce(&ident, &combine)
+                    .reduce(&ident, &combine))
             })
-            .reduce(&ident, &combine)
+            .try_reduce(&ident, |a, b| Ok(combine(a, b)))
     }
 }
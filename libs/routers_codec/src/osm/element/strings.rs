@@ -0,0 +1,130 @@
+//! Decoding of the string table of a block, shared by all of its elements.
+
+use crate::osm::PrimitiveBlock;
+use crate::osm::element::Interner;
+use crate::osm::element::variants::TagString;
+
+use std::sync::{LazyLock, OnceLock};
+
+/// Tag keys, such as `highway`, which recur across every block of a file.
+static KEYS: LazyLock<Interner> = LazyLock::new(Interner::default);
+
+/// The string table of a block, whose strings are decoded upon first use
+/// and shared by every element referencing them, rather than being allocated
+/// once per reference.
+///
+/// Keys are additionally interned across blocks, such that the tags of all
+/// elements share a single allocation of each key for as long as any holds it.
+pub struct BlockStrings<'a> {
+    block: &'a PrimitiveBlock,
+    strings: Box<[OnceLock<TagString>]>,
+}
+
+impl<'a> BlockStrings<'a> {
+    pub fn new(block: &'a PrimitiveBlock) -> Self {
+        Self {
+            block,
+            strings: (0..block.stringtable.s.len())
+                .map(|_| OnceLock::new())
+                .collect(),
+        }
+    }
+
+    /// The block the strings belong to.
+    #[inline]
+    pub fn block(&self) -> &'a PrimitiveBlock {
+        self.block
+    }
+
+    /// The string at the index, decoding it should it not yet have been.
+    ///
+    /// Returns `None` should the index lie beyond the string table, as in a corrupt block.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<TagString> {
+        let string = self.strings.get(index)?;
+        Some(
+            string
+                .get_or_init(|| TagString::recover(index, self.block))
+                .clone(),
+        )
+    }
+
+    /// The tag key at the index, interned should it not yet have been decoded.
+    ///
+    /// Returns `None` should the index lie beyond the string table, as in a corrupt block.
+    #[inline]
+    pub fn key(&self, index: usize) -> Option<TagString> {
+        let string = self.strings.get(index)?;
+        Some(
+            string
+                .get_or_init(|| {
+                    TagString::from(
+                        KEYS.intern(&String::from_utf8_lossy(&self.block.stringtable.s[index])),
+                    )
+                })
+                .clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::element::Tags;
+    use crate::osm::model::StringTable;
+    use std::sync::Arc;
+
+    /// A block whose string table holds the strings, in order.
+    fn block(strings: &[&str]) -> PrimitiveBlock {
+        PrimitiveBlock {
+            stringtable: StringTable {
+                s: strings
+                    .iter()
+                    .map(|s| s.as_bytes().to_vec().into())
+                    .collect(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shares_strings() {
+        let block = block(&["", "highway", "residential"]);
+
+        let table = BlockStrings::new(&block);
+        let other = BlockStrings::new(&block);
+
+        let (value, key) = (table.get(2).unwrap(), table.key(1).unwrap());
+        assert!(Arc::ptr_eq(value.as_arc(), table.get(2).unwrap().as_arc()));
+        assert!(Arc::ptr_eq(key.as_arc(), other.key(1).unwrap().as_arc()));
+        assert_eq!(value.as_str(), "residential");
+    }
+
+    #[test]
+    fn recovers_tags() {
+        let block = block(&["", "name", "highway", "residential", "oneway", "yes"]);
+
+        let table = BlockStrings::new(&block);
+        let tags = Tags::from_block([(&4, &5), (&2, &3), (&4, &3)].into_iter(), &table);
+
+        // The last of a duplicated key takes precedence.
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.get("oneway").map(|v| v.as_str()), Some("residential"));
+        assert_eq!(tags.road_tag(), Some("residential"));
+        assert!(!tags.contains_key("name"));
+    }
+
+    #[test]
+    fn skips_out_of_range_indices() {
+        let block = block(&["", "highway", "residential"]);
+
+        let table = BlockStrings::new(&block);
+        assert!(table.get(3).is_none());
+        assert!(table.key(u32::MAX as usize).is_none());
+
+        // Only the tag whose key and value are both within the table is kept.
+        let tags = Tags::from_block([(&1, &2), (&9, &2), (&1, &9)].into_iter(), &table);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags.road_tag(), Some("residential"));
+    }
+}
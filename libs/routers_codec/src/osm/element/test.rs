@@ -6,7 +6,9 @@ use crate::osm::element::item::Element;
 use crate::osm::element::item::ProcessedElement;
 use crate::osm::element::iterator::ElementIterator;
 use crate::osm::element::processed_iterator::ProcessedElementIterator;
+use crate::osm::element::strings::BlockStrings;
 use crate::osm::parallel::Parallel;
+use crate::osm::{DenseNodes, PrimitiveBlock, StringTable};
 
 use log::info;
 use std::time::Instant;
@@ -18,7 +20,8 @@ fn try_into_iter() {
 
     iter.for_each(|item| {
         info!("Element: {}", item.str_type());
-    });
+    })
+    .expect("Could not decode blocks");
 }
 
 #[test]
@@ -27,16 +30,18 @@ fn iter_count() {
     let iter = ElementIterator::new(path).expect("Could not create iterator");
     let now = Instant::now();
 
-    let nodes = iter.map_red(
-        |item| match item {
-            Element::Way(_) => 0,
-            Element::Node(_) => 1,
-            Element::Relation(_) => 0,
-            Element::DenseNodes(_) => 0,
-        },
-        |a, b| a + b,
-        || 0,
-    );
+    let nodes = iter
+        .map_red(
+            |item| match item {
+                Element::Way(_) => 0,
+                Element::Node(_) => 1,
+                Element::Relation(_) => 0,
+                Element::DenseNodes(_) => 0,
+            },
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("Could not decode blocks");
 
     println!("There are {nodes} nodes");
     println!("Took: {}ms", now.elapsed().as_micros() / 1000)
@@ -49,15 +54,53 @@ fn processed_iter_count() {
 
     let now = Instant::now();
 
-    let nodes = iter.map_red(
-        |item| match item {
-            ProcessedElement::Node(_) => 1,
-            _ => 0,
-        },
-        |a, b| a + b,
-        || 0,
-    );
+    let nodes = iter
+        .map_red(
+            |item| match item {
+                ProcessedElement::Node(..) => 1,
+                _ => 0,
+            },
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("Could not decode blocks");
 
     println!("There are {nodes} nodes");
     println!("Took: {}ms", now.elapsed().as_micros() / 1000)
 }
+
+#[test]
+fn dense_nodes_missing_delimiters() {
+    let block = PrimitiveBlock {
+        stringtable: StringTable {
+            s: ["", "barrier", "bollard"]
+                .iter()
+                .map(|s| s.as_bytes().to_vec().into())
+                .collect(),
+        },
+        ..Default::default()
+    };
+
+    // Three nodes, of which only the first has its tags delimited.
+    let nodes = DenseNodes {
+        id: vec![1, 1, 1],
+        lat: vec![0, 1, 1],
+        lon: vec![0, 1, 1],
+        keys_vals: vec![1, 2, 0],
+        ..Default::default()
+    };
+
+    let strings = BlockStrings::new(&block);
+    let elements = ProcessedElement::from_raw(Element::DenseNodes(&nodes), &strings, true);
+
+    let tags = elements
+        .iter()
+        .map(|element| match element {
+            ProcessedElement::Node(_, tags) => tags.get("barrier").map(|v| v.as_str()),
+            _ => panic!("expected a node"),
+        })
+        .collect::<Vec<_>>();
+
+    // The trailing nodes are untagged, rather than dropped.
+    assert_eq!(tags, vec![Some("bollard"), None, None]);
+}
@@ -8,15 +8,17 @@ pub use relation::*;
 pub use way::*;
 
 pub mod common {
-    #[cfg(debug_assertions)]
     use crate::osm::relation::MemberType;
 
     use crate::osm::PrimitiveBlock;
     use crate::primitive::Entry;
 
+    use crate::osm::element::BlockStrings;
+
     use std::str::FromStr;
+    use std::sync::Arc;
     use std::{
-        collections::HashMap,
+        borrow::Borrow,
         hash::{Hash, Hasher},
         ops::{Add, Deref},
     };
@@ -43,12 +45,12 @@ pub mod common {
         "road",
     ];
 
+    /// The identifier of an element. The type of the element it identifies
+    /// is not held, as a [`Reference`] carries the type of its member.
     #[derive(Clone, Copy, Debug, Eq, PartialOrd, Ord)]
-    #[cfg_attr(not(debug_assertions), repr(transparent))]
+    #[repr(transparent)]
     pub struct OsmEntryId {
         pub identifier: i64,
-        #[cfg(debug_assertions)]
-        variant: MemberType,
     }
 
     impl Entry for OsmEntryId {
@@ -65,20 +67,12 @@ pub mod common {
     }
 
     impl OsmEntryId {
-        pub const fn new(id: i64, #[cfg(debug_assertions)] variant: MemberType) -> OsmEntryId {
-            OsmEntryId {
-                identifier: id,
-                #[cfg(debug_assertions)]
-                variant,
-            }
+        pub const fn new(id: i64) -> OsmEntryId {
+            OsmEntryId { identifier: id }
         }
 
         pub const fn null() -> OsmEntryId {
-            OsmEntryId {
-                identifier: OSM_NULL_SENTINEL,
-                #[cfg(debug_assertions)]
-                variant: MemberType::Node,
-            }
+            OsmEntryId::new(OSM_NULL_SENTINEL)
         }
 
         #[inline]
@@ -88,20 +82,17 @@ pub mod common {
 
         #[inline]
         pub const fn node(identifier: i64) -> OsmEntryId {
-            OsmEntryId {
-                identifier,
-                #[cfg(debug_assertions)]
-                variant: MemberType::Node,
-            }
+            OsmEntryId::new(identifier)
         }
 
         #[inline]
         pub const fn way(identifier: i64) -> OsmEntryId {
-            OsmEntryId {
-                identifier,
-                #[cfg(debug_assertions)]
-                variant: MemberType::Way,
-            }
+            OsmEntryId::new(identifier)
+        }
+
+        #[inline]
+        pub const fn relation(identifier: i64) -> OsmEntryId {
+            OsmEntryId::new(identifier)
         }
     }
 
@@ -109,11 +100,7 @@ pub mod common {
         type Output = OsmEntryId;
 
         fn add(self, other: i64) -> Self::Output {
-            OsmEntryId {
-                identifier: self.identifier + other,
-                #[cfg(debug_assertions)]
-                variant: self.variant,
-            }
+            OsmEntryId::new(self.identifier + other)
         }
     }
 
@@ -143,6 +130,7 @@ pub mod common {
     #[derive(Clone, Debug)]
     pub struct Reference {
         pub id: OsmEntryId,
+        pub member_type: MemberType,
         pub role: Option<Role>,
     }
 
@@ -161,19 +149,28 @@ pub mod common {
     impl Eq for Reference {}
 
     impl Reference {
-        pub const fn new(id: OsmEntryId, role: Option<Role>) -> Self {
-            Reference { id, role }
+        pub const fn new(id: OsmEntryId, member_type: MemberType, role: Option<Role>) -> Self {
+            Reference {
+                id,
+                member_type,
+                role,
+            }
         }
 
         #[inline]
-        pub const fn without_role(id: OsmEntryId) -> Self {
-            Reference { id, role: None }
+        pub const fn without_role(id: OsmEntryId, member_type: MemberType) -> Self {
+            Reference {
+                id,
+                member_type,
+                role: None,
+            }
         }
 
         #[inline]
-        pub const fn with_role(id: OsmEntryId, role: Role) -> Self {
+        pub const fn with_role(id: OsmEntryId, member_type: MemberType, role: Role) -> Self {
             Reference {
                 id,
+                member_type,
                 role: Some(role),
             }
         }
@@ -188,46 +185,44 @@ pub mod common {
     pub struct Intermediate<'a> {
         pub(crate) role: &'a i32,
         pub(crate) index: &'a i64,
-        #[cfg(debug_assertions)]
         pub(crate) member_type: &'a i32,
     }
 
     pub struct IntermediateRole {
         role: Option<Role>,
         index: i64,
-        #[cfg(debug_assertions)]
         member_type: MemberType,
     }
 
     pub trait Referential {
-        fn indices(&self) -> impl Iterator<Item = ReferenceKey>;
+        fn indices(&self) -> impl Iterator<Item = ReferenceKey<'_>>;
 
-        fn references(&self, block: &PrimitiveBlock) -> References {
+        fn references(&self, strings: &BlockStrings) -> References {
             self.indices()
                 .fold(vec![], |mut prior, intermediate| {
-                    #[cfg(debug_assertions)]
-                    let Intermediate { member_type, .. } = intermediate;
-                    let Intermediate { role, index, .. } = intermediate;
+                    let Intermediate {
+                        role,
+                        index,
+                        member_type,
+                    } = intermediate;
 
                     let index = index
                         + prior
                             .last()
                             .map_or(&0i64, |IntermediateRole { index, .. }| index);
 
-                    let role = if *role == -1 {
-                        None
-                    } else {
-                        Some(Role(TagString::recover(*role as usize, block)))
-                    };
+                    // A role of `-1`, or one beyond the string table, is absent.
+                    let role = usize::try_from(*role)
+                        .ok()
+                        .and_then(|role| strings.get(role))
+                        .map(Role);
 
-                    #[cfg(debug_assertions)]
                     let member_type =
                         MemberType::try_from(*member_type).unwrap_or(MemberType::Node);
 
                     prior.push(IntermediateRole {
                         role,
                         index,
-                        #[cfg(debug_assertions)]
                         member_type,
                     });
 
@@ -236,12 +231,8 @@ pub mod common {
                 .into_iter()
                 // All nodes in a Way are `Node` types, therefore navigable.
                 .map(|intermediate| {
-                    let entry = OsmEntryId::new(
-                        intermediate.index,
-                        #[cfg(debug_assertions)]
-                        intermediate.member_type,
-                    );
-                    Reference::new(entry, intermediate.role)
+                    let entry = OsmEntryId::new(intermediate.index);
+                    Reference::new(entry, intermediate.member_type, intermediate.role)
                 })
                 .collect::<Vec<_>>()
                 .into()
@@ -262,26 +253,41 @@ pub mod common {
         }
     }
 
-    #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Hash)]
-    pub struct TagString(String);
+    /// A tag key or value, shared between every element of a
+    /// block referencing it, as recovered by [`BlockStrings`].
+    #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+    pub struct TagString(Arc<str>);
 
     impl Deref for TagString {
-        type Target = String;
+        type Target = str;
 
         fn deref(&self) -> &Self::Target {
             &self.0
         }
     }
 
+    /// Permits looking up tags by `&str`, without allocating a [`TagString`].
+    impl Borrow<str> for TagString {
+        fn borrow(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl From<Arc<str>> for TagString {
+        fn from(s: Arc<str>) -> Self {
+            TagString(s)
+        }
+    }
+
     impl From<String> for TagString {
         fn from(s: String) -> Self {
-            TagString(s)
+            TagString(Arc::from(s))
         }
     }
 
     impl From<&str> for TagString {
         fn from(s: &str) -> Self {
-            TagString(s.to_string())
+            TagString(Arc::from(s))
         }
     }
 
@@ -291,9 +297,33 @@ pub mod common {
         pub(crate) const JUNCTION: &'static str = "junction";
         pub(crate) const LANES: &'static str = "lanes";
         pub(crate) const MAX_SPEED: &'static str = "maxspeed";
+        pub(crate) const MAX_SPEED_TYPE: &'static str = "maxspeed:type";
+        pub(crate) const SOURCE_MAX_SPEED: &'static str = "source:maxspeed";
+        pub(crate) const ZONE_MAX_SPEED: &'static str = "zone:maxspeed";
+        pub(crate) const BARRIER: &'static str = "barrier";
+        pub(crate) const CROSSING: &'static str = "crossing";
+        pub(crate) const SURFACE: &'static str = "surface";
+        pub(crate) const SMOOTHNESS: &'static str = "smoothness";
+        pub(crate) const TRACK_TYPE: &'static str = "tracktype";
+        pub(crate) const INCLINE: &'static str = "incline";
+        pub(crate) const WIDTH: &'static str = "width";
+        pub(crate) const TOLL: &'static str = "toll";
+        pub(crate) const ROUTE: &'static str = "route";
+        pub(crate) const DURATION: &'static str = "duration";
 
         pub fn recover(k: usize, block: &PrimitiveBlock) -> TagString {
-            TagString::from(String::from_utf8_lossy(&block.stringtable.s[k]).into_owned())
+            TagString::from(String::from_utf8_lossy(&block.stringtable.s[k]).as_ref())
+        }
+
+        #[inline]
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        /// The shared allocation of the string.
+        #[inline]
+        pub fn as_arc(&self) -> &Arc<str> {
+            &self.0
         }
 
         pub fn parse<F: FromStr>(&self) -> Option<F> {
@@ -301,54 +331,83 @@ pub mod common {
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct Tags(HashMap<TagString, TagString>);
+    /// The tags of an element, sorted by key, such that
+    /// a tag is found by binary search without hashing.
+    ///
+    /// Elements rarely carry more than a handful of tags, for which this
+    /// is both smaller and faster to look up than a hashed map.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Tags(Box<[(TagString, TagString)]>);
 
     pub trait Taggable {
         fn indices(&self) -> impl Iterator<Item = (&u32, &u32)>;
-        fn tags(&self, block: &PrimitiveBlock) -> Tags {
-            Tags::from_block(self.indices(), block)
+        fn tags(&self, strings: &BlockStrings) -> Tags {
+            Tags::from_block(self.indices(), strings)
         }
     }
 
     impl Tags {
-        pub fn new(map: HashMap<TagString, TagString>) -> Self {
-            Tags(map)
+        /// Creates the tags from their keys and values, where
+        /// the last of any duplicated key takes precedence.
+        pub fn new(tags: impl IntoIterator<Item = (TagString, TagString)>) -> Self {
+            let mut tags = tags.into_iter().collect::<Vec<_>>();
+
+            tags.reverse();
+            tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+            tags.dedup_by(|(a, _), (b, _)| a == b);
+
+            Tags(tags.into_boxed_slice())
         }
 
         /// Takes an iterator of indicies within the string table of the
         /// associated block, and recovers the strings at the specified
-        /// indexes, to generate an associative hashmap of the tag keys and values.
+        /// indexes, to generate the tag keys and values.
         ///
         /// The iterator must yield in the order of (KeyIndex, ValueIndex).
         /// This is most often implemented under the Taggable trait.
+        ///
+        /// Tags whose key or value lies beyond the string table, as in a
+        /// corrupt block, are skipped.
         pub fn from_block<'a>(
             iter: impl Iterator<Item = (&'a u32, &'a u32)>,
-            block: &PrimitiveBlock,
+            strings: &BlockStrings,
         ) -> Self {
-            Tags(
-                iter.map(|(&k, &v)| {
-                    (
-                        TagString::recover(k as usize, block),
-                        TagString::recover(v as usize, block),
-                    )
-                })
-                .collect::<HashMap<TagString, TagString>>(),
+            Tags::new(
+                iter.filter_map(|(&k, &v)| {
+                    Some((strings.key(k as usize)?, strings.get(v as usize)?))
+                }),
             )
         }
 
-        fn r#use(assoc: &str) -> TagString {
-            TagString::from(assoc)
-        }
-
-        pub(crate) fn get(&self, assoc: &str) -> Option<&TagString> {
-            self.0.get(&Tags::r#use(assoc))
+        /// The value of the tag with the given key.
+        pub fn get(&self, assoc: &str) -> Option<&TagString> {
+            self.0
+                .binary_search_by(|(key, _)| key.as_str().cmp(assoc))
+                .ok()
+                .map(|index| &self.0[index].1)
         }
 
         pub(crate) fn r#as<F: FromStr>(&self, assoc: &str) -> Option<F> {
             self.get(assoc).and_then(TagString::parse::<F>)
         }
 
+        pub fn contains_key(&self, assoc: &str) -> bool {
+            self.get(assoc).is_some()
+        }
+
+        /// The keys and values of the tags, in order of key.
+        pub fn iter(&self) -> impl Iterator<Item = (&TagString, &TagString)> {
+            self.0.iter().map(|(key, value)| (key, value))
+        }
+
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+
         #[inline]
         pub fn road_tag(&self) -> Option<&str> {
             self.get(TagString::HIGHWAY)
@@ -377,11 +436,19 @@ pub mod common {
         }
     }
 
-    impl Deref for Tags {
-        type Target = HashMap<TagString, TagString>;
+    impl FromIterator<(TagString, TagString)> for Tags {
+        fn from_iter<T: IntoIterator<Item = (TagString, TagString)>>(iter: T) -> Self {
+            Tags::new(iter)
+        }
+    }
 
-        fn deref(&self) -> &Self::Target {
-            &self.0
+    /// Creates the tags from string literals, such as `[("highway", "residential")]`.
+    impl<'a> FromIterator<(&'a str, &'a str)> for Tags {
+        fn from_iter<T: IntoIterator<Item = (&'a str, &'a str)>>(iter: T) -> Self {
+            Tags::new(
+                iter.into_iter()
+                    .map(|(key, value)| (TagString::from(key), TagString::from(value))),
+            )
         }
     }
 }
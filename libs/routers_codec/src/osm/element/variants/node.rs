@@ -5,8 +5,9 @@
 use geo::point;
 use std::ops::{Add, Mul};
 
-use super::common::OsmEntryId;
+use super::common::{OsmEntryId, Taggable, Tags};
 use crate::osm;
+use crate::osm::element::BlockStrings;
 use crate::primitive::{Entry, Node};
 
 impl Node<OsmEntryId> {
@@ -70,3 +71,40 @@ impl From<&osm::Node> for Node<OsmEntryId> {
         }
     }
 }
+
+impl Taggable for osm::Node {
+    fn indices(&self) -> impl Iterator<Item = (&u32, &u32)> {
+        self.keys.iter().zip(self.vals.iter())
+    }
+}
+
+impl Tags {
+    /// Recovers the tags of each of the `DenseNodes`, in order.
+    ///
+    /// The tags of all nodes are packed into a single `keys_vals` list,
+    /// as alternating key and value indices, where the tags of each node
+    /// are delimited by a `0`. Should no node be tagged, the list is empty.
+    ///
+    /// There is always one set of tags per node, such that a list missing
+    /// delimiters leaves the trailing nodes untagged rather than dropping them.
+    pub fn from_dense(value: &osm::DenseNodes, strings: &BlockStrings) -> Vec<Tags> {
+        if value.keys_vals.is_empty() {
+            return vec![Tags::default(); value.id.len()];
+        }
+
+        value
+            .keys_vals
+            .split(|index| *index == 0)
+            .map(|pairs| {
+                let indices = pairs
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0] as u32, pair[1] as u32))
+                    .collect::<Vec<_>>();
+
+                Tags::from_block(indices.iter().map(|(k, v)| (k, v)), strings)
+            })
+            .chain(std::iter::repeat_with(Tags::default))
+            .take(value.id.len())
+            .collect()
+    }
+}
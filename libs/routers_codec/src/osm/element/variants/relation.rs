@@ -1,6 +1,18 @@
-use super::common::{ReferenceKey, References, Referential, Taggable, Tags};
+//! Describes a `Relation`, an ordered collection of members
+//! such as the ways of a bus route.
+
+use super::common::{OsmEntryId, Reference, ReferenceKey, References, Referential, Taggable, Tags};
+use super::way::Way;
 use crate::osm;
+use crate::osm::element::BlockStrings;
 use crate::osm::element::variants::Intermediate;
+use crate::osm::relation::MemberType;
+
+/// Roles of route members which are not traversed, being the stops and
+/// platforms of a public transport route, rather than the ways it follows.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Public_transport#Route_relations
+const NON_TRAVERSED_ROLES: [&str; 2] = ["stop", "platform"];
 
 #[derive(Clone, Debug)]
 pub struct Relation {
@@ -9,12 +21,116 @@ pub struct Relation {
     pub refs: References,
 }
 
+/// A way of a route, in the direction it is traversed.
+#[derive(Clone, Debug)]
+pub struct RouteWay<'a> {
+    pub way: &'a Way,
+    /// Whether the way is traversed in the order of its nodes.
+    pub forward: bool,
+}
+
+impl RouteWay<'_> {
+    /// The first and last node of the way, in the direction it is traversed.
+    fn ends(&self) -> Option<(OsmEntryId, OsmEntryId)> {
+        let (first, last) = (self.way.refs().first()?.id, self.way.refs().last()?.id);
+        Some(if self.forward {
+            (first, last)
+        } else {
+            (last, first)
+        })
+    }
+}
+
 impl Relation {
-    pub fn from_raw(relation: &osm::Relation, block: &osm::PrimitiveBlock) -> Self {
+    /// The members of the given type, in order.
+    pub fn members(&self, member_type: MemberType) -> impl Iterator<Item = &Reference> {
+        self.refs
+            .iter()
+            .filter(move |member| member.member_type == member_type)
+    }
+
+    /// The route type of the relation, such as `bus` or `bicycle`,
+    /// if it is a route relation.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Relation:route
+    pub fn route(&self) -> Option<&str> {
+        self.tags
+            .get("type")
+            .filter(|kind| kind.as_str() == "route")
+            .and(self.tags.get("route"))
+            .map(|route| route.as_str())
+    }
+
+    /// Resolves the traversed ways of a route relation into ordered sequences,
+    /// orienting each way such that it continues from the end of its predecessor.
+    ///
+    /// Ways are looked up by identifier, such that routes may be resolved against
+    /// any collection of ways. A new sequence is started wherever the route is
+    /// discontinuous, such as when a member way is missing from an extract.
+    pub fn resolve_route<'a>(
+        &self,
+        lookup: impl Fn(&OsmEntryId) -> Option<&'a Way>,
+    ) -> Vec<Vec<RouteWay<'a>>> {
+        let ways = self.members(MemberType::Way).filter(|member| {
+            let role = member.role.as_ref().map_or("", |role| role.0.as_str());
+            !NON_TRAVERSED_ROLES
+                .iter()
+                .any(|excluded| role.starts_with(excluded))
+        });
+
+        let mut sequences: Vec<Vec<RouteWay<'a>>> = vec![];
+        let mut current: Vec<RouteWay<'a>> = vec![];
+
+        for member in ways {
+            let Some(way) = lookup(&member.id).filter(|way| !way.refs().is_empty()) else {
+                if !current.is_empty() {
+                    sequences.push(std::mem::take(&mut current));
+                }
+
+                continue;
+            };
+
+            let (first, last) = (way.refs()[0].id, way.refs()[way.refs().len() - 1].id);
+            let length = current.len();
+            let Some(previous) = current.last_mut() else {
+                current.push(RouteWay { way, forward: true });
+                continue;
+            };
+
+            // The first way's direction is only known once its successor is.
+            if length == 1 {
+                let (start, end) = previous.ends().unwrap_or_default();
+                if (start == first || start == last) && end != first && end != last {
+                    previous.forward = !previous.forward;
+                }
+            }
+
+            let (_, end) = previous.ends().unwrap_or_default();
+            if end == first {
+                current.push(RouteWay { way, forward: true });
+            } else if end == last {
+                current.push(RouteWay {
+                    way,
+                    forward: false,
+                });
+            } else {
+                sequences.push(std::mem::take(&mut current));
+                current.push(RouteWay { way, forward: true });
+            }
+        }
+
+        if !current.is_empty() {
+            sequences.push(current);
+        }
+
+        sequences
+    }
+
+    pub fn from_raw(relation: &osm::Relation, strings: &BlockStrings) -> Self {
         Self {
             id: relation.id,
-            tags: relation.tags(block),
-            refs: relation.references(block),
+            tags: relation.tags(strings),
+            refs: relation.references(strings),
         }
     }
 }
@@ -26,16 +142,123 @@ impl Taggable for osm::Relation {
 }
 
 impl Referential for osm::Relation {
-    fn indices(&self) -> impl Iterator<Item = ReferenceKey> {
+    fn indices(&self) -> impl Iterator<Item = ReferenceKey<'_>> {
         self.roles_sid
             .iter()
             .zip(self.memids.iter())
             .zip(self.types.iter())
-            .map(|(e, _v)| Intermediate {
-                index: e.1,
-                role: e.0,
-                #[cfg(debug_assertions)]
-                member_type: _v,
+            .map(|((role, index), member_type)| Intermediate {
+                index,
+                role,
+                member_type,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entry;
+    use crate::osm::element::variants::{Role, TagString};
+    use std::collections::HashMap;
+
+    fn way(id: i64, refs: &[i64]) -> Way {
+        let refs = refs
+            .iter()
+            .map(|id| Reference::without_role(OsmEntryId::node(*id), MemberType::Node))
+            .collect::<Vec<_>>();
+
+        Way::new(OsmEntryId::way(id), References::from(refs), Tags::default())
+    }
+
+    fn bus_route(members: &[(MemberType, i64, &str)]) -> Relation {
+        let refs = members
+            .iter()
+            .map(|(member_type, id, role)| {
+                Reference::with_role(
+                    OsmEntryId::new(*id),
+                    *member_type,
+                    Role(TagString::from(*role)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Relation {
+            id: 1,
+            tags: Tags::from_iter([("type", "route"), ("route", "bus")]),
+            refs: References::from(refs),
+        }
+    }
+
+    fn resolve(relation: &Relation, ways: &HashMap<i64, Way>) -> Vec<Vec<(i64, bool)>> {
+        relation
+            .resolve_route(|id| ways.get(&id.identifier()))
+            .into_iter()
+            .map(|sequence| {
+                sequence
+                    .iter()
+                    .map(|route| (route.way.id().identifier(), route.forward))
+                    .collect()
             })
+            .collect()
+    }
+
+    #[test]
+    fn test_route_members() {
+        let relation = bus_route(&[
+            (MemberType::Node, 100, "stop"),
+            (MemberType::Way, 10, ""),
+            (MemberType::Way, 11, "platform"),
+        ]);
+
+        assert_eq!(relation.route(), Some("bus"));
+        assert_eq!(relation.members(MemberType::Node).count(), 1);
+        assert_eq!(relation.members(MemberType::Way).count(), 2);
+    }
+
+    #[test]
+    fn test_resolve_route() {
+        let ways = [
+            way(10, &[1, 2, 3]),
+            way(11, &[5, 4, 3]),
+            way(12, &[5, 6]),
+            way(13, &[20, 21]),
+            way(14, &[90, 91]),
+        ]
+        .map(|way| (way.id().identifier(), way))
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        // The second way is traversed against the order of its nodes.
+        let relation = bus_route(&[
+            (MemberType::Node, 1, "stop"),
+            (MemberType::Way, 10, ""),
+            (MemberType::Way, 11, ""),
+            (MemberType::Way, 14, "platform"),
+            (MemberType::Way, 12, ""),
+        ]);
+        assert_eq!(
+            resolve(&relation, &ways),
+            vec![vec![(10, true), (11, false), (12, true)]]
+        );
+
+        // The first way is oriented by its successor.
+        let relation = bus_route(&[(MemberType::Way, 11, ""), (MemberType::Way, 10, "")]);
+        assert_eq!(
+            resolve(&relation, &ways),
+            vec![vec![(11, true), (10, false)]]
+        );
+
+        // Disconnected and missing ways begin a new sequence.
+        let relation = bus_route(&[
+            (MemberType::Way, 10, ""),
+            (MemberType::Way, 13, ""),
+            (MemberType::Way, 99, ""),
+            (MemberType::Way, 12, ""),
+        ]);
+        assert_eq!(
+            resolve(&relation, &ways),
+            vec![vec![(10, true)], vec![(13, true)], vec![(12, true)]]
+        );
     }
 }
@@ -2,12 +2,11 @@
 //! Has methods for accessing appropriate
 //! tags for graph representation.
 
-#[cfg(debug_assertions)]
 use crate::osm::relation::MemberType;
 
 use super::common::{OsmEntryId, ReferenceKey, References, Referential, Taggable, Tags};
 use crate::osm;
-use crate::osm::PrimitiveBlock;
+use crate::osm::element::BlockStrings;
 use crate::osm::element::variants::Intermediate;
 
 #[derive(Clone, Debug)]
@@ -18,6 +17,10 @@ pub struct Way {
 }
 
 impl Way {
+    pub fn new(id: OsmEntryId, refs: References, tags: Tags) -> Self {
+        Way { id, refs, tags }
+    }
+
     pub fn id(&self) -> OsmEntryId {
         self.id
     }
@@ -38,11 +41,11 @@ impl Way {
     }
 
     #[inline]
-    pub fn from_raw(value: &osm::Way, block: &PrimitiveBlock) -> Self {
+    pub fn from_raw(value: &osm::Way, strings: &BlockStrings) -> Self {
         Way {
             id: OsmEntryId::way(value.id),
-            refs: value.references(block),
-            tags: value.tags(block),
+            refs: value.references(strings),
+            tags: value.tags(strings),
         }
     }
 }
@@ -54,11 +57,10 @@ impl Taggable for osm::Way {
 }
 
 impl Referential for osm::Way {
-    fn indices(&self) -> impl Iterator<Item = ReferenceKey> {
+    fn indices(&self) -> impl Iterator<Item = ReferenceKey<'_>> {
         self.refs.iter().map(|id| Intermediate {
             role: &-1i32,
             index: id,
-            #[cfg(debug_assertions)]
             member_type: &(MemberType::Node as i32),
         })
     }
@@ -1,9 +1,128 @@
+use std::fmt::{Display, Formatter};
 use std::io;
 use std::io::Error;
 
 #[derive(Debug)]
 pub enum CodecError {
     IOError(std::io::Error),
+
+    /// The blob is compressed using a scheme which is not supported,
+    /// either as it is obsolete, or its cargo feature is not enabled.
+    UnsupportedCompression(&'static str),
+    /// The blob could not be decompressed using its declared scheme.
+    DecompressionError(&'static str, std::io::Error),
+    /// The blob did not contain any data.
+    EmptyBlob,
+    /// The (decompressed) protocol buffer could not be decoded.
+    DecodeError(prost::DecodeError),
+    /// The file requires a feature which is not supported, such as `HistoricalInformation`.
+    UnsupportedFeature(String),
+    /// The XML document could not be read.
+    XmlError(quick_xml::Error),
+    /// The XML document is well-formed, but does not describe valid `OSM` elements.
+    MalformedXml(String),
+
+    /// The file ended part-way through a blob, such as when it has been truncated,
+    /// having expected a further `expected` bytes of which only `available` remained.
+    Truncated {
+        expected: usize,
+        available: usize,
+    },
+    /// The compressed blob declares no, or a negative, uncompressed size.
+    InvalidRawSize(Option<i32>),
+    /// The blob (or its header) declares a size beyond the maximum the specification permits.
+    OversizedBlob {
+        kind: &'static str,
+        size: usize,
+    },
+    /// The blob at the given index, whose header begins at the given offset
+    /// within the file, could not be read or decoded due to the `cause`.
+    Blob {
+        index: u64,
+        offset: u64,
+        cause: Box<CodecError>,
+    },
+}
+
+impl CodecError {
+    /// Attributes the error to the blob at the given index and offset.
+    pub(crate) fn at_blob(self, index: u64, offset: u64) -> Self {
+        match self {
+            CodecError::Blob { .. } => self,
+            cause => CodecError::Blob {
+                index,
+                offset,
+                cause: Box::new(cause),
+            },
+        }
+    }
+
+    /// The underlying cause of the error, without the context of where it occurred.
+    pub fn cause(&self) -> &CodecError {
+        match self {
+            CodecError::Blob { cause, .. } => cause.cause(),
+            other => other,
+        }
+    }
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecError::IOError(err) => write!(f, "failed to read file: {err}"),
+            CodecError::UnsupportedCompression(scheme) => {
+                write!(f, "unsupported blob compression: {scheme}")
+            }
+            CodecError::DecompressionError(scheme, err) => {
+                write!(f, "failed to decompress {scheme} blob: {err}")
+            }
+            CodecError::EmptyBlob => write!(f, "blob contains no data"),
+            CodecError::DecodeError(err) => write!(f, "failed to decode protobuf: {err}"),
+            CodecError::UnsupportedFeature(feature) => {
+                write!(f, "file requires unsupported feature: {feature}")
+            }
+            CodecError::XmlError(err) => write!(f, "failed to read xml: {err}"),
+            CodecError::MalformedXml(reason) => write!(f, "malformed osm xml: {reason}"),
+            CodecError::Truncated {
+                expected,
+                available,
+            } => {
+                write!(
+                    f,
+                    "file is truncated, expected a further {expected} bytes but only {available} remain"
+                )
+            }
+            CodecError::InvalidRawSize(Some(size)) => {
+                write!(
+                    f,
+                    "compressed blob declares an invalid size of {size} bytes"
+                )
+            }
+            CodecError::InvalidRawSize(None) => {
+                write!(f, "compressed blob does not declare its uncompressed size")
+            }
+            CodecError::OversizedBlob { kind, size } => {
+                write!(f, "{kind} of {size} bytes exceeds the maximum size")
+            }
+            CodecError::Blob {
+                index,
+                offset,
+                cause,
+            } => write!(f, "blob {index} at offset {offset}: {cause}"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CodecError::IOError(err) | CodecError::DecompressionError(_, err) => Some(err),
+            CodecError::DecodeError(err) => Some(err),
+            CodecError::XmlError(err) => Some(err),
+            CodecError::Blob { cause, .. } => Some(cause.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<io::Error> for CodecError {
@@ -11,3 +130,15 @@ impl From<io::Error> for CodecError {
         Self::IOError(value)
     }
 }
+
+impl From<prost::DecodeError> for CodecError {
+    fn from(value: prost::DecodeError) -> Self {
+        Self::DecodeError(value)
+    }
+}
+
+impl From<quick_xml::Error> for CodecError {
+    fn from(value: quick_xml::Error) -> Self {
+        Self::XmlError(value)
+    }
+}
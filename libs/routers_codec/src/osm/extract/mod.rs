@@ -0,0 +1,271 @@
+//! Cuts an extract of a region from a `.osm.pbf` file
+
+#[doc(hidden)]
+mod test;
+
+use geo::{BoundingRect, Intersects, Point, Polygon, Rect};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::Entry;
+use crate::osm::block::iterator::BlockIterator;
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::processed_iterator::ProcessedElementIterator;
+use crate::osm::error::CodecError;
+use crate::osm::parallel::Parallel;
+use crate::osm::relation::MemberType;
+use crate::osm::writer::PbfWriter;
+
+/// The region of an extract.
+#[derive(Clone, Debug)]
+pub enum Region {
+    BoundingBox(Rect),
+    Polygon(Polygon),
+}
+
+impl Region {
+    /// Determines if the point lies within (or upon the boundary of) the region.
+    pub fn contains(&self, point: &Point) -> bool {
+        match self {
+            Region::BoundingBox(rect) => rect.intersects(point),
+            Region::Polygon(polygon) => polygon.intersects(point),
+        }
+    }
+
+    /// The bounding box of the region, written into the header of the extract.
+    pub fn bounding(&self) -> Option<Rect> {
+        match self {
+            Region::BoundingBox(rect) => Some(*rect),
+            Region::Polygon(polygon) => polygon.bounding_rect(),
+        }
+    }
+}
+
+impl From<Rect> for Region {
+    fn from(value: Rect) -> Self {
+        Region::BoundingBox(value)
+    }
+}
+
+impl From<Polygon> for Region {
+    fn from(value: Polygon) -> Self {
+        Region::Polygon(value)
+    }
+}
+
+/// The number of each element written into an extract.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExtractSummary {
+    pub nodes: usize,
+    pub ways: usize,
+    pub relations: usize,
+}
+
+/// Extracts the elements of a region from a file.
+///
+/// The extract contains:
+/// - All nodes inside the region,
+/// - All ways referencing any such node, in their entirety,
+///   including the nodes they reference outside the region,
+/// - All relations referencing any node, way or relation in the extract,
+///   such that the parents of a kept relation are kept in turn.
+///
+/// The relations a kept relation references are not themselves kept,
+/// unless they too reference an element of the extract.
+///
+/// Elements are selected in three parallel passes over the file, the first
+/// collecting the nodes inside the region, the second the ways which
+/// reference them, and the third the relations which reference either.
+/// The selected elements are then written in file order.
+///
+/// ```rust,no_run
+/// use geo::{Rect, coord};
+/// use routers_codec::osm::extract::Extract;
+/// use std::path::PathBuf;
+///
+/// let region = Rect::new(coord! { x: -77.05, y: 38.88 }, coord! { x: -77.00, y: 38.91 });
+/// let summary = Extract::new(region)
+///     .to_file(PathBuf::from("district-of-columbia.osm.pbf"), PathBuf::from("mall.osm.pbf"))
+///     .expect("Could not extract region");
+/// ```
+pub struct Extract {
+    region: Region,
+}
+
+/// The identifiers of the elements selected for an extract.
+#[derive(Default)]
+struct Selection {
+    nodes: HashSet<i64>,
+    ways: HashSet<i64>,
+    relations: HashSet<i64>,
+}
+
+/// The relations referencing a selected node or way, and the
+/// parents of each relation which is a member of another.
+#[derive(Default)]
+struct Parents {
+    referencing: HashSet<i64>,
+    parents: HashMap<i64, Vec<i64>>,
+}
+
+impl Extract {
+    pub fn new(region: impl Into<Region>) -> Self {
+        Extract {
+            region: region.into(),
+        }
+    }
+
+    /// The identifiers of all nodes inside the region.
+    fn inside(&self, input: &Path) -> Result<HashSet<i64>, CodecError> {
+        ProcessedElementIterator::new(input.to_path_buf())?.par_red(
+            |mut nodes, element| {
+                if let ProcessedElement::Node(node, _) = element
+                    && self.region.contains(&node.position)
+                {
+                    nodes.insert(node.id.identifier());
+                }
+
+                nodes
+            },
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+            HashSet::new,
+        )
+    }
+
+    /// Selects the ways referencing any of the inside nodes,
+    /// alongside every node such ways reference.
+    fn select(&self, input: &Path, inside: HashSet<i64>) -> Result<Selection, CodecError> {
+        let referenced = ProcessedElementIterator::new(input.to_path_buf())?.par_red(
+            |mut selection: Selection, element| {
+                if let ProcessedElement::Way(way) = element
+                    && way
+                        .refs()
+                        .iter()
+                        .any(|node| inside.contains(&node.id.identifier()))
+                {
+                    selection.ways.insert(way.id().identifier());
+                    selection
+                        .nodes
+                        .extend(way.refs().iter().map(|node| node.id.identifier()));
+                }
+
+                selection
+            },
+            |mut a, b| {
+                a.nodes.extend(b.nodes);
+                a.ways.extend(b.ways);
+                a
+            },
+            Selection::default,
+        )?;
+
+        Ok(Selection {
+            nodes: inside.into_iter().chain(referenced.nodes).collect(),
+            ways: referenced.ways,
+            relations: HashSet::new(),
+        })
+    }
+
+    /// Selects the relations referencing any selected node or way, and
+    /// those referencing a selected relation, until no more are found.
+    fn relations(&self, input: &Path, selection: &mut Selection) -> Result<(), CodecError> {
+        let Parents {
+            referencing,
+            parents,
+        } = ProcessedElementIterator::new(input.to_path_buf())?.par_red(
+            |mut found: Parents, element| {
+                if let ProcessedElement::Relation(relation) = element {
+                    for member in relation.refs.iter() {
+                        let id = member.id.identifier();
+                        let selected = match member.member_type {
+                            MemberType::Node => selection.nodes.contains(&id),
+                            MemberType::Way => selection.ways.contains(&id),
+                            MemberType::Relation => {
+                                found.parents.entry(id).or_default().push(relation.id);
+                                false
+                            }
+                        };
+
+                        if selected {
+                            found.referencing.insert(relation.id);
+                        }
+                    }
+                }
+
+                found
+            },
+            |mut a, b| {
+                a.referencing.extend(b.referencing);
+                for (child, parents) in b.parents {
+                    a.parents.entry(child).or_default().extend(parents);
+                }
+                a
+            },
+            Parents::default,
+        )?;
+
+        let mut pending = referencing.into_iter().collect::<Vec<_>>();
+        while let Some(relation) = pending.pop() {
+            if selection.relations.insert(relation) {
+                pending.extend(parents.get(&relation).into_iter().flatten());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Determines if the element belongs within the extract.
+    fn keep(selection: &Selection, element: &ProcessedElement) -> bool {
+        match element {
+            ProcessedElement::Node(node, _) => selection.nodes.contains(&node.id.identifier()),
+            ProcessedElement::Way(way) => selection.ways.contains(&way.id().identifier()),
+            ProcessedElement::Relation(relation) => selection.relations.contains(&relation.id),
+        }
+    }
+
+    /// Writes the extract of the input file into the writer,
+    /// returning the writer and the number of elements written.
+    pub fn write<W: Write>(
+        &self,
+        input: PathBuf,
+        writer: PbfWriter<W>,
+    ) -> Result<(W, ExtractSummary), CodecError> {
+        let inside = self.inside(&input)?;
+        let mut selection = self.select(&input, inside)?;
+        self.relations(&input, &mut selection)?;
+
+        let mut writer = match self.region.bounding() {
+            Some(bbox) => writer.bbox(bbox),
+            None => writer,
+        };
+
+        let mut summary = ExtractSummary::default();
+        for block in BlockIterator::new(input)? {
+            for element in block?.element_iter_with(true) {
+                if !Self::keep(&selection, &element) {
+                    continue;
+                }
+
+                match element {
+                    ProcessedElement::Node(..) => summary.nodes += 1,
+                    ProcessedElement::Way(_) => summary.ways += 1,
+                    ProcessedElement::Relation(_) => summary.relations += 1,
+                }
+
+                writer.write(&element)?;
+            }
+        }
+
+        Ok((writer.finish()?, summary))
+    }
+
+    /// Writes the extract of the input file into the output file.
+    pub fn to_file(&self, input: PathBuf, output: PathBuf) -> Result<ExtractSummary, CodecError> {
+        self.write(input, PbfWriter::create(output)?)
+            .map(|(_, summary)| summary)
+    }
+}
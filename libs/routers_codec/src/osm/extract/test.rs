@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use bytes::Bytes;
+use geo::{Rect, coord, point, polygon};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::Entry;
+use crate::osm::BlobIterator;
+use crate::osm::block::iterator::BlockIterator;
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::{OsmEntryId, Reference, References, Relation, Tags, Way};
+use crate::osm::extract::{Extract, ExtractSummary, Region};
+use crate::osm::relation::MemberType;
+use crate::osm::writer::PbfWriter;
+use crate::primitive::Node;
+
+fn way(id: i64, refs: &[i64]) -> ProcessedElement {
+    let refs = refs
+        .iter()
+        .map(|id| Reference::without_role(OsmEntryId::node(*id), MemberType::Node))
+        .collect::<Vec<_>>();
+
+    ProcessedElement::Way(Way::new(
+        OsmEntryId::way(id),
+        References::from(refs),
+        Tags::from_iter([("highway", "residential")]),
+    ))
+}
+
+fn relation(id: i64, members: &[(MemberType, i64)]) -> ProcessedElement {
+    let refs = members
+        .iter()
+        .map(|&(member_type, id)| {
+            let id = match member_type {
+                MemberType::Node => OsmEntryId::node(id),
+                MemberType::Way => OsmEntryId::way(id),
+                MemberType::Relation => OsmEntryId::relation(id),
+            };
+
+            Reference::without_role(id, member_type)
+        })
+        .collect::<Vec<_>>();
+
+    ProcessedElement::Relation(Relation {
+        id,
+        tags: Tags::new(HashMap::new()),
+        refs: References::from(refs),
+    })
+}
+
+/// Writes a file of nodes around the origin, with ways and relations
+/// which do and do not reference the nodes nearest to it, and relations
+/// of those relations.
+fn input(name: &str) -> PathBuf {
+    let nodes = [
+        (1, 0.0, 0.0),
+        (2, 0.5, 0.5),
+        (3, 2.0, 2.0),
+        (4, 3.0, 3.0),
+        (5, 3.0, 4.0),
+        (6, -2.0, -2.0),
+    ]
+    .map(|(id, x, y)| {
+        ProcessedElement::Node(
+            Node::new(point! { x: x, y: y }, OsmEntryId::node(id)),
+            Tags::default(),
+        )
+    });
+
+    let elements = nodes.into_iter().chain([
+        way(10, &[1, 3]),
+        way(11, &[4, 5]),
+        way(12, &[2, 6]),
+        relation(20, &[(MemberType::Way, 11)]),
+        relation(21, &[(MemberType::Node, 2)]),
+        relation(22, &[(MemberType::Way, 12)]),
+        relation(23, &[(MemberType::Relation, 22)]),
+        relation(24, &[(MemberType::Relation, 23)]),
+        relation(25, &[(MemberType::Way, 10), (MemberType::Relation, 20)]),
+    ]);
+
+    let path = std::env::temp_dir().join(format!("{name}-{}.osm.pbf", std::process::id()));
+    let mut writer = PbfWriter::create(path.clone()).unwrap().block_size(4);
+    for element in elements {
+        writer.write(&element).unwrap();
+    }
+
+    writer.finish().unwrap();
+    path
+}
+
+/// Extracts the region, returning the summary and the elements written.
+fn extract(name: &str, region: impl Into<Region>) -> (ExtractSummary, Vec<(MemberType, i64)>) {
+    let path = input(name);
+    let (output, summary) = Extract::new(region)
+        .write(path.clone(), PbfWriter::new(vec![]))
+        .unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    let blobs = BlobIterator::with_existing(Bytes::from(output)).unwrap();
+    let mut elements = BlockIterator::from_blobs(blobs)
+        .flat_map(|block| block.unwrap().element_iter().collect::<Vec<_>>())
+        .map(|element| match element {
+            ProcessedElement::Node(node, _) => (MemberType::Node, node.id.identifier()),
+            ProcessedElement::Way(way) => (MemberType::Way, way.id().identifier()),
+            ProcessedElement::Relation(relation) => (MemberType::Relation, relation.id),
+        })
+        .collect::<Vec<_>>();
+
+    elements.sort();
+    (summary, elements)
+}
+
+#[test]
+fn extract_bounding_box() {
+    let region = Rect::new(coord! { x: -1.0, y: -1.0 }, coord! { x: 1.0, y: 1.0 });
+    let (summary, elements) = extract("extract_bbox", region);
+
+    assert_eq!(
+        summary,
+        ExtractSummary {
+            nodes: 4,
+            ways: 2,
+            relations: 5
+        }
+    );
+
+    // Relations are kept through any chain of parents reaching the extract,
+    // whereas the relation members of a kept relation (20) are not.
+
+    // Ways touching the region are complete, including their nodes outside it.
+    assert_eq!(
+        elements,
+        vec![
+            (MemberType::Node, 1),
+            (MemberType::Node, 2),
+            (MemberType::Node, 3),
+            (MemberType::Node, 6),
+            (MemberType::Way, 10),
+            (MemberType::Way, 12),
+            (MemberType::Relation, 21),
+            (MemberType::Relation, 22),
+            (MemberType::Relation, 23),
+            (MemberType::Relation, 24),
+            (MemberType::Relation, 25),
+        ]
+    );
+}
+
+#[test]
+fn extract_polygon() {
+    let region = polygon![(x: -1.0, y: -1.0), (x: 1.0, y: -1.0), (x: -1.0, y: 1.0)];
+    let (summary, elements) = extract("extract_polygon", region);
+
+    assert_eq!(summary.relations, 1);
+    assert_eq!(
+        elements,
+        vec![
+            (MemberType::Node, 1),
+            (MemberType::Node, 3),
+            (MemberType::Way, 10),
+            (MemberType::Relation, 25),
+        ]
+    );
+}
@@ -0,0 +1,69 @@
+//! The file header, decoded from the leading `HeaderBlock`
+
+use geo::{Rect, coord};
+
+use crate::osm::HeaderBlock;
+use crate::osm::error::CodecError;
+
+/// The required features which can be read.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/PBF_Format#Definition_of_the_OSMHeader_fileblock
+pub(crate) const SUPPORTED_FEATURES: [&str; 2] = ["OsmSchema-V0.6", "DenseNodes"];
+
+/// The metadata of a file, as declared by its `HeaderBlock`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PbfHeader {
+    /// The bounding box of the data within the file.
+    pub bbox: Option<Rect>,
+
+    /// Features a reader must support to correctly read the file.
+    pub required_features: Vec<String>,
+    /// Features which may be used, such as `Sort.Type_then_ID`.
+    pub optional_features: Vec<String>,
+
+    pub writing_program: Option<String>,
+    pub source: Option<String>,
+
+    /// The replication timestamp, in seconds since the epoch.
+    pub replication_timestamp: Option<i64>,
+    pub replication_sequence_number: Option<i64>,
+    pub replication_base_url: Option<String>,
+}
+
+impl PbfHeader {
+    /// Ensures every required feature of the file is supported,
+    /// otherwise the file would be read incorrectly.
+    pub fn validate(&self) -> Result<(), CodecError> {
+        match self
+            .required_features
+            .iter()
+            .find(|feature| !SUPPORTED_FEATURES.contains(&feature.as_str()))
+        {
+            Some(feature) => Err(CodecError::UnsupportedFeature(feature.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<HeaderBlock> for PbfHeader {
+    fn from(value: HeaderBlock) -> Self {
+        // The bounding box is given in nanodegrees.
+        let bbox = value.bbox.map(|bbox| {
+            Rect::new(
+                coord! { x: bbox.left as f64 * 1e-9, y: bbox.bottom as f64 * 1e-9 },
+                coord! { x: bbox.right as f64 * 1e-9, y: bbox.top as f64 * 1e-9 },
+            )
+        });
+
+        PbfHeader {
+            bbox,
+            required_features: value.required_features,
+            optional_features: value.optional_features,
+            writing_program: value.writingprogram,
+            source: value.source,
+            replication_timestamp: value.osmosis_replication_timestamp,
+            replication_sequence_number: value.osmosis_replication_sequence_number,
+            replication_base_url: value.osmosis_replication_base_url,
+        }
+    }
+}
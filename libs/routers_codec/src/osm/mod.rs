@@ -3,7 +3,13 @@
 // Exposed modules
 pub mod blob;
 pub mod block;
+pub mod change;
+pub mod country;
 pub mod element;
+pub mod extract;
+pub mod header;
+pub mod writer;
+pub mod xml;
 
 pub mod parsers;
 
@@ -19,13 +25,21 @@ pub mod test;
 #[doc(inline)]
 pub use blob::iterator::BlobIterator;
 #[doc(inline)]
-pub use block::iterator::BlockIterator;
+pub use block::iterator::{BlockIterator, DecodeMode, SkippedBlobs};
+#[doc(inline)]
+pub use change::{ChangeAction, OsmChange};
 #[doc(inline)]
 pub use element::OsmEntryId;
 #[doc(inline)]
 pub use element::iterator::ElementIterator;
 #[doc(inline)]
 pub use element::processed_iterator::ProcessedElementIterator;
+#[doc(inline)]
+pub use header::PbfHeader;
+#[doc(inline)]
+pub use writer::PbfWriter;
+#[doc(inline)]
+pub use xml::XmlElementIterator;
 
 // Doc-Linking
 #[doc(inline)]
@@ -46,9 +60,9 @@ pub use block::item::BlockItem;
 pub use element::item::Element;
 
 #[doc(inline)]
-pub use meta::OsmEdgeMetadata;
+pub use meta::{Barrier, OsmEdgeMetadata, OsmMetadata, OsmNodeMetadata};
 #[doc(inline)]
-pub use runtime::TraversalConditions;
+pub use runtime::{Avoidance, TraversalConditions, TripContext, VehicleProfile};
 
 // Protocol Buffer Includes
 pub mod model {
@@ -57,46 +71,1013 @@ pub mod model {
 }
 
 pub mod meta {
-    use std::num::NonZeroU8;
-
-    use crate::Metadata;
-    use crate::osm::access_tag::AccessTag;
+    use crate::osm::access_tag::access::AccessValue;
+    use crate::osm::access_tag::{AccessResolver, AccessTag, ConditionalAccessTag};
     use crate::osm::element::{TagString, Tags};
+    use crate::osm::ferry::FerryRoute;
+    use crate::osm::lanes::LaneLayout;
+    use crate::osm::names::EdgeNames;
+    use crate::osm::oneway::OnewayRestriction;
+    use crate::osm::primitives::condition::VehicleProperty;
+    use crate::osm::primitives::dimension::measure;
     use crate::osm::primitives::*;
-    use crate::osm::speed_limit::SpeedLimitCollection;
-    use crate::osm::{Access, SpeedLimit};
+    use crate::osm::speed_limit::{ImplicitSpeed, SpeedLimitCollection, SpeedLimitExt};
+    use crate::osm::toll::Tolls;
+    use crate::osm::vehicle::VehicleRestrictions;
+    use crate::osm::{
+        Access, Ferry, Lane, Names, Oneway, SpeedLimit, Toll, TraversalConditions, TripContext,
+        VehicleRestriction,
+    };
+    use crate::{Metadata, NodeMetadata};
+
+    /// The cost of stopping at a barrier, such as a gate or toll booth, in a vehicle.
+    /// Costs are in units of edge weight, such that a residential segment costs `10`.
+    const BARRIER_COST: u32 = 10;
+    /// The cost of passing through a set of traffic signals.
+    const TRAFFIC_SIGNALS_COST: u32 = 3;
+    /// The cost of halting at a stop sign in a vehicle.
+    const STOP_COST: u32 = 2;
+    /// The cost of yielding at a give way sign in a vehicle.
+    const GIVE_WAY_COST: u32 = 1;
+    /// The penalty of a way only accessible to destination or delivery traffic,
+    /// such that it is used to reach a destination along it, rather than passed through.
+    const LOCAL_ACCESS_PENALTY: f64 = 10.0;
+    /// The penalty of a toll, ferry or motorway which the traveller [avoids](crate::osm::Avoidance),
+    /// such that it is only used should no reasonable alternative exist.
+    const AVOID_PENALTY: f64 = 100.0;
+    /// The cost of a toll booth which the traveller [avoids](crate::osm::Avoidance).
+    const AVOID_COST: u32 = 1000;
 
     #[derive(Debug, Clone, Default)]
     pub struct OsmEdgeMetadata {
-        pub lane_count: Option<NonZeroU8>,
+        pub lanes: LaneLayout,
+        pub names: EdgeNames,
         pub speed_limit: Option<SpeedLimitCollection>,
         pub access: Vec<AccessTag>,
+        pub conditional_access: Vec<ConditionalAccessTag>,
+        pub oneway: OnewayRestriction,
         pub road_class: Option<RoadClass>,
+        /// The ferry route the way is part of, should it be one.
+        pub ferry: Option<FerryRoute>,
+        pub tolls: Tolls,
+        pub vehicle: VehicleRestrictions,
+        pub surface: Option<Surface>,
+        pub smoothness: Option<Smoothness>,
+        pub tracktype: Option<TrackType>,
+        pub incline: Option<Incline>,
+        /// The width of the carriageway, in centimeters.
+        pub width: Option<Dimension>,
+        /// The country in which the way lies, upon which its implicit speed limit depends.
+        ///
+        /// This is not known from the tags of the way, and is instead
+        /// resolved from its position by a [`CountryResolver`](crate::osm::country::CountryResolver).
+        pub country: Option<CountryCode>,
+    }
+
+    impl OsmEdgeMetadata {
+        /// Determines if the transport mode is permitted on the way, ignoring direction
+        /// and any conditional restriction.
+        ///
+        /// The most specific access tag within the mode's [hierarchy](TransportMode::hierarchy)
+        /// decides. If the way has no such tag, the road class decides implicitly.
+        pub fn permits(&self, mode: TransportMode) -> bool {
+            self.resolve_access(
+                mode,
+                self.access_resolver()
+                    .resolve(mode, Directionality::BothWays),
+                &TripContext::default(),
+            )
+        }
+
+        /// Determines if the way may be traversed under the conditions, ignoring direction,
+        /// where an applicable conditional access tag replaces that of the same transport mode.
+        pub fn permits_under(&self, conditions: &TraversalConditions) -> bool {
+            self.permits_toward(conditions, Directionality::BothWays)
+        }
+
+        /// Determines if the way may be traversed under the conditions in the direction of
+        /// travel, where access tags of that direction, like `motor_vehicle:forward=no`,
+        /// replace those of the same transport mode.
+        pub fn permits_toward(
+            &self,
+            conditions: &TraversalConditions,
+            direction: Directionality,
+        ) -> bool {
+            self.resolve_access(
+                conditions.transport_mode,
+                self.access_resolver().resolve_under(conditions, direction),
+                &conditions.trip,
+            )
+        }
+
+        /// The resolver of the access tags of the way.
+        pub fn access_resolver(&self) -> AccessResolver<'_> {
+            AccessResolver::new(&self.access, &self.conditional_access)
+        }
+
+        /// Determines if the resolved access permits the mode and traveller of the trip,
+        /// or should there be none, the kind of way does implicitly.
+        fn resolve_access(
+            &self,
+            mode: TransportMode,
+            access: Option<&AccessValue>,
+            trip: &TripContext,
+        ) -> bool {
+            access.map_or_else(
+                || match (self.road_class, self.ferry) {
+                    (Some(class), _) => class.permits(mode),
+                    // Ferries carry those on foot and in land vehicles alike.
+                    (None, Some(_)) => mode.is_land(),
+                    (None, None) => false,
+                },
+                |access| access.permits_trip(trip),
+            )
+        }
+
+        /// The penalty of a way whose access is restricted to those with business
+        /// along it, such as `motor_vehicle=destination`, should the trip not declare it.
+        fn access_penalty(&self, conditions: &TraversalConditions) -> f64 {
+            self.access_resolver()
+                .resolve_under(conditions, conditions.directionality)
+                .filter(|access| access.restricts_trip(&conditions.trip))
+                .map_or(1.0, |_| LOCAL_ACCESS_PENALTY)
+        }
+
+        /// If the way is a motorway, or a link road to one.
+        pub fn motorway(&self) -> bool {
+            matches!(
+                self.road_class,
+                Some(RoadClass::Motorway | RoadClass::MotorwayLink)
+            )
+        }
+
+        /// The penalty of the tolls, ferries and motorways the traveller [avoids](crate::osm::Avoidance).
+        fn avoid_penalty(&self, conditions: &TraversalConditions) -> f64 {
+            let avoid = &conditions.avoid;
+            let avoided = (avoid.tolls && self.tolls.applies(conditions.transport_mode))
+                || (avoid.ferries && self.ferry.is_some())
+                || (avoid.motorways && self.motorway());
+
+            if avoided { AVOID_PENALTY } else { 1.0 }
+        }
+
+        /// The surface of the way, or that typical of its [track type](TrackType::surface)
+        /// should it not declare one.
+        pub fn surface(&self) -> Option<Surface> {
+            self.surface
+                .or_else(|| self.tracktype.as_ref().map(TrackType::surface))
+        }
+
+        /// The penalty of travelling upon the surface and smoothness of the way,
+        /// both of which are more punishing to bicycles than motor vehicles.
+        fn surface_penalty(&self, conditions: &TraversalConditions) -> f64 {
+            let mode = conditions.transport_mode;
+
+            self.surface().map_or(1.0, |surface| surface.penalty(mode))
+                * self
+                    .smoothness
+                    .map_or(1.0, |smoothness| smoothness.penalty(mode))
+        }
+
+        /// The speed limit, in kilometers per hour, under the traversal conditions.
+        ///
+        /// Should the way not declare a limit applicable to them, the implicit limit of
+        /// its class of road within its [country](Self::country) applies instead.
+        pub fn max_speed(&self, conditions: &TraversalConditions) -> Option<Speed> {
+            if let Some(limits) = &self.speed_limit {
+                if let Some(limit) = limits.limit_under(conditions) {
+                    return Some(limit);
+                }
+
+                // A declared limit without a numeric speed, such as `maxspeed=none`.
+                if limits
+                    .relevant_limits(conditions.clone())
+                    .iter()
+                    .any(|limit| limit.condition.is_none())
+                {
+                    return None;
+                }
+            }
+
+            self.implicit_speed()
+        }
+
+        /// The implicit speed limit, in kilometers per hour, of the class of road within its country.
+        fn implicit_speed(&self) -> Option<Speed> {
+            ImplicitSpeed::for_road(self.country?, self.road_class?)
+                .speed()?
+                .in_kmh()
+        }
+
+        /// The penalty of an applicable conditional speed limit below the
+        /// unconditional limit, such as `maxspeed:conditional=30 @ (06:00-18:00)`,
+        /// being the ratio of the two.
+        fn speed_penalty(&self, conditions: &TraversalConditions) -> f64 {
+            let Some(limits) = &self.speed_limit else {
+                return 1.0;
+            };
+
+            match (
+                limits
+                    .unconditional(conditions)
+                    .or_else(|| self.implicit_speed()),
+                limits.limit_under(conditions),
+            ) {
+                (Some(base), Some(limit)) if limit < base => base.get() as f64 / limit.get() as f64,
+                _ => 1.0,
+            }
+        }
+    }
+
+    /// Edge metadata picked from the tags of a way, which may therefore be ingested into a
+    /// graph, such as [`OsmEdgeMetadata`] or a custom type whose fields are each extracted
+    /// by a [`TagRule`](crate::osm::query::TagRule).
+    pub trait OsmMetadata:
+        'static
+        + for<'a> Metadata<
+            Raw<'a> = &'a Tags,
+            Node: 'static + PartialEq + for<'b> NodeMetadata<Raw<'b> = &'b Tags>,
+        >
+    {
+        /// The base weight of each of the segments of the way, of which it has `segments`,
+        /// or `None` should the way not be routable, in which case it is not ingested.
+        fn weighting(&self, segments: usize) -> Option<u32>;
+
+        /// Records the country in which the way lies, as resolved from its position.
+        fn locate(&mut self, _country: Option<CountryCode>) {}
+    }
+
+    impl OsmMetadata for OsmEdgeMetadata {
+        /// Ferries are weighted by the duration of the crossing, and roads by their class.
+        /// Ways which are neither are not routable.
+        fn weighting(&self, segments: usize) -> Option<u32> {
+            self.ferry
+                .map(|ferry| ferry.weighting(segments))
+                .or_else(|| self.road_class.map(|class| class.weighting()))
+        }
+
+        fn locate(&mut self, country: Option<CountryCode>) {
+            self.country = country;
+        }
     }
 
     impl Metadata for OsmEdgeMetadata {
         type Raw<'a> = &'a Tags;
+        type Runtime = TraversalConditions;
 
         fn pick(raw: Self::Raw<'_>) -> Self {
             Self {
                 road_class: raw.r#as::<RoadClass>(TagString::HIGHWAY),
-                lane_count: raw.r#as::<NonZeroU8>(TagString::LANES),
+                ferry: raw.ferry(),
+                tolls: raw.tolls(),
+                lanes: raw.lanes(),
+                names: raw.names(),
                 speed_limit: raw.speed_limit(),
                 access: raw.access(),
+                conditional_access: raw.conditional_access(),
+                oneway: raw.oneway(),
+                vehicle: raw.vehicle_restrictions(),
+                surface: raw.r#as::<Surface>(TagString::SURFACE),
+                smoothness: raw.r#as::<Smoothness>(TagString::SMOOTHNESS),
+                tracktype: raw.r#as::<TrackType>(TagString::TRACK_TYPE),
+                incline: raw.r#as::<Incline>(TagString::INCLINE),
+                width: raw
+                    .get(TagString::WIDTH)
+                    .and_then(|width| measure(VehicleProperty::Width, width)),
+                country: None,
+            }
+        }
+
+        type Node = OsmNodeMetadata;
+
+        /// The trip, such as its time, decides which conditional restrictions apply,
+        /// and so traversals of a known trip are not shared, since each would otherwise
+        /// be cached by its own time.
+        fn cache_key(runtime: &Self::Runtime) -> Option<Self::Runtime> {
+            (runtime.trip == TripContext::default()).then(|| runtime.clone())
+        }
+
+        fn accessible(&self, runtime: &Self::Runtime, direction: Directionality) -> bool {
+            self.permits_toward(runtime, direction)
+                && self.oneway.permits_under(runtime, direction)
+                && (!runtime.transport_mode.is_vehicle() || self.vehicle.permits(runtime))
+                && self
+                    .smoothness
+                    .is_none_or(|smoothness| smoothness.permits(runtime.transport_mode))
+        }
+
+        fn penalty(&self, runtime: &Self::Runtime) -> f64 {
+            self.vehicle.penalty(runtime)
+                * self.speed_penalty(runtime)
+                * self.surface_penalty(runtime)
+                * self.avoid_penalty(runtime)
+                * self.access_penalty(runtime)
+        }
+    }
+
+    /// The metadata of a node which affects traversal, such as a `barrier=*`
+    /// or a `highway=traffic_signals`.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:barrier
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct OsmNodeMetadata {
+        pub barrier: Option<TagString>,
+        pub traffic_signals: bool,
+        pub stop: bool,
+        pub give_way: bool,
+        pub crossing: bool,
+    }
+
+    /// How a `barrier=*` affects the vehicles passing it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Barrier {
+        /// Prevents motor vehicles from passing, such as a bollard or jersey barrier.
+        Blocking,
+        /// Is stopped at, such as a gate which is opened or a toll booth.
+        Slowing,
+        /// Is passed without stopping, such as a kerb or cattle grid.
+        Passable,
+    }
+
+    impl Barrier {
+        /// Classifies the value of the `barrier=*` tag, where unknown
+        /// barriers are presumed to be passable.
+        pub fn from_value(value: &str) -> Self {
+            match value {
+                "bollard"
+                | "block"
+                | "jersey_barrier"
+                | "chain"
+                | "rope"
+                | "log"
+                | "planter"
+                | "debris"
+                | "bus_trap"
+                | "motorcycle_barrier"
+                | "cycle_barrier"
+                | "kissing_gate"
+                | "stile"
+                | "turnstile"
+                | "full-height_turnstile"
+                | "fence"
+                | "wall" => Self::Blocking,
+                "gate" | "lift_gate" | "swing_gate" | "sliding_gate" | "hampshire_gate"
+                | "toll_booth" | "border_control" | "sally_port" => Self::Slowing,
+                _ => Self::Passable,
+            }
+        }
+    }
+
+    impl OsmNodeMetadata {
+        /// If the node is a toll booth, being a barrier at which a toll is paid.
+        pub fn toll_booth(&self) -> bool {
+            self.barrier
+                .as_ref()
+                .is_some_and(|barrier| barrier.as_str() == "toll_booth")
+        }
+
+        /// How the barrier on the node, should it have one, affects vehicles.
+        pub fn barrier(&self) -> Option<Barrier> {
+            self.barrier
+                .as_ref()
+                .map(|barrier| Barrier::from_value(barrier))
+        }
+    }
+
+    impl NodeMetadata for OsmNodeMetadata {
+        type Raw<'a> = &'a Tags;
+        type Runtime = TraversalConditions;
+
+        fn pick(raw: Self::Raw<'_>) -> Option<Self> {
+            let highway = raw.get(TagString::HIGHWAY).map(|v| v.as_str());
+
+            let metadata = Self {
+                barrier: raw.get(TagString::BARRIER).cloned(),
+                traffic_signals: highway == Some("traffic_signals")
+                    || raw
+                        .get(TagString::CROSSING)
+                        .is_some_and(|v| v.as_str() == "traffic_signals"),
+                stop: highway == Some("stop"),
+                give_way: highway == Some("give_way"),
+                crossing: highway == Some("crossing") || raw.get(TagString::CROSSING).is_some(),
+            };
+
+            (metadata != Self::default()).then_some(metadata)
+        }
+
+        fn accessible(&self, runtime: &Self::Runtime) -> bool {
+            let mode = runtime.transport_mode;
+            let motorised = mode.is_vehicle() && !mode.is_non_motorized();
+
+            !(motorised && self.barrier() == Some(Barrier::Blocking))
+        }
+
+        fn cost(&self, runtime: &Self::Runtime) -> u32 {
+            let vehicle = runtime.transport_mode.is_vehicle();
+            let mut cost = 0;
+
+            if vehicle && self.barrier() == Some(Barrier::Slowing) {
+                cost += BARRIER_COST;
+            }
+
+            if self.traffic_signals {
+                cost += TRAFFIC_SIGNALS_COST;
+            }
+
+            if vehicle && self.stop {
+                cost += STOP_COST;
+            }
+
+            if vehicle && self.give_way {
+                cost += GIVE_WAY_COST;
             }
+
+            if runtime.avoid.tolls && self.toll_booth() {
+                cost += AVOID_COST;
+            }
+
+            cost
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::osm::primitives::condition::PurposeCondition;
+        use crate::osm::{Avoidance, TripContext, VehicleProfile};
+        use Directionality::{Backward, Forward};
+        use chrono::NaiveDate;
+
+        fn metadata(pairs: &[(&str, &str)]) -> OsmEdgeMetadata {
+            OsmEdgeMetadata::pick(&pairs.iter().copied().collect())
+        }
+
+        #[test]
+        fn implicit_access() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+            let bus = TraversalConditions::new(TransportMode::Bus);
+
+            let motorway = metadata(&[("highway", "motorway")]);
+            assert!(motorway.accessible(&car, Forward));
+            assert!(!motorway.accessible(&bicycle, Forward));
+            assert!(!motorway.accessible(&foot, Forward));
+
+            let busway = metadata(&[("highway", "busway")]);
+            assert!(busway.accessible(&bus, Forward));
+            assert!(!busway.accessible(&car, Forward));
+        }
+
+        #[test]
+        fn explicit_access() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+            let bus = TraversalConditions::new(TransportMode::Bus);
+
+            let road = metadata(&[("highway", "residential"), ("motor_vehicle", "no")]);
+            assert!(!road.accessible(&car, Forward));
+            assert!(road.accessible(&bicycle, Forward));
+
+            let road = metadata(&[("highway", "residential"), ("access", "no"), ("bus", "yes")]);
+            assert!(road.accessible(&bus, Forward));
+            assert!(!road.accessible(&foot, Forward));
+        }
+
+        #[test]
+        fn restricted_access() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bus = TraversalConditions::new(TransportMode::Bus);
+
+            // A private road served by public transport.
+            let road = metadata(&[
+                ("highway", "service"),
+                ("access", "private"),
+                ("psv", "yes"),
+            ]);
+            assert!(!road.accessible(&car, Forward));
+            assert!(road.accessible(&bus, Forward));
+            assert!(!road.permits(TransportMode::Motorcar));
+
+            let owner = car
+                .clone()
+                .trip(TripContext::default().purpose(PurposeCondition::Private));
+            assert!(road.accessible(&owner, Forward));
+
+            // Local traffic may pass, but only to reach a destination along the road.
+            let road = metadata(&[("highway", "residential"), ("motor_vehicle", "destination")]);
+            assert!(road.accessible(&car, Forward));
+            assert!(road.penalty(&car) > 1.0);
+
+            let visitor = car
+                .clone()
+                .trip(TripContext::default().purpose(PurposeCondition::Destination));
+            assert_eq!(road.penalty(&visitor), 1.0);
+        }
+
+        #[test]
+        fn oneway_access() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+
+            let road = metadata(&[("highway", "primary"), ("oneway", "yes")]);
+            assert!(road.accessible(&car, Forward));
+            assert!(!road.accessible(&car, Backward));
+            assert!(road.accessible(&foot, Backward));
+        }
+
+        #[test]
+        fn directional_access() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+
+            // A contraflow cycle lane along a road closed to motor vehicles in one direction.
+            let road = metadata(&[("highway", "residential"), ("motor_vehicle:backward", "no")]);
+            assert!(road.accessible(&car, Forward));
+            assert!(!road.accessible(&car, Backward));
+            assert!(road.accessible(&bicycle, Backward));
+            assert!(road.permits(TransportMode::Motorcar));
+        }
+
+        #[test]
+        fn vehicle_access() {
+            let truck = VehicleProfile::default().height(4.0).weight(18.0);
+            let hgv = TraversalConditions::new(TransportMode::Hgv).vehicle(truck.clone());
+            let foot = TraversalConditions::new(TransportMode::Foot).vehicle(truck);
+
+            let underpass = metadata(&[("highway", "primary"), ("maxheight", "3.5")]);
+            assert!(!underpass.accessible(&hgv, Forward));
+            assert!(underpass.accessible(&TraversalConditions::new(TransportMode::Hgv), Forward));
+            assert!(underpass.accessible(&foot, Forward));
+        }
+
+        #[test]
+        fn conditional_access() {
+            let at = |mode, hour| {
+                let time = NaiveDate::from_ymd_opt(2025, 1, 6)
+                    .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                    .unwrap();
+
+                TraversalConditions::new(mode).trip(TripContext::default().time(time))
+            };
+
+            let school = metadata(&[
+                ("highway", "residential"),
+                ("motor_vehicle:conditional", "no @ (Mo-Fr 07:00-09:00)"),
+                ("maxspeed", "50"),
+                ("maxspeed:conditional", "25 @ (Mo-Fr 14:00-16:00)"),
+            ]);
+
+            assert!(!school.accessible(&at(TransportMode::Motorcar, 8), Forward));
+            assert!(school.accessible(&at(TransportMode::Bicycle, 8), Forward));
+            assert!(school.accessible(&at(TransportMode::Motorcar, 12), Forward));
+            assert!(school.accessible(&TraversalConditions::default(), Forward));
+            assert!(school.permits(TransportMode::Motorcar));
+
+            assert_eq!(school.penalty(&at(TransportMode::Motorcar, 12)), 1.0);
+            assert_eq!(school.penalty(&at(TransportMode::Motorcar, 15)), 2.0);
+
+            let delivery = metadata(&[
+                ("highway", "residential"),
+                ("motor_vehicle", "no"),
+                ("motor_vehicle:conditional", "yes @ (delivery)"),
+            ]);
+
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let trip = TripContext::default().purpose(PurposeCondition::Delivery);
+            assert!(!delivery.accessible(&car, Forward));
+            assert!(delivery.accessible(&car.trip(trip), Forward));
+        }
+
+        #[test]
+        fn surface_penalty() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+
+            let road = metadata(&[("highway", "residential"), ("surface", "asphalt")]);
+            assert_eq!(road.penalty(&bicycle), 1.0);
+
+            let track = metadata(&[("highway", "track"), ("tracktype", "grade3")]);
+            assert_eq!(track.surface(), Some(Surface::Gravel));
+            assert!(track.penalty(&bicycle) > track.penalty(&car));
+
+            let trail = metadata(&[
+                ("highway", "track"),
+                ("smoothness", "horrible"),
+                ("incline", "-12%"),
+                ("width", "2.5"),
+            ]);
+            assert!(!trail.accessible(&car, Forward));
+            assert!(trail.accessible(&TraversalConditions::new(TransportMode::Mtb), Forward));
+            assert_eq!(trail.incline, Some(Incline::Grade(-12)));
+            assert_eq!(trail.width, Some(250));
+        }
+
+        #[test]
+        fn implicit_speed() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let speed = |meta: &OsmEdgeMetadata| meta.max_speed(&car).map(|speed| speed.get());
+
+            let mut road = metadata(&[("highway", "secondary")]);
+            assert_eq!(speed(&road), None);
+
+            road.country = Some(CountryCode::new(b"FR"));
+            assert_eq!(speed(&road), Some(80));
+
+            // Signposted limits take precedence over the implicit limit.
+            let mut signposted = metadata(&[("highway", "secondary"), ("maxspeed", "70")]);
+            signposted.country = Some(CountryCode::new(b"FR"));
+            assert_eq!(speed(&signposted), Some(70));
+
+            let mut unrestricted = metadata(&[("highway", "motorway"), ("maxspeed", "none")]);
+            unrestricted.country = Some(CountryCode::new(b"AT"));
+            assert_eq!(speed(&unrestricted), None);
+
+            let zone = metadata(&[("highway", "residential"), ("maxspeed", "DE:zone30")]);
+            assert_eq!(speed(&zone), Some(30));
+        }
+
+        #[test]
+        fn ferries_and_tolls() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+
+            let ferry = metadata(&[("route", "ferry"), ("duration", "01:00")]);
+            assert!(ferry.accessible(&car, Forward) && ferry.accessible(&foot, Forward));
+            assert_eq!(ferry.weighting(2), Some(300));
+
+            let passengers = metadata(&[("route", "ferry"), ("motor_vehicle", "no")]);
+            assert!(!passengers.accessible(&car, Forward));
+            assert!(passengers.accessible(&foot, Forward));
+
+            assert_eq!(metadata(&[("route", "bus")]).weighting(2), None);
+
+            let tollway = metadata(&[("highway", "motorway"), ("toll", "yes")]);
+            assert_eq!(tollway.penalty(&car), 1.0);
+
+            let avoid = |avoidance: Avoidance| car.clone().avoid(avoidance);
+            assert!(tollway.penalty(&avoid(Avoidance::default().tolls(true))) > 1.0);
+            assert!(tollway.penalty(&avoid(Avoidance::default().motorways(true))) > 1.0);
+            assert_eq!(
+                tollway.penalty(&avoid(Avoidance::default().ferries(true))),
+                1.0
+            );
+            assert!(ferry.penalty(&avoid(Avoidance::default().ferries(true))) > 1.0);
+        }
+
+        #[test]
+        fn node_cost() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+
+            let node =
+                |pairs: &[(&str, &str)]| OsmNodeMetadata::pick(&pairs.iter().copied().collect());
+
+            assert_eq!(node(&[("name", "Main Street")]), None);
+
+            let signals = node(&[("highway", "traffic_signals")]).unwrap();
+            assert!(signals.traffic_signals);
+            assert!(signals.cost(&car) > 0);
+            assert!(signals.cost(&foot) > 0);
+
+            let gate = node(&[("barrier", "gate")]).unwrap();
+            assert!(gate.cost(&car) > signals.cost(&car));
+            assert_eq!(gate.cost(&foot), 0);
+
+            let kerb = node(&[("barrier", "kerb")]).unwrap();
+            assert_eq!(kerb.cost(&car), 0);
+            assert_eq!(node(&[("barrier", "cattle_grid")]).unwrap().cost(&car), 0);
+
+            let toll = node(&[("barrier", "toll_booth")]).unwrap();
+            assert!(toll.toll_booth() && !gate.toll_booth());
+
+            let avoiding = car.clone().avoid(Avoidance::default().tolls(true));
+            assert!(toll.cost(&avoiding) > toll.cost(&car));
+            assert_eq!(gate.cost(&avoiding), gate.cost(&car));
+        }
+
+        #[test]
+        fn node_barriers() {
+            let car = TraversalConditions::new(TransportMode::Motorcar);
+            let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+            let foot = TraversalConditions::new(TransportMode::Foot);
+
+            let node = |barrier: &str| {
+                OsmNodeMetadata::pick(&Tags::from_iter([("barrier", barrier)])).unwrap()
+            };
+
+            // A bollard keeps cars out, but not bicycles or pedestrians.
+            let bollard = node("bollard");
+            assert_eq!(bollard.barrier(), Some(Barrier::Blocking));
+            assert!(!bollard.accessible(&car));
+            assert!(bollard.accessible(&bicycle) && bollard.accessible(&foot));
+            assert_eq!(bollard.cost(&bicycle), 0);
+
+            // Whereas a gate is opened, and so delays them instead.
+            let gate = node("gate");
+            assert_eq!(gate.barrier(), Some(Barrier::Slowing));
+            assert!(gate.accessible(&car));
+            assert!(gate.cost(&car) > 0);
+
+            let kerb = node("kerb");
+            assert_eq!(kerb.barrier(), Some(Barrier::Passable));
+            assert!(kerb.accessible(&car));
         }
     }
 }
 
 pub mod runtime {
+    use crate::osm::primitives::condition::{
+        PurposeCondition, RoadCondition, SeasonCondition, UserGroupCondition, VehicleProperty,
+    };
+    use crate::osm::primitives::dimension::{Dimension, in_unit};
     use crate::osm::primitives::{Directionality, TransportMode};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use geo::Point;
+    use std::collections::BTreeSet;
     use std::num::NonZeroU8;
 
+    /// The precision to which the location of a trip is held, in degrees.
+    const LOCATION_PRECISION: f64 = 1e-6;
+
     // TODO: Internalise
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub struct TraversalConditions {
         pub transport_mode: TransportMode,
         pub directionality: Directionality,
+        /// The lane of travel, numbered from one at the left
+        /// in the direction of [`directionality`](Self::directionality).
         pub lane: Option<NonZeroU8>,
+        pub vehicle: VehicleProfile,
+        pub trip: TripContext,
+        pub avoid: Avoidance,
+    }
+
+    impl Default for TraversalConditions {
+        fn default() -> Self {
+            Self {
+                transport_mode: TransportMode::Motorcar,
+                directionality: Directionality::BothWays,
+                lane: None,
+                vehicle: VehicleProfile::default(),
+                trip: TripContext::default(),
+                avoid: Avoidance::default(),
+            }
+        }
+    }
+
+    impl TraversalConditions {
+        pub fn new(transport_mode: TransportMode) -> Self {
+            Self {
+                transport_mode,
+                ..Self::default()
+            }
+        }
+
+        pub fn vehicle(self, vehicle: VehicleProfile) -> Self {
+            Self { vehicle, ..self }
+        }
+
+        pub fn trip(self, trip: TripContext) -> Self {
+            Self { trip, ..self }
+        }
+
+        pub fn avoid(self, avoid: Avoidance) -> Self {
+            Self { avoid, ..self }
+        }
+
+        /// The conditions of travel along an edge in the given direction,
+        /// relative to how its way is drawn.
+        pub fn toward(&self, directionality: Directionality) -> Self {
+            Self {
+                directionality,
+                ..self.clone()
+            }
+        }
+
+        pub fn lane(self, lane: NonZeroU8) -> Self {
+            Self {
+                lane: Some(lane),
+                ..self
+            }
+        }
+    }
+
+    /// The features of the network which the traveller would rather not use, such as tolls.
+    ///
+    /// These are strongly penalised, rather than excluded, such that a route
+    /// may still use them should no reasonable alternative exist.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct Avoidance {
+        /// Avoids tolled ways and toll booths.
+        pub tolls: bool,
+        /// Avoids ferry routes.
+        pub ferries: bool,
+        /// Avoids motorways and their link roads.
+        pub motorways: bool,
+    }
+
+    impl Avoidance {
+        pub fn tolls(self, tolls: bool) -> Self {
+            Self { tolls, ..self }
+        }
+
+        pub fn ferries(self, ferries: bool) -> Self {
+            Self { ferries, ..self }
+        }
+
+        pub fn motorways(self, motorways: bool) -> Self {
+            Self { motorways, ..self }
+        }
+    }
+
+    /// The circumstances of the trip being matched or routed, against which
+    /// conditional restrictions such as `access:conditional=no @ (Mo-Fr 07:00-09:00)`
+    /// are evaluated.
+    ///
+    /// Conditions upon a circumstance which is unknown, such as the
+    /// time of travel, are never considered to apply.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+    pub struct TripContext {
+        /// The local date and time of travel.
+        pub time: Option<NaiveDateTime>,
+        /// The public holidays observed where travelling.
+        pub holidays: BTreeSet<NaiveDate>,
+        /// The season of travel, otherwise derived from the time of travel.
+        pub season: Option<SeasonCondition>,
+        /// The weather and road conditions, if known.
+        pub road: Option<Vec<RoadCondition>>,
+        /// The number of occupants of the vehicle, if known.
+        pub occupants: Option<u32>,
+        /// The user groups the traveller belongs to, such as `disabled`.
+        pub user_groups: Vec<UserGroupCondition>,
+        /// The purpose of travel, such as `delivery`.
+        pub purpose: Option<PurposeCondition>,
+        /// The intended duration of stay, in minutes.
+        pub stay: Option<u32>,
+
+        /// The location of travel, in millionths of a degree.
+        location: Option<Point<i32>>,
+    }
+
+    impl TripContext {
+        pub fn time(self, time: NaiveDateTime) -> Self {
+            Self {
+                time: Some(time),
+                ..self
+            }
+        }
+
+        pub fn holidays(self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+            Self {
+                holidays: holidays.into_iter().collect(),
+                ..self
+            }
+        }
+
+        pub fn season(self, season: SeasonCondition) -> Self {
+            Self {
+                season: Some(season),
+                ..self
+            }
+        }
+
+        pub fn road(self, road: impl IntoIterator<Item = RoadCondition>) -> Self {
+            Self {
+                road: Some(road.into_iter().collect()),
+                ..self
+            }
+        }
+
+        pub fn occupants(self, occupants: u32) -> Self {
+            Self {
+                occupants: Some(occupants),
+                ..self
+            }
+        }
+
+        pub fn user_groups(
+            self,
+            user_groups: impl IntoIterator<Item = UserGroupCondition>,
+        ) -> Self {
+            Self {
+                user_groups: user_groups.into_iter().collect(),
+                ..self
+            }
+        }
+
+        pub fn purpose(self, purpose: PurposeCondition) -> Self {
+            Self {
+                purpose: Some(purpose),
+                ..self
+            }
+        }
+
+        /// The intended duration of stay, in minutes.
+        pub fn stay(self, minutes: u32) -> Self {
+            Self {
+                stay: Some(minutes),
+                ..self
+            }
+        }
+
+        /// The approximate location of travel, from which the times of sunrise
+        /// and sunset, and the hemisphere of the season, are derived.
+        pub fn location(self, location: Point) -> Self {
+            let quantize = |degrees: f64| (degrees / LOCATION_PRECISION).round() as i32;
+
+            Self {
+                location: Some(Point::new(quantize(location.x()), quantize(location.y()))),
+                ..self
+            }
+        }
+
+        pub(crate) fn point(&self) -> Option<Point> {
+            self.location.map(|location| {
+                Point::new(
+                    location.x() as f64 * LOCATION_PRECISION,
+                    location.y() as f64 * LOCATION_PRECISION,
+                )
+            })
+        }
+    }
+
+    /// The physical properties of the vehicle being matched or routed.
+    ///
+    /// Edges whose dimension limits (such as `maxheight`) the vehicle
+    /// exceeds, or which prohibit the hazardous materials it carries,
+    /// are not traversable. Unknown dimensions are never restricted.
+    ///
+    /// Dimensions are held as a [`Dimension`], in kilograms or centimeters,
+    /// such that the profile may be hashed as part of the runtime conditions.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+    pub struct VehicleProfile {
+        pub weight: Option<Dimension>,
+        pub axleload: Option<Dimension>,
+        pub height: Option<Dimension>,
+        pub width: Option<Dimension>,
+        pub length: Option<Dimension>,
+
+        /// If the vehicle is carrying hazardous materials.
+        pub hazmat: bool,
+    }
+
+    impl VehicleProfile {
+        /// The total weight of the vehicle, in tonnes.
+        pub fn weight(self, tonnes: f64) -> Self {
+            Self {
+                weight: in_unit(VehicleProperty::Weight, tonnes, "t"),
+                ..self
+            }
+        }
+
+        /// The maximum load upon any axle of the vehicle, in tonnes.
+        pub fn axleload(self, tonnes: f64) -> Self {
+            Self {
+                axleload: in_unit(VehicleProperty::Axleload, tonnes, "t"),
+                ..self
+            }
+        }
+
+        /// The height of the vehicle, in meters.
+        pub fn height(self, meters: f64) -> Self {
+            Self {
+                height: in_unit(VehicleProperty::Height, meters, "m"),
+                ..self
+            }
+        }
+
+        /// The width of the vehicle, in meters.
+        pub fn width(self, meters: f64) -> Self {
+            Self {
+                width: in_unit(VehicleProperty::Width, meters, "m"),
+                ..self
+            }
+        }
+
+        /// The length of the vehicle, in meters.
+        pub fn length(self, meters: f64) -> Self {
+            Self {
+                length: in_unit(VehicleProperty::Length, meters, "m"),
+                ..self
+            }
+        }
+
+        pub fn hazmat(self, hazmat: bool) -> Self {
+            Self { hazmat, ..self }
+        }
+
+        /// The dimension of the vehicle for the given property, if known.
+        pub fn dimension(&self, property: VehicleProperty) -> Option<Dimension> {
+            match property {
+                VehicleProperty::Weight => self.weight,
+                VehicleProperty::Axleload => self.axleload,
+                VehicleProperty::Height => self.height,
+                VehicleProperty::Width => self.width,
+                VehicleProperty::Length => self.length,
+                VehicleProperty::Wheels | VehicleProperty::Draught => None,
+            }
+        }
     }
 }
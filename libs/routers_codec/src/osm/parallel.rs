@@ -1,3 +1,5 @@
+use crate::osm::error::CodecError;
+
 /// Defines the set of functions available on a
 /// parallel iterator. This allows for more
 /// efficient traversal of elements within
@@ -5,6 +7,9 @@
 ///
 /// Requires defining the item being traversed
 /// over, with a <'a> lifetime.
+///
+/// Traversal stops at the first block which cannot
+/// be decoded, returning its [`CodecError`].
 pub trait Parallel {
     type Item<'a>;
 
@@ -12,7 +17,7 @@ pub trait Parallel {
     ///
     /// The traversing function must be
     /// both `Send` and `Sync`.
-    fn for_each<F>(self, f: F)
+    fn for_each<F>(self, f: F) -> Result<(), CodecError>
     where
         F: for<'a> Fn(Self::Item<'_>) + Send + Sync;
 
@@ -40,15 +45,21 @@ pub trait Parallel {
     ///     .expect("!")
     ///     .map_red(|item| {
     ///        match item {
-    ///            ProcessedElement::Node(_) => 1,
+    ///            ProcessedElement::Node(..) => 1,
     ///            _ => 0
     ///        }
-    ///     }, |a, b| a + b, || 0);
+    ///     }, |a, b| a + b, || 0)
+    ///     .expect("!");
     /// ```
     ///
     /// ### Idea
     /// The idea for this function was obtained from [osmpbf](https://github.com/b-r-u/osmpbf/blob/5907ca998a30ef51941bf40257ec78cf8e0b66ed/src/reader.rs#L119)
-    fn map_red<Map, Reduce, Identity, T>(self, map_op: Map, red_op: Reduce, ident: Identity) -> T
+    fn map_red<Map, Reduce, Identity, T>(
+        self,
+        map_op: Map,
+        red_op: Reduce,
+        ident: Identity,
+    ) -> Result<T, CodecError>
     where
         Map: for<'a> Fn(Self::Item<'_>) -> T + Send + Sync,
         Reduce: Fn(T, T) -> T + Send + Sync,
@@ -78,12 +89,13 @@ pub trait Parallel {
     /// let nodes = ProcessedElementIterator::new(path)
     ///     .expect("!")
     ///     .par_red(|tree, item| {
-    ///         if let ProcessedElement::Node(node) = item {
+    ///         if let ProcessedElement::Node(node, _) = item {
     ///             tree.insert(node.id, node);
     ///         }
     ///
     ///         tree
-    ///     }, |a, b| BTreeMap::from_iter(a.iter().chain(b.iter())), || BTreeMap::new());
+    ///     }, |a, b| BTreeMap::from_iter(a.iter().chain(b.iter())), || BTreeMap::new())
+    ///     .expect("!");
     /// ```
     ///
     /// ### Idea
@@ -93,7 +105,7 @@ pub trait Parallel {
         fold_op: Reduce,
         combine: Combine,
         ident: Identity,
-    ) -> T
+    ) -> Result<T, CodecError>
     where
         Reduce: for<'a> Fn(T, Self::Item<'_>) -> T + Send + Sync,
         Identity: Fn() -> T + Send + Sync,
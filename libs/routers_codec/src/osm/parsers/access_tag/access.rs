@@ -1,5 +1,6 @@
-use crate::osm::primitives::TransportMode;
-use crate::osm::{Parser, TagString, Tags};
+use crate::osm::primitives::condition::{PurposeCondition, UserGroupCondition};
+use crate::osm::primitives::{Condition, Directionality, TransportMode};
+use crate::osm::{Parser, TagString, Tags, TripContext};
 use std::str::FromStr;
 use strum::{AsRefStr, Display, EnumIter, EnumString};
 
@@ -43,6 +44,60 @@ pub enum AccessValue {
     Variable,
 }
 
+impl AccessValue {
+    /// Determines if the value grants access to the general public of
+    /// the tagged transport mode.
+    ///
+    /// Values which restrict access to a specific group, such as private,
+    /// customer or agricultural traffic, are treated as prohibitive. Values
+    /// which only discourage access, or restrict it to those with business
+    /// along the way, such as `destination` or `delivery`, are not.
+    pub fn permits(&self) -> bool {
+        !matches!(
+            self,
+            Self::No
+                | Self::Private
+                | Self::Permit
+                | Self::Customers
+                | Self::Agricultural
+                | Self::Forestry
+                | Self::Military
+        )
+    }
+
+    /// Determines if the value grants access to the traveller of the trip, being
+    /// those it [permits](Self::permits) and the groups it restricts access to,
+    /// should the purpose or user groups of the trip declare it belongs to them.
+    pub fn permits_trip(&self, trip: &TripContext) -> bool {
+        let purpose = |purpose| trip.purpose == Some(purpose);
+        let group = |group| trip.user_groups.contains(&group);
+
+        self.permits()
+            || match self {
+                Self::Private => purpose(PurposeCondition::Private),
+                Self::Permit => {
+                    purpose(PurposeCondition::Permit) || group(UserGroupCondition::Permit)
+                }
+                Self::Customers => {
+                    purpose(PurposeCondition::Customers) || group(UserGroupCondition::Customers)
+                }
+                Self::Agricultural => purpose(PurposeCondition::Agricultural),
+                Self::Forestry => purpose(PurposeCondition::Forestry),
+                _ => false,
+            }
+    }
+
+    /// Determines if the value restricts access to those with business along the way,
+    /// such as `destination` or `delivery`, which the purpose of the trip does not declare.
+    pub fn restricts_trip(&self, trip: &TripContext) -> bool {
+        match self {
+            Self::Destination => trip.purpose != Some(PurposeCondition::Destination),
+            Self::Delivery => trip.purpose != Some(PurposeCondition::Delivery),
+            _ => false,
+        }
+    }
+}
+
 /// Physical accessibility restrictions (not legal restrictions)
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Display, EnumString, EnumIter, AsRefStr)]
 #[strum(serialize_all = "snake_case")]
@@ -55,10 +110,24 @@ pub enum PhysicalAccess {
     Stroller,
 }
 
+/// Splits the direction of travel from a key, such as `motor_vehicle:forward`.
+fn directed(key: &str) -> (&str, Option<Directionality>) {
+    [Directionality::Forward, Directionality::Backward]
+        .into_iter()
+        .find_map(|direction| {
+            let mode = key.strip_suffix(direction.as_ref())?.strip_suffix(':')?;
+            Some((mode, Some(direction)))
+        })
+        .unwrap_or((key, None))
+}
+
 /// Main parser structure for OSM access tags
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccessTag {
     pub transport_mode: TransportMode,
+    /// The direction of travel the access applies in, like `motor_vehicle:forward=no`,
+    /// or both should it be `None`.
+    pub direction: Option<Directionality>,
     pub access: AccessValue,
 }
 
@@ -67,20 +136,29 @@ impl AccessTag {
     ///
     /// # Examples
     /// ```
-    /// use std::str::FromStr;
     /// use routers_codec::osm::access_tag::AccessTag;
+    /// use routers_codec::osm::primitives::Directionality;
     ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// // Parse "bicycle=no"
     /// let tag = AccessTag::from_key_value("bicycle", "no")?;
     ///
     /// // Parse "motor_vehicle=destination"
     /// let tag = AccessTag::from_key_value("motor_vehicle", "destination")?;
+    ///
+    /// // Parse "hgv:backward=no"
+    /// let tag = AccessTag::from_key_value("hgv:backward", "no")?;
+    /// assert_eq!(tag.direction, Some(Directionality::Backward));
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn from_key_value(key: &str, value: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let access_value = AccessValue::try_from(value)?;
+        let (mode, direction) = directed(key);
 
         Ok(AccessTag {
-            transport_mode: TransportMode::from_str(key)?,
+            transport_mode: TransportMode::from_str(mode)?,
+            direction,
             access: access_value,
         })
     }
@@ -91,7 +169,66 @@ impl AccessTag {
 
     #[cfg(test)]
     fn to_key_value(&self) -> (String, String) {
-        (self.transport_mode.to_string(), self.access.to_string())
+        let key = match self.direction {
+            Some(direction) => format!("{}:{direction}", self.transport_mode),
+            None => self.transport_mode.to_string(),
+        };
+
+        (key, self.access.to_string())
+    }
+}
+
+/// An access restriction which only applies under a [`Condition`],
+/// like `motor_vehicle:conditional=no @ (Mo-Fr 07:00-09:00)`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Conditional_restrictions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalAccessTag {
+    pub transport_mode: TransportMode,
+    /// The direction of travel the access applies in, like
+    /// `motor_vehicle:forward:conditional`, or both should it be `None`.
+    pub direction: Option<Directionality>,
+    pub access: AccessValue,
+    pub condition: Condition,
+}
+
+impl ConditionalAccessTag {
+    const SUFFIX: &str = ":conditional";
+
+    fn from_tag((key, value): (&TagString, &TagString)) -> Vec<Self> {
+        let Some((transport_mode, direction)) = key.strip_suffix(Self::SUFFIX).and_then(|key| {
+            let (mode, direction) = directed(key);
+            Some((TransportMode::from_str(mode).ok()?, direction))
+        }) else {
+            return vec![];
+        };
+
+        Condition::parse_conditional(value)
+            .into_iter()
+            .filter_map(|(value, condition)| {
+                Some(ConditionalAccessTag {
+                    transport_mode,
+                    direction,
+                    access: AccessValue::from_str(value).ok()?,
+                    condition,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Parser for Vec<ConditionalAccessTag> {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let as_vec = tags
+            .iter()
+            .flat_map(ConditionalAccessTag::from_tag)
+            .collect::<Vec<_>>();
+
+        if as_vec.is_empty() {
+            None
+        } else {
+            Some(as_vec)
+        }
     }
 }
 
@@ -129,6 +266,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_access_value_permits() {
+        assert!(AccessValue::Yes.permits());
+        assert!(AccessValue::Destination.permits());
+        assert!(!AccessValue::Private.permits());
+        assert!(!AccessValue::Customers.permits());
+        assert!(!AccessValue::No.permits());
+        assert!(!AccessValue::Agricultural.permits());
+
+        let anyone = TripContext::default();
+        assert!(!AccessValue::Private.permits_trip(&anyone));
+        assert!(AccessValue::Destination.permits_trip(&anyone));
+        assert!(AccessValue::Destination.restricts_trip(&anyone));
+
+        let owner = TripContext::default().purpose(PurposeCondition::Private);
+        assert!(AccessValue::Private.permits_trip(&owner));
+        assert!(!AccessValue::Permit.permits_trip(&owner));
+
+        let holder = TripContext::default().user_groups([UserGroupCondition::Permit]);
+        assert!(AccessValue::Permit.permits_trip(&holder));
+
+        let courier = TripContext::default().purpose(PurposeCondition::Delivery);
+        assert!(!AccessValue::Delivery.restricts_trip(&courier));
+        assert!(!AccessValue::No.permits_trip(&courier));
+    }
+
     #[test]
     fn test_transport_mode_parsing() {
         let tag = AccessTag::from_key_value("bicycle", "no").unwrap();
@@ -138,11 +301,27 @@ mod tests {
         assert_eq!(tag.access, AccessValue::Destination);
     }
 
+    #[test]
+    fn test_directional_parsing() {
+        let tag = AccessTag::from_key_value("motor_vehicle:forward", "no").unwrap();
+        assert_eq!(tag.transport_mode, TransportMode::MotorVehicle);
+        assert_eq!(tag.direction, Some(Directionality::Forward));
+
+        // Modes which contain a colon are not mistaken for a direction.
+        let tag = AccessTag::from_key_value("ski:nordic", "designated").unwrap();
+        assert_eq!(tag.transport_mode, TransportMode::SkiNordic);
+        assert_eq!(tag.direction, None);
+
+        assert!(AccessTag::from_key_value("bicycle:left", "no").is_err());
+    }
+
     #[test]
     fn test_round_trip_conversion() {
-        let original_tag = AccessTag::from_key_value("foot", "yes").unwrap();
-        let (key, value) = original_tag.to_key_value();
-        let parsed_tag = AccessTag::from_key_value(&key, &value).unwrap();
-        assert_eq!(original_tag, parsed_tag);
+        for (key, value) in [("foot", "yes"), ("hgv:backward", "delivery")] {
+            let original_tag = AccessTag::from_key_value(key, value).unwrap();
+            let (key, value) = original_tag.to_key_value();
+            let parsed_tag = AccessTag::from_key_value(&key, &value).unwrap();
+            assert_eq!(original_tag, parsed_tag);
+        }
     }
 }
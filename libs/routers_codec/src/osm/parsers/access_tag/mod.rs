@@ -1,14 +1,21 @@
 pub mod access;
+pub mod resolve;
 
 use crate::osm::{Parser, Tags};
-pub use access::AccessTag;
+pub use access::{AccessTag, ConditionalAccessTag};
+pub use resolve::AccessResolver;
 
 pub trait Access {
     fn access(&self) -> Vec<AccessTag>;
+    fn conditional_access(&self) -> Vec<ConditionalAccessTag>;
 }
 
 impl Access for Tags {
     fn access(&self) -> Vec<AccessTag> {
         Vec::<AccessTag>::parse(self).unwrap_or_default()
     }
+
+    fn conditional_access(&self) -> Vec<ConditionalAccessTag> {
+        Vec::<ConditionalAccessTag>::parse(self).unwrap_or_default()
+    }
 }
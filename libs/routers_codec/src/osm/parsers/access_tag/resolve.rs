@@ -0,0 +1,230 @@
+use crate::osm::TraversalConditions;
+use crate::osm::access_tag::access::AccessValue;
+use crate::osm::access_tag::{AccessTag, ConditionalAccessTag};
+use crate::osm::primitives::{Directionality, TransportMode};
+
+/// Resolves the access of a transport mode along a way, in a direction of travel.
+///
+/// The transport mode's [hierarchy](TransportMode::hierarchy) is walked from the mode itself
+/// toward `access`, such that `motor_vehicle=no` implies `motorcar=no` while `motorcar=yes`
+/// overrides it. Within each mode, the more specific key decides, in the order of
+/// `mode:forward:conditional`, `mode:forward`, `mode:conditional` and then `mode`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:access
+#[derive(Debug, Clone, Copy)]
+pub struct AccessResolver<'a> {
+    access: &'a [AccessTag],
+    conditional: &'a [ConditionalAccessTag],
+}
+
+impl<'a> AccessResolver<'a> {
+    pub fn new(access: &'a [AccessTag], conditional: &'a [ConditionalAccessTag]) -> Self {
+        Self {
+            access,
+            conditional,
+        }
+    }
+
+    /// The access of the transport mode in the direction of travel,
+    /// disregarding any conditional restriction.
+    ///
+    /// Should no tag within the hierarchy apply, the access is not
+    /// known from the tags, and is instead implied by the kind of way.
+    pub fn resolve(
+        &self,
+        mode: TransportMode,
+        direction: Directionality,
+    ) -> Option<&'a AccessValue> {
+        self.walk(mode, direction, |_, _| None)
+    }
+
+    /// The access under the traversal conditions in the direction of travel, where an
+    /// applicable conditional access tag replaces that of the same mode and direction.
+    pub fn resolve_under(
+        &self,
+        conditions: &TraversalConditions,
+        direction: Directionality,
+    ) -> Option<&'a AccessValue> {
+        self.walk(conditions.transport_mode, direction, |mode, direction| {
+            self.conditional
+                .iter()
+                .rev()
+                .find(|tag| {
+                    tag.transport_mode == mode
+                        && tag.direction == direction
+                        && tag.condition.applies(conditions) == Some(true)
+                })
+                .map(|tag| &tag.access)
+        })
+    }
+
+    fn walk(
+        &self,
+        mode: TransportMode,
+        direction: Directionality,
+        conditional: impl Fn(TransportMode, Option<Directionality>) -> Option<&'a AccessValue>,
+    ) -> Option<&'a AccessValue> {
+        // Only travel along the way is directed, unlike `access:left=*`.
+        let directed = matches!(
+            direction,
+            Directionality::Forward | Directionality::Backward
+        )
+        .then_some(direction);
+
+        mode.hierarchy().find_map(|mode| {
+            directed
+                .into_iter()
+                .map(Some)
+                .chain([None])
+                .find_map(|direction| {
+                    conditional(mode, direction).or_else(|| {
+                        self.access
+                            .iter()
+                            .find(|tag| tag.transport_mode == mode && tag.direction == direction)
+                            .map(|tag| &tag.access)
+                    })
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::access_tag::Access;
+    use crate::osm::{Tags, TripContext};
+    use Directionality::{Backward, BothWays, Forward};
+    use TransportMode::*;
+    use chrono::NaiveDate;
+
+    /// Resolves the access of each mode in the direction from the tags.
+    fn resolve(
+        pairs: &[(&str, &str)],
+        direction: Directionality,
+        modes: &[TransportMode],
+    ) -> Vec<Option<AccessValue>> {
+        let tags = pairs.iter().copied().collect::<Tags>();
+        let (access, conditional) = (tags.access(), tags.conditional_access());
+        let resolver = AccessResolver::new(&access, &conditional);
+
+        modes
+            .iter()
+            .map(|mode| resolver.resolve(*mode, direction).cloned())
+            .collect()
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        use AccessValue::{Destination, No, Private, Yes};
+
+        // A footway closed to all but pedestrians.
+        assert_eq!(
+            resolve(
+                &[("access", "no"), ("foot", "yes")],
+                Forward,
+                &[Foot, Bicycle, Motorcar]
+            ),
+            [Some(Yes), Some(No), Some(No)]
+        );
+
+        // `motor_vehicle=no` implies `motorcar=no`, but says nothing of bicycles.
+        assert_eq!(
+            resolve(
+                &[("motor_vehicle", "no")],
+                Forward,
+                &[Motorcar, Motorcycle, Bicycle, Foot]
+            ),
+            [Some(No), Some(No), None, None]
+        );
+
+        // Bicycles are exempt from a closure to vehicles.
+        assert_eq!(
+            resolve(
+                &[("vehicle", "no"), ("bicycle", "yes")],
+                Forward,
+                &[Bicycle, Mtb, Motorcar, Foot]
+            ),
+            [Some(Yes), Some(Yes), Some(No), None]
+        );
+
+        // A private road served by public transport.
+        assert_eq!(
+            resolve(
+                &[("access", "private"), ("psv", "yes")],
+                Forward,
+                &[Bus, Taxi, Motorcar]
+            ),
+            [Some(Yes), Some(Yes), Some(Private)]
+        );
+
+        // Local traffic only, and no lorries at all.
+        assert_eq!(
+            resolve(
+                &[("motor_vehicle", "destination"), ("hgv", "no")],
+                Forward,
+                &[Motorcar, Hgv, HgvArticulated]
+            ),
+            [Some(Destination), Some(No), Some(No)]
+        );
+    }
+
+    #[test]
+    fn test_direction() {
+        use AccessValue::{Delivery, No, Yes};
+
+        let contraflow = [("motor_vehicle:forward", "no")];
+        assert_eq!(
+            resolve(&contraflow, Forward, &[Motorcar, Bicycle]),
+            [Some(No), None]
+        );
+        assert_eq!(resolve(&contraflow, Backward, &[Motorcar]), [None]);
+        // Directed access is disregarded should the direction not be known.
+        assert_eq!(resolve(&contraflow, BothWays, &[Motorcar]), [None]);
+
+        // The direction replaces the undirected access of the same mode.
+        let deliveries = [("hgv", "no"), ("hgv:backward", "delivery")];
+        assert_eq!(resolve(&deliveries, Forward, &[Hgv]), [Some(No)]);
+        assert_eq!(resolve(&deliveries, Backward, &[Hgv]), [Some(Delivery)]);
+
+        // While a more specific mode replaces the directed access of its parent.
+        let cycles = [("vehicle:forward", "no"), ("bicycle", "yes")];
+        assert_eq!(
+            resolve(&cycles, Forward, &[Bicycle, Motorcar]),
+            [Some(Yes), Some(No)]
+        );
+    }
+
+    #[test]
+    fn test_conditional() {
+        let tags = Tags::from_iter([
+            ("motor_vehicle", "yes"),
+            (
+                "motor_vehicle:forward:conditional",
+                "no @ (Mo-Fr 07:00-09:00)",
+            ),
+        ]);
+        let (access, conditional) = (tags.access(), tags.conditional_access());
+        let resolver = AccessResolver::new(&access, &conditional);
+
+        let at = |hour| {
+            let time = NaiveDate::from_ymd_opt(2025, 1, 6)
+                .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                .unwrap();
+
+            TraversalConditions::new(Motorcar).trip(TripContext::default().time(time))
+        };
+
+        assert_eq!(
+            resolver.resolve_under(&at(8), Forward),
+            Some(&AccessValue::No)
+        );
+        assert_eq!(
+            resolver.resolve_under(&at(8), Backward),
+            Some(&AccessValue::Yes)
+        );
+        assert_eq!(
+            resolver.resolve_under(&at(12), Forward),
+            Some(&AccessValue::Yes)
+        );
+    }
+}
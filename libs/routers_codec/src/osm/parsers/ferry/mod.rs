@@ -0,0 +1,129 @@
+use crate::osm::{Parser, TagString, Tags};
+use std::time::Duration;
+
+/// The weight of each minute aboard a ferry, being that of a residential road segment.
+const WEIGHT_PER_MINUTE: f64 = 10.0;
+
+/// The weight of each segment of a ferry route of unknown duration.
+const DEFAULT_WEIGHT: u32 = 100;
+
+/// A ferry route, being a way with `route=ferry`, along which
+/// vehicles and passengers are carried across water.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Tag:route%3Dferry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FerryRoute {
+    /// The scheduled duration of the crossing, like `duration=01:30`.
+    pub duration: Option<Duration>,
+}
+
+impl FerryRoute {
+    /// Parses a duration, as `HH:MM`, `HH:MM:SS`, minutes, or ISO 8601 like `PT1H30M`.
+    fn parse_duration(value: &str) -> Option<Duration> {
+        let value = value.trim();
+
+        if let Some(iso) = value.strip_prefix("PT") {
+            let mut seconds = 0;
+            let mut rest = iso;
+
+            while !rest.is_empty() {
+                let split = rest.find(|c: char| !c.is_ascii_digit())?;
+                let (number, unit) = rest.split_at(split);
+                let number = number.parse::<u64>().ok()?;
+
+                seconds += number
+                    * match unit.as_bytes()[0] {
+                        b'H' => 3600,
+                        b'M' => 60,
+                        b'S' => 1,
+                        _ => return None,
+                    };
+                rest = &unit[1..];
+            }
+
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let parts = value
+            .split(':')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        let seconds = match parts[..] {
+            [minutes] => minutes * 60,
+            [hours, minutes] => hours * 3600 + minutes * 60,
+            [hours, minutes, seconds] => hours * 3600 + minutes * 60 + seconds,
+            _ => return None,
+        };
+
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// The weight of each of the segments of the route, dividing its duration between them.
+    pub fn weighting(&self, segments: usize) -> u32 {
+        let Some(duration) = self.duration else {
+            return DEFAULT_WEIGHT;
+        };
+
+        let weight = duration.as_secs_f64() / 60.0 * WEIGHT_PER_MINUTE / segments.max(1) as f64;
+        (weight.ceil() as u32).max(1)
+    }
+}
+
+impl Parser for FerryRoute {
+    fn parse(tags: &Tags) -> Option<Self> {
+        if tags
+            .get(TagString::ROUTE)
+            .is_none_or(|route| route.as_str() != "ferry")
+        {
+            return None;
+        }
+
+        Some(FerryRoute {
+            duration: tags
+                .get(TagString::DURATION)
+                .and_then(|value| Self::parse_duration(value)),
+        })
+    }
+}
+
+pub trait Ferry {
+    fn ferry(&self) -> Option<FerryRoute>;
+}
+
+impl Ferry for Tags {
+    fn ferry(&self) -> Option<FerryRoute> {
+        FerryRoute::parse(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration() {
+        let duration = |value| FerryRoute::parse_duration(value).map(|d| d.as_secs());
+
+        assert_eq!(duration("01:30"), Some(5400));
+        assert_eq!(duration("0:45:30"), Some(2730));
+        assert_eq!(duration("45"), Some(2700));
+        assert_eq!(duration("PT1H30M"), Some(5400));
+        assert_eq!(duration("PT90S"), Some(90));
+        assert_eq!(duration("an hour"), None);
+        assert_eq!(duration("PT1X"), None);
+    }
+
+    #[test]
+    fn test_ferry() {
+        let ferry = Tags::from_iter([("route", "ferry"), ("duration", "00:30")])
+            .ferry()
+            .unwrap();
+
+        assert_eq!(ferry.duration, Some(Duration::from_secs(1800)));
+        assert_eq!(ferry.weighting(3), 100);
+        assert_eq!(FerryRoute::default().weighting(3), DEFAULT_WEIGHT);
+
+        assert_eq!(Tags::from_iter([("route", "bus")]).ferry(), None);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::osm::primitives::{Directionality, LaneChange, Lanes, Turn};
+use crate::osm::{Oneway, Parser, TagString, Tags};
+use std::num::NonZeroU8;
+
+const TURN_LANES: &str = "turn:lanes";
+const CHANGE_LANES: &str = "change:lanes";
+
+/// The lanes of a way in a single direction of travel,
+/// each ordered from left to right in that direction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectedLanes {
+    /// The turns indicated upon each lane, from `turn:lanes`,
+    /// where an unmarked lane has no turns.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:turn
+    pub turns: Vec<Vec<Turn>>,
+
+    /// The lane changes permitted from each lane, from `change:lanes`.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:change
+    pub change: Vec<Option<LaneChange>>,
+}
+
+impl DirectedLanes {
+    /// The number of lanes described, should the way not declare a count.
+    fn len(&self) -> usize {
+        self.turns.len().max(self.change.len())
+    }
+}
+
+/// The lane-level layout of a way, such as `lanes:forward=2`
+/// with `turn:lanes:forward=left|through;right`.
+///
+/// Lanes are numbered from one, at the left of the direction of travel,
+/// as is [`TraversalConditions::lane`](crate::osm::TraversalConditions::lane).
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Lanes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaneLayout {
+    /// The number of lanes, from `lanes=*` and its directional suffixes.
+    pub count: Option<Lanes>,
+    pub forward: DirectedLanes,
+    pub backward: DirectedLanes,
+}
+
+impl LaneLayout {
+    /// The lanes in the direction of travel, or every lane of the way for
+    /// [`Directionality::BothWays`], inferred from the turn and change lanes
+    /// should the way not declare a count.
+    pub fn count(&self, direction: Directionality) -> Option<NonZeroU8> {
+        self.count
+            .and_then(|count| count.count(direction))
+            .or_else(|| {
+                let lanes = match direction {
+                    Directionality::Forward => self.forward.len(),
+                    Directionality::Backward => self.backward.len(),
+                    Directionality::BothWays => self.forward.len() + self.backward.len(),
+                    _ => 0,
+                };
+
+                u8::try_from(lanes).ok().and_then(NonZeroU8::new)
+            })
+    }
+
+    /// The lanes in the direction of travel, which is either
+    /// [`Directionality::Forward`] or [`Directionality::Backward`].
+    pub fn directed(&self, direction: Directionality) -> Option<&DirectedLanes> {
+        match direction {
+            Directionality::Forward => Some(&self.forward),
+            Directionality::Backward => Some(&self.backward),
+            _ => None,
+        }
+    }
+
+    /// The turns indicated upon the lane in the direction of travel.
+    pub fn turns(&self, direction: Directionality, lane: NonZeroU8) -> &[Turn] {
+        self.directed(direction)
+            .and_then(|lanes| lanes.turns.get(lane.get() as usize - 1))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The lanes in the direction of travel from which the turn is indicated,
+    /// used to guide the traveller into the correct lane.
+    pub fn lanes_for(&self, direction: Directionality, turn: Turn) -> Vec<NonZeroU8> {
+        self.directed(direction)
+            .into_iter()
+            .flat_map(|lanes| lanes.turns.iter().zip(1..=u8::MAX))
+            .filter(|(turns, _)| turns.contains(&turn))
+            .filter_map(|(_, lane)| NonZeroU8::new(lane))
+            .collect()
+    }
+
+    /// Parses the number of lanes, where those of a one-way way lie in its direction,
+    /// and a single directional count is completed by the total, like
+    /// `lanes=3` with `lanes:forward=2`, less any `lanes:both_ways`.
+    fn parse_count(tags: &Tags, oneway: Directionality) -> Option<Lanes> {
+        let count = |suffix: &str| {
+            let key = format!("{}{suffix}", TagString::LANES);
+            tags.r#as::<NonZeroU8>(&key)
+        };
+
+        let total = count("");
+        let (forward, backward) = (count(":forward"), count(":backward"));
+        let both_ways = count(":both_ways").map_or(0, NonZeroU8::get);
+
+        let remainder = |given: NonZeroU8| {
+            total
+                .and_then(|total| total.get().checked_sub(given.get()))
+                .and_then(|lanes| lanes.checked_sub(both_ways))
+                .and_then(NonZeroU8::new)
+        };
+
+        match (oneway, forward, backward) {
+            (Directionality::Forward, ..) => total.or(forward).map(|lanes| Lanes::Unidirectional {
+                forward: Some(lanes),
+                backward: None,
+            }),
+            (Directionality::Backward, ..) => {
+                total.or(backward).map(|lanes| Lanes::Unidirectional {
+                    forward: None,
+                    backward: Some(lanes),
+                })
+            }
+            (_, None, None) => total.map(Lanes::Bidirectional),
+            (_, Some(forward), None) => Some(Lanes::Unidirectional {
+                forward: Some(forward),
+                backward: remainder(forward),
+            }),
+            (_, None, Some(backward)) => Some(Lanes::Unidirectional {
+                forward: remainder(backward),
+                backward: Some(backward),
+            }),
+            (_, forward, backward) => Some(Lanes::Unidirectional { forward, backward }),
+        }
+    }
+
+    /// Parses the lanes in the direction of travel from their directional keys,
+    /// like `turn:lanes:forward`, or the plain keys should the way be one-way in it.
+    fn parse_directed(
+        tags: &Tags,
+        oneway: Directionality,
+        direction: Directionality,
+    ) -> DirectedLanes {
+        let value = |key: &str| {
+            tags.get(&format!("{key}:{direction}"))
+                .or_else(|| (oneway == direction).then(|| tags.get(key)).flatten())
+        };
+
+        DirectedLanes {
+            turns: value(TURN_LANES)
+                .map(|value| value.split('|').map(Turn::parse_lane).collect())
+                .unwrap_or_default(),
+            change: value(CHANGE_LANES)
+                .map(|value| {
+                    value
+                        .split('|')
+                        .map(|lane| lane.trim().parse().ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Parser for LaneLayout {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let oneway = tags.oneway().direction;
+
+        Some(LaneLayout {
+            count: Self::parse_count(tags, oneway),
+            forward: Self::parse_directed(tags, oneway, Directionality::Forward),
+            backward: Self::parse_directed(tags, oneway, Directionality::Backward),
+        })
+    }
+}
+
+pub trait Lane {
+    fn lanes(&self) -> LaneLayout;
+}
+
+impl Lane for Tags {
+    fn lanes(&self) -> LaneLayout {
+        LaneLayout::parse(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Directionality::{Backward, BothWays, Forward};
+
+    fn lanes(count: u8) -> Option<NonZeroU8> {
+        NonZeroU8::new(count)
+    }
+
+    #[test]
+    fn test_lane_count() {
+        let road = Tags::from_iter([("lanes", "4")]).lanes();
+        assert_eq!(road.count, Some(Lanes::Bidirectional(lanes(4).unwrap())));
+        assert_eq!(road.count(Forward), lanes(2));
+        assert_eq!(road.count(BothWays), lanes(4));
+
+        let road = Tags::from_iter([("lanes", "3"), ("oneway", "yes")]).lanes();
+        assert_eq!(road.count(Forward), lanes(3));
+        assert_eq!(road.count(Backward), None);
+
+        let road = Tags::from_iter([("lanes", "2"), ("oneway", "-1")]).lanes();
+        assert_eq!(road.count(Backward), lanes(2));
+        assert_eq!(road.count(Forward), None);
+    }
+
+    #[test]
+    fn test_directional_count() {
+        let road = Tags::from_iter([("lanes", "3"), ("lanes:forward", "2")]).lanes();
+        assert_eq!(road.count(Forward), lanes(2));
+        assert_eq!(road.count(Backward), lanes(1));
+
+        // The centre turn lane belongs to neither direction
+        let road = Tags::from_iter([
+            ("lanes", "5"),
+            ("lanes:backward", "2"),
+            ("lanes:both_ways", "1"),
+        ])
+        .lanes();
+        assert_eq!(road.count(Forward), lanes(2));
+        assert_eq!(road.count(Backward), lanes(2));
+        assert_eq!(road.count(BothWays), lanes(4));
+    }
+
+    #[test]
+    fn test_turn_lanes() {
+        let road = Tags::from_iter([
+            ("lanes:forward", "3"),
+            ("lanes:backward", "1"),
+            ("turn:lanes:forward", "left|through|through;right"),
+            ("turn:lanes:backward", "none"),
+        ])
+        .lanes();
+
+        assert_eq!(road.turns(Forward, lanes(1).unwrap()), &[Turn::Left]);
+        assert_eq!(
+            road.turns(Forward, lanes(3).unwrap()),
+            &[Turn::Through, Turn::Right]
+        );
+        assert!(road.turns(Backward, lanes(1).unwrap()).is_empty());
+        assert!(road.turns(Forward, lanes(4).unwrap()).is_empty());
+
+        assert_eq!(
+            road.lanes_for(Forward, Turn::Through),
+            vec![lanes(2).unwrap(), lanes(3).unwrap()]
+        );
+        assert!(road.lanes_for(Backward, Turn::Left).is_empty());
+    }
+
+    #[test]
+    fn test_oneway_lanes() {
+        let road = Tags::from_iter([
+            ("oneway", "yes"),
+            ("turn:lanes", "left||right"),
+            ("change:lanes", "not_left|yes|no"),
+        ])
+        .lanes();
+
+        // The count is implied by the turn lanes
+        assert_eq!(road.count(Forward), lanes(3));
+        assert!(road.turns(Forward, lanes(2).unwrap()).is_empty());
+        assert!(road.backward.turns.is_empty());
+
+        let change = &road.forward.change;
+        assert_eq!(change[0], Some(LaneChange::NotLeft));
+        assert!(change[0].is_some_and(|change| change.to_right() && !change.to_left()));
+        assert!(change[2].is_some_and(|change| !change.to_right()));
+
+        // Plain keys are ambiguous upon a way travelled in both directions
+        let road = Tags::from_iter([("turn:lanes", "left|right")]).lanes();
+        assert_eq!(road, LaneLayout::default());
+    }
+}
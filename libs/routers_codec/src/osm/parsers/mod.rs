@@ -1,9 +1,22 @@
 pub mod access_tag;
+pub mod ferry;
+pub mod lanes;
+pub mod names;
+pub mod oneway;
 pub mod primitives;
+pub mod query;
 pub mod speed_limit;
+pub mod toll;
+pub mod vehicle;
 
 pub use access_tag::Access;
+pub use ferry::Ferry;
+pub use lanes::Lane;
+pub use names::Names;
+pub use oneway::Oneway;
 pub use speed_limit::SpeedLimit;
+pub use toll::Toll;
+pub use vehicle::VehicleRestriction;
 
 pub trait Parser: Sized {
     fn parse(tags: &crate::osm::Tags) -> Option<Self>;
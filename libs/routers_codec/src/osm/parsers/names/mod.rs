@@ -0,0 +1,242 @@
+use crate::osm::element::Interner;
+use crate::osm::primitives::Directionality;
+use crate::osm::{Oneway, Parser, Tags};
+use std::sync::{Arc, LazyLock};
+
+const NAME: &str = "name";
+const REF: &str = "ref";
+const INT_REF: &str = "int_ref";
+const DESTINATION: &str = "destination";
+const DESTINATION_REF: &str = "destination:ref";
+
+/// The names shared between the ways of the graph, since the many ways
+/// along a road commonly carry the same name and reference.
+/// A name is freed alongside the last graph holding it.
+static NAMES: LazyLock<Interner> = LazyLock::new(Interner::default);
+
+/// The destinations signposted in a single direction of travel.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:destination
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Destinations {
+    /// The destinations signposted, like `destination=Berlin;Hamburg`.
+    pub destination: Vec<Arc<str>>,
+    /// The references of the roads signposted, like `destination:ref=A 9`.
+    pub refs: Vec<Arc<str>>,
+}
+
+impl Destinations {
+    pub fn is_empty(&self) -> bool {
+        self.destination.is_empty() && self.refs.is_empty()
+    }
+}
+
+/// The names and references by which a way is known and signposted,
+/// such as `name=High Street` or `ref=A1`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdgeNames {
+    /// The primary name of the way.
+    pub name: Option<Arc<str>>,
+
+    /// Translations of the name, like `name:de=*`, as
+    /// the language code and name, ordered by language.
+    pub translations: Vec<(Arc<str>, Arc<str>)>,
+
+    /// The references of the way, like `ref=A1;E15`.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:ref
+    pub refs: Vec<Arc<str>>,
+
+    /// The international references of the way, like `int_ref=E 15`.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:int_ref
+    pub int_refs: Vec<Arc<str>>,
+
+    pub forward: Destinations,
+    pub backward: Destinations,
+}
+
+impl EdgeNames {
+    /// Every name of the way, being the primary name followed by its translations.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.name
+            .iter()
+            .chain(self.translations.iter().map(|(_, name)| name))
+            .map(|name| name.as_ref())
+    }
+
+    /// The name of the way in the language, falling back to the primary name.
+    pub fn name_in(&self, language: &str) -> Option<&str> {
+        self.translations
+            .binary_search_by(|(code, _)| code.as_ref().cmp(language))
+            .ok()
+            .map(|index| self.translations[index].1.as_ref())
+            .or(self.name.as_deref())
+    }
+
+    /// Every reference of the way, being its references followed by its international references.
+    pub fn refs(&self) -> impl Iterator<Item = &str> {
+        self.refs
+            .iter()
+            .chain(&self.int_refs)
+            .map(|reference| reference.as_ref())
+    }
+
+    /// The destinations signposted in the direction of travel, which is either
+    /// [`Directionality::Forward`] or [`Directionality::Backward`].
+    pub fn destinations(&self, direction: Directionality) -> Option<&Destinations> {
+        match direction {
+            Directionality::Forward => Some(&self.forward),
+            Directionality::Backward => Some(&self.backward),
+            _ => None,
+        }
+    }
+
+    /// Splits a value of many entries, like `A1;E15`, interning each.
+    fn split(value: &str) -> Vec<Arc<str>> {
+        value
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| NAMES.intern(entry))
+            .collect()
+    }
+
+    /// Determines if the suffix of a `name:*` key is a language code, like `de` or
+    /// `zh-Hans`, rather than a property of the name, like `name:etymology`.
+    fn is_language(code: &str) -> bool {
+        let (language, region) = code.split_once('-').unwrap_or((code, ""));
+
+        (2..=3).contains(&language.len())
+            && language.bytes().all(|b| b.is_ascii_lowercase())
+            && region
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    }
+
+    /// Parses the destinations in the direction of travel from their directional keys,
+    /// like `destination:forward`, or the plain keys should the way be one-way in it.
+    fn parse_destinations(
+        tags: &Tags,
+        oneway: Directionality,
+        direction: Directionality,
+    ) -> Destinations {
+        let value = |key: &str| {
+            tags.get(&format!("{key}:{direction}"))
+                .or_else(|| (oneway == direction).then(|| tags.get(key)).flatten())
+                .map(|value| Self::split(value))
+                .unwrap_or_default()
+        };
+
+        Destinations {
+            destination: value(DESTINATION),
+            refs: value(DESTINATION_REF),
+        }
+    }
+}
+
+impl Parser for EdgeNames {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let oneway = tags.oneway().direction;
+
+        let mut translations = tags
+            .iter()
+            .filter_map(|(key, value)| {
+                let language = key.strip_prefix(NAME)?.strip_prefix(':')?;
+                Self::is_language(language)
+                    .then(|| (NAMES.intern(language), NAMES.intern(value.trim())))
+            })
+            .collect::<Vec<_>>();
+        translations.sort_unstable();
+
+        Some(EdgeNames {
+            name: tags.get(NAME).map(|name| NAMES.intern(name.trim())),
+            translations,
+            refs: tags.get(REF).map(|v| Self::split(v)).unwrap_or_default(),
+            int_refs: tags
+                .get(INT_REF)
+                .map(|v| Self::split(v))
+                .unwrap_or_default(),
+            forward: Self::parse_destinations(tags, oneway, Directionality::Forward),
+            backward: Self::parse_destinations(tags, oneway, Directionality::Backward),
+        })
+    }
+}
+
+pub trait Names {
+    fn names(&self) -> EdgeNames;
+}
+
+impl Names for Tags {
+    fn names(&self) -> EdgeNames {
+        EdgeNames::parse(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        let road = Tags::from_iter([
+            ("name", "Unter den Linden"),
+            ("name:en", "Under the Linden Trees"),
+            ("name:zh-Hans", "菩提树下大街"),
+            ("name:etymology:wikidata", "Q12345"),
+            ("name:pronunciation", "ˈʊntɐ deːn ˈlɪndn̩"),
+        ])
+        .names();
+
+        assert_eq!(
+            road.names().collect::<Vec<_>>(),
+            vec!["Unter den Linden", "Under the Linden Trees", "菩提树下大街"]
+        );
+        assert_eq!(road.name_in("en"), Some("Under the Linden Trees"));
+        assert_eq!(road.name_in("fr"), Some("Unter den Linden"));
+    }
+
+    #[test]
+    fn test_refs() {
+        let road = Tags::from_iter([("ref", "A1; A 20"), ("int_ref", "E 22")]).names();
+        assert_eq!(road.refs().collect::<Vec<_>>(), vec!["A1", "A 20", "E 22"]);
+        assert_eq!(road.name, None);
+    }
+
+    #[test]
+    fn test_destinations() {
+        let link = Tags::from_iter([
+            ("oneway", "yes"),
+            ("destination", "Berlin;Hamburg"),
+            ("destination:ref", "A 24"),
+        ])
+        .names();
+
+        let forward = link.destinations(Directionality::Forward).unwrap();
+        assert_eq!(forward.destination.len(), 2);
+        assert_eq!(forward.refs[0].as_ref(), "A 24");
+        assert!(link.backward.is_empty());
+
+        let road = Tags::from_iter([
+            ("destination:forward", "Leeds"),
+            ("destination:backward", "York"),
+        ])
+        .names();
+
+        assert_eq!(road.forward.destination[0].as_ref(), "Leeds");
+        assert_eq!(road.backward.destination[0].as_ref(), "York");
+    }
+
+    #[test]
+    fn test_interned() {
+        let first = Tags::from_iter([("name", "Station Road")]).names();
+        let second = Tags::from_iter([("name", "Station Road")]).names();
+
+        assert!(Arc::ptr_eq(
+            first.name.as_ref().unwrap(),
+            second.name.as_ref().unwrap()
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::osm::primitives::{Condition, Directionality, TransportMode};
+use crate::osm::{Parser, TagString, Tags, TraversalConditions};
+use std::str::FromStr;
+
+const CONDITIONAL: &str = ":conditional";
+
+/// A direction which only applies under a [`Condition`],
+/// like `oneway:conditional=yes @ (Mo-Fr 06:00-10:00)`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Conditional_restrictions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalOneway {
+    /// The transport mode the direction applies to,
+    /// or vehicles in general should it be `None`.
+    pub transport_mode: Option<TransportMode>,
+    pub direction: Directionality,
+    pub condition: Condition,
+}
+
+/// The directions of travel permitted along a way, relative to how it is drawn.
+///
+/// Holds the general `oneway=*` restriction, which applies to vehicles,
+/// and any mode-specific overrides such as `oneway:bicycle=no`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:oneway
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnewayRestriction {
+    /// The permitted direction for vehicles, one of [`Directionality::Forward`],
+    /// [`Directionality::Backward`] or [`Directionality::BothWays`].
+    pub direction: Directionality,
+
+    /// Mode-specific overrides of the general direction.
+    pub overrides: Vec<(TransportMode, Directionality)>,
+
+    /// Directions which apply only under a condition, replacing the
+    /// general direction or override of the same transport mode.
+    pub conditional: Vec<ConditionalOneway>,
+}
+
+impl Default for OnewayRestriction {
+    fn default() -> Self {
+        Self {
+            direction: Directionality::BothWays,
+            overrides: vec![],
+            conditional: vec![],
+        }
+    }
+}
+
+impl OnewayRestriction {
+    fn direction_from_value(value: &str) -> Option<Directionality> {
+        match value {
+            "yes" | "true" | "1" => Some(Directionality::Forward),
+            "-1" | "reverse" => Some(Directionality::Backward),
+            // Reversible and alternating ways change direction over time,
+            // so are treated as bidirectional.
+            "no" | "false" | "0" | "reversible" | "alternating" => Some(Directionality::BothWays),
+            _ => None,
+        }
+    }
+
+    /// The direction permitted for the given transport mode,
+    /// disregarding any conditional restriction.
+    ///
+    /// The most specific override within the mode's [hierarchy](TransportMode::hierarchy)
+    /// is used. Otherwise, the general restriction applies only to vehicles.
+    pub fn direction_for(&self, mode: TransportMode) -> Directionality {
+        self.resolve(mode, |_| None)
+    }
+
+    /// The direction permitted under the traversal conditions, where an applicable
+    /// conditional restriction replaces that of the same transport mode.
+    pub fn direction_under(&self, conditions: &TraversalConditions) -> Directionality {
+        self.resolve(conditions.transport_mode, |mode| {
+            self.conditional
+                .iter()
+                .rev()
+                .find(|conditional| {
+                    conditional.transport_mode == mode
+                        && conditional.condition.applies(conditions) == Some(true)
+                })
+                .map(|conditional| conditional.direction)
+        })
+    }
+
+    /// Resolves the direction for the transport mode, given the applicable
+    /// conditional direction of each mode, or of vehicles in general for `None`.
+    fn resolve(
+        &self,
+        mode: TransportMode,
+        conditional: impl Fn(Option<TransportMode>) -> Option<Directionality>,
+    ) -> Directionality {
+        mode.hierarchy()
+            .find_map(|mode| {
+                conditional(Some(mode)).or_else(|| {
+                    self.overrides
+                        .iter()
+                        .find(|(over, _)| *over == mode)
+                        .map(|(_, direction)| *direction)
+                })
+            })
+            .unwrap_or_else(|| {
+                if mode.is_vehicle() {
+                    conditional(None).unwrap_or(self.direction)
+                } else {
+                    Directionality::BothWays
+                }
+            })
+    }
+
+    /// Determines if the transport mode may travel along the way in the given direction,
+    /// disregarding any conditional restriction.
+    pub fn permits(&self, mode: TransportMode, direction: Directionality) -> bool {
+        match self.direction_for(mode) {
+            Directionality::BothWays => true,
+            permitted => permitted == direction,
+        }
+    }
+
+    /// Determines if travel along the way in the given direction
+    /// is permitted under the traversal conditions.
+    pub fn permits_under(
+        &self,
+        conditions: &TraversalConditions,
+        direction: Directionality,
+    ) -> bool {
+        match self.direction_under(conditions) {
+            Directionality::BothWays => true,
+            permitted => permitted == direction,
+        }
+    }
+}
+
+impl Parser for OnewayRestriction {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let direction = tags
+            .get(TagString::ONE_WAY)
+            .and_then(|value| Self::direction_from_value(value))
+            // Roundabouts are implicitly one-way.
+            .or_else(|| tags.roundabout().then_some(Directionality::Forward))
+            .unwrap_or(Directionality::BothWays);
+
+        let overrides = tags
+            .iter()
+            .filter_map(|(key, value)| {
+                let mode = key.strip_prefix(TagString::ONE_WAY)?.strip_prefix(':')?;
+                Some((
+                    TransportMode::from_str(mode).ok()?,
+                    Self::direction_from_value(value)?,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let conditional = tags
+            .iter()
+            .filter_map(|(key, value)| {
+                let key = key
+                    .strip_prefix(TagString::ONE_WAY)?
+                    .strip_suffix(CONDITIONAL)?;
+                let transport_mode = match key.strip_prefix(':') {
+                    Some(mode) => Some(TransportMode::from_str(mode).ok()?),
+                    None if key.is_empty() => None,
+                    None => return None,
+                };
+
+                Some((transport_mode, value))
+            })
+            .flat_map(|(transport_mode, value)| {
+                Condition::parse_conditional(value).into_iter().filter_map(
+                    move |(value, condition)| {
+                        Some(ConditionalOneway {
+                            transport_mode,
+                            direction: Self::direction_from_value(value)?,
+                            condition,
+                        })
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Some(OnewayRestriction {
+            direction,
+            overrides,
+            conditional,
+        })
+    }
+}
+
+pub trait Oneway {
+    fn oneway(&self) -> OnewayRestriction;
+}
+
+impl Oneway for Tags {
+    fn oneway(&self) -> OnewayRestriction {
+        OnewayRestriction::parse(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::TripContext;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_general_oneway() {
+        let oneway = Tags::from_iter([("oneway", "yes")]).oneway();
+        assert!(oneway.permits(TransportMode::Motorcar, Directionality::Forward));
+        assert!(!oneway.permits(TransportMode::Motorcar, Directionality::Backward));
+        assert!(!oneway.permits(TransportMode::Bicycle, Directionality::Backward));
+
+        // Pedestrians are not subject to the general restriction
+        assert!(oneway.permits(TransportMode::Foot, Directionality::Backward));
+    }
+
+    #[test]
+    fn test_reverse_oneway() {
+        let oneway = Tags::from_iter([("oneway", "-1")]).oneway();
+        assert!(!oneway.permits(TransportMode::Motorcar, Directionality::Forward));
+        assert!(oneway.permits(TransportMode::Motorcar, Directionality::Backward));
+    }
+
+    #[test]
+    fn test_roundabout() {
+        let oneway = Tags::from_iter([("junction", "roundabout")]).oneway();
+        assert_eq!(oneway.direction, Directionality::Forward);
+
+        let oneway = Tags::from_iter([("junction", "roundabout"), ("oneway", "no")]).oneway();
+        assert_eq!(oneway.direction, Directionality::BothWays);
+    }
+
+    #[test]
+    fn test_mode_overrides() {
+        let oneway = Tags::from_iter([("oneway", "yes"), ("oneway:bicycle", "no")]).oneway();
+        assert!(oneway.permits(TransportMode::Bicycle, Directionality::Backward));
+        assert!(oneway.permits(TransportMode::Mtb, Directionality::Backward));
+        assert!(!oneway.permits(TransportMode::Motorcar, Directionality::Backward));
+
+        let oneway = Tags::from_iter([("oneway", "yes"), ("oneway:psv", "no")]).oneway();
+        assert!(oneway.permits(TransportMode::Bus, Directionality::Backward));
+        assert!(!oneway.permits(TransportMode::Hgv, Directionality::Backward));
+    }
+
+    #[test]
+    fn test_unrestricted() {
+        let oneway = Tags::from_iter([("highway", "residential")]).oneway();
+        assert_eq!(oneway, OnewayRestriction::default());
+        assert!(oneway.permits(TransportMode::Motorcar, Directionality::Backward));
+    }
+
+    #[test]
+    fn test_conditional_oneway() {
+        let oneway = Tags::from_iter([
+            ("oneway", "no"),
+            ("oneway:conditional", "yes @ (Mo-Fr 06:00-10:00)"),
+            ("oneway:bicycle:conditional", "no @ (Mo-Fr 06:00-10:00)"),
+        ])
+        .oneway();
+
+        assert_eq!(oneway.conditional.len(), 2);
+
+        let at = |mode, hour| {
+            let time = NaiveDate::from_ymd_opt(2025, 1, 6)
+                .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                .unwrap();
+
+            TraversalConditions::new(mode).trip(TripContext::default().time(time))
+        };
+
+        let morning = at(TransportMode::Motorcar, 8);
+        assert!(!oneway.permits_under(&morning, Directionality::Backward));
+        assert!(oneway.permits_under(&morning, Directionality::Forward));
+
+        let evening = at(TransportMode::Motorcar, 18);
+        assert!(oneway.permits_under(&evening, Directionality::Backward));
+
+        let bicycle = at(TransportMode::Bicycle, 8);
+        assert!(oneway.permits_under(&bicycle, Directionality::Backward));
+
+        // Without a time of travel, the condition does not apply
+        let unknown = TraversalConditions::new(TransportMode::Motorcar);
+        assert!(oneway.permits_under(&unknown, Directionality::Backward));
+    }
+}
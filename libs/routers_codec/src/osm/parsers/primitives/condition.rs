@@ -1,4 +1,7 @@
+use crate::osm::TraversalConditions;
+use crate::osm::primitives::dimension::measure;
 use crate::osm::primitives::opening_hours::{OpeningHours, OpeningHoursParser};
+use chrono::{Datelike, NaiveDate};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -66,7 +69,7 @@ pub struct TimeDateCondition {
 }
 
 /// Seasonal time restrictions
-#[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum SeasonCondition {
     /// Winter season (dates vary by location/year)
@@ -80,7 +83,7 @@ pub enum SeasonCondition {
 }
 
 /// Road surface and weather conditions
-#[derive(Debug, Clone, PartialEq, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum RoadCondition {
     /// Wet road surface
@@ -107,7 +110,7 @@ pub struct VehiclePropertyCondition {
 }
 
 /// Vehicle properties that can be restricted
-#[derive(Debug, Clone, PartialEq, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum VehicleProperty {
     /// Vehicle weight in tonnes
@@ -127,7 +130,7 @@ pub enum VehicleProperty {
 }
 
 /// Comparison operators for vehicle properties
-#[derive(Debug, Clone, PartialEq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
 pub enum ComparisonOperator {
     #[strum(serialize = "<")]
     LessThan,
@@ -157,7 +160,7 @@ pub enum VehicleUsageCondition {
 }
 
 /// User group conditions for access restrictions
-#[derive(Debug, Clone, PartialEq, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum UserGroupCondition {
     /// Medical doctors
@@ -179,7 +182,7 @@ pub enum UserGroupCondition {
 }
 
 /// Purpose of access conditions
-#[derive(Debug, Clone, PartialEq, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum PurposeCondition {
     /// Destination traffic only
@@ -237,6 +240,118 @@ pub enum LogicalOperator {
     Or,
 }
 
+impl ComparisonOperator {
+    /// Compares the left-hand side against the right-hand side,
+    /// such that `weight > 7.5` is `GreaterThan.compare(weight, 7.5)`.
+    pub fn compare<T: PartialOrd>(&self, lhs: T, rhs: T) -> bool {
+        match self {
+            ComparisonOperator::LessThan => lhs < rhs,
+            ComparisonOperator::GreaterThan => lhs > rhs,
+            ComparisonOperator::Equal => lhs == rhs,
+            ComparisonOperator::LessThanOrEqual => lhs <= rhs,
+            ComparisonOperator::GreaterThanOrEqual => lhs >= rhs,
+        }
+    }
+}
+
+impl SeasonCondition {
+    /// The meteorological season of the date, being winter from
+    /// december to february in the northern hemisphere.
+    pub fn of(date: NaiveDate, southern: bool) -> Self {
+        let season = match date.month() {
+            3..=5 => SeasonCondition::Spring,
+            6..=8 => SeasonCondition::Summer,
+            9..=11 => SeasonCondition::Autumn,
+            _ => SeasonCondition::Winter,
+        };
+
+        match (southern, season) {
+            (false, season) => season,
+            (true, SeasonCondition::Spring) => SeasonCondition::Autumn,
+            (true, SeasonCondition::Summer) => SeasonCondition::Winter,
+            (true, SeasonCondition::Autumn) => SeasonCondition::Spring,
+            (true, SeasonCondition::Winter) => SeasonCondition::Summer,
+        }
+    }
+}
+
+impl Duration {
+    /// The duration in minutes.
+    pub fn minutes(&self) -> u32 {
+        match self.unit {
+            DurationUnit::Minutes => self.value,
+            DurationUnit::Hours => self.value * 60,
+            DurationUnit::Days => self.value * 60 * 24,
+        }
+    }
+}
+
+impl ConditionType {
+    /// Determines if the condition applies under the traversal conditions.
+    ///
+    /// Should it depend upon a circumstance of the trip or vehicle
+    /// which is unknown, such as the time of travel, `None` is returned.
+    pub fn applies(&self, conditions: &TraversalConditions) -> Option<bool> {
+        let trip = &conditions.trip;
+        let vehicle = &conditions.vehicle;
+
+        match self {
+            ConditionType::TimeDate(time_date) => {
+                let mut evaluator = time_date.opening_hours.evaluator().holidays(&trip.holidays);
+                if let Some(location) = trip.point() {
+                    evaluator = evaluator.location(location);
+                }
+
+                Some(evaluator.is_open(trip.time?))
+            }
+            ConditionType::Season(season) => {
+                let current = trip.season.or_else(|| {
+                    let southern = trip.point().is_some_and(|point| point.y() < 0.0);
+                    Some(SeasonCondition::of(trip.time?.date(), southern))
+                })?;
+
+                Some(current == *season)
+            }
+            ConditionType::RoadCondition(road) => Some(trip.road.as_ref()?.contains(road)),
+            ConditionType::VehicleProperty(property) => {
+                let dimension = vehicle.dimension(property.property)?;
+                let unit = property.unit.as_deref().unwrap_or_default();
+                let value = measure(property.property, &format!("{}{}", property.value, unit))?;
+
+                Some(property.operator.compare(dimension, value))
+            }
+            ConditionType::VehicleUsage(VehicleUsageCondition::Hazmat) => Some(vehicle.hazmat),
+            ConditionType::VehicleUsage(VehicleUsageCondition::Occupants { operator, count }) => {
+                Some(operator.compare(trip.occupants?, *count))
+            }
+            ConditionType::VehicleUsage(VehicleUsageCondition::Load(_)) => None,
+            ConditionType::UserGroup(group) => Some(trip.user_groups.contains(group)),
+            ConditionType::Purpose(purpose) => Some(trip.purpose == Some(*purpose)),
+            ConditionType::StayDuration(stay) => {
+                Some(stay.operator.compare(trip.stay?, stay.duration.minutes()))
+            }
+            ConditionType::Combined(combined) => {
+                let left = combined.left.applies(conditions);
+                let right = combined.right.applies(conditions);
+
+                match combined.operator {
+                    LogicalOperator::And => match (left, right) {
+                        (Some(false), _) | (_, Some(false)) => Some(false),
+                        (Some(true), Some(true)) => Some(true),
+                        _ => None,
+                    },
+                    LogicalOperator::Or => match (left, right) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None,
+                    },
+                }
+            }
+            ConditionType::Raw(_) => None,
+        }
+    }
+}
+
 impl FromStr for ComparisonOperator {
     type Err = String;
 
@@ -359,12 +474,6 @@ impl Condition {
             });
         }
 
-        if let Ok(vehicle_usage) = Self::parse_vehicle_usage(cleaned) {
-            return Ok(Condition {
-                condition_type: ConditionType::VehicleUsage(vehicle_usage),
-            });
-        }
-
         if let Ok(user_group) = Self::parse_user_group(cleaned) {
             return Ok(Condition {
                 condition_type: ConditionType::UserGroup(user_group),
@@ -383,12 +492,53 @@ impl Condition {
             });
         }
 
+        // Any other usage is treated as a load, so is attempted last
+        if let Ok(vehicle_usage) = Self::parse_vehicle_usage(cleaned) {
+            return Ok(Condition {
+                condition_type: ConditionType::VehicleUsage(vehicle_usage),
+            });
+        }
+
         // If no specific parser matches, store as raw condition
         Ok(Condition {
             condition_type: ConditionType::Raw(cleaned.to_string()),
         })
     }
 
+    /// Parses a conditional restriction value, like `no @ (Mo-Fr 07:00-09:00); yes @ wet`,
+    /// into each value and the condition under which it applies, in order of declaration.
+    ///
+    /// Entries whose condition cannot be parsed are omitted.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Conditional_restrictions
+    pub fn parse_conditional(value: &str) -> Vec<(&str, Condition)> {
+        let mut depth = 0usize;
+        let entries = value.split(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            c == ';' && depth == 0
+        });
+
+        entries
+            .filter_map(|entry| {
+                let (value, condition) = entry.split_once('@')?;
+                Some((value.trim(), Condition::parse(condition).ok()?))
+            })
+            .collect()
+    }
+
+    /// Determines if the condition applies under the traversal conditions,
+    /// or `None` should it depend upon an unknown circumstance.
+    ///
+    /// See [`ConditionType::applies`].
+    pub fn applies(&self, conditions: &TraversalConditions) -> Option<bool> {
+        self.condition_type.applies(conditions)
+    }
+
     // Private parsing methods
 
     fn parse_combined_condition(s: &str) -> Result<ConditionType, ParseError> {
@@ -653,13 +803,14 @@ mod tests {
                 OpeningRule {
                     weekdays: Some(WeekdayRange::Range(Weekday::Tuesday, Weekday::Friday)),
                     times: vec![TimeRange {
-                        start: Time { hour: 0, minute: 0 },
+                        start: Time { hour: 0, minute: 0 }.into(),
                         end: Time {
                             hour: 24,
                             minute: 0
                         }
+                        .into()
                     }],
-                    closed: false
+                    ..OpeningRule::default()
                 }
             );
             assert_eq!(td.comment, None);
@@ -707,4 +858,55 @@ mod tests {
         let regenerated = condition.to_string();
         assert_eq!(regenerated, "weight<7.5"); // Note: spaces might be normalized
     }
+
+    #[test]
+    fn test_applies() {
+        use crate::osm::primitives::TransportMode;
+        use crate::osm::{TripContext, VehicleProfile};
+
+        let time = NaiveDate::from_ymd_opt(2025, 1, 6)
+            .and_then(|date| date.and_hms_opt(8, 0, 0))
+            .unwrap();
+
+        let unknown = TraversalConditions::new(TransportMode::Hgv);
+        let known = unknown
+            .clone()
+            .vehicle(VehicleProfile::default().weight(12.0))
+            .trip(
+                TripContext::default()
+                    .time(time)
+                    .road([RoadCondition::Wet])
+                    .occupants(1)
+                    .stay(90),
+            );
+
+        let applies = |condition: &str, conditions: &TraversalConditions| {
+            Condition::parse(condition).unwrap().applies(conditions)
+        };
+
+        assert_eq!(applies("Mo-Fr 07:00-09:00", &known), Some(true));
+        assert_eq!(applies("Mo-Fr 07:00-09:00", &unknown), None);
+        assert_eq!(applies("winter", &known), Some(true));
+        assert_eq!(applies("wet", &known), Some(true));
+        assert_eq!(applies("snow", &unknown), None);
+        assert_eq!(applies("occupants>1", &known), Some(false));
+        assert_eq!(applies("stay < 2 hours", &known), Some(true));
+        assert_eq!(applies("delivery", &known), Some(false));
+        assert_eq!(applies("weight>7.5 AND Sa-Su", &known), Some(false));
+        assert_eq!(applies("weight>7.5 AND snow", &unknown), None);
+        assert_eq!(applies("weight>7.5 OR snow", &known), Some(true));
+    }
+
+    #[test]
+    fn test_parse_conditional() {
+        let entries =
+            Condition::parse_conditional("no @ (Mo-Fr 07:00-09:00; Sa 10:00-12:00); yes @ wet");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "no");
+        assert!(matches!(
+            &entries[0].1.condition_type,
+            ConditionType::TimeDate(time_date) if time_date.opening_hours.rules.len() == 2
+        ));
+        assert_eq!(entries[1].0, "yes");
+    }
 }
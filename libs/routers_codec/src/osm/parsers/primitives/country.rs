@@ -0,0 +1,57 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// An ISO 3166-1 alpha-2 country code, such as `DE` within `maxspeed=DE:urban`.
+///
+/// Source: https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    /// Creates a country code from its two uppercase ASCII letters,
+    /// for use in constant tables.
+    pub const fn new(code: &[u8; 2]) -> Self {
+        assert!(code[0].is_ascii_uppercase() && code[1].is_ascii_uppercase());
+        CountryCode(*code)
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Always valid, being two ASCII letters.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = ();
+
+    /// Parses a country code, like `DE` or `gb`, ignoring case.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.as_bytes() {
+            [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => Ok(CountryCode([
+                a.to_ascii_uppercase(),
+                b.to_ascii_uppercase(),
+            ])),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!("DE".parse(), Ok(CountryCode::new(b"DE")));
+        assert_eq!("gb".parse(), Ok(CountryCode::new(b"GB")));
+        assert_eq!("DEU".parse::<CountryCode>(), Err(()));
+        assert_eq!("D1".parse::<CountryCode>(), Err(()));
+        assert_eq!(CountryCode::new(b"AU").to_string(), "AU");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::osm::primitives::condition::VehicleProperty;
+
+/// A physical dimension of a vehicle, or a limit upon one.
+///
+/// Dimensions are normalised into integer units such that they
+/// may be compared (and hashed) exactly. Weights are represented
+/// in kilograms, lengths in centimeters, and counts as-is.
+pub type Dimension = u32;
+
+/// Values which explicitly state a dimension is not limited.
+const UNLIMITED: [&str; 6] = [
+    "none",
+    "unsigned",
+    "default",
+    "no_sign",
+    "no_indications",
+    "below_default",
+];
+
+/// A limit upon a physical dimension of a vehicle, such as `maxweight=7.5`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:maxweight
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DimensionLimit {
+    /// The dimension must not exceed the given value.
+    Limit(Dimension),
+    /// The dimension is explicitly unlimited, such as `maxheight=none`.
+    Unlimited,
+}
+
+impl DimensionLimit {
+    /// Parses a limit upon the given property, like `7.5`, `3.5 m` or `12'6"`.
+    ///
+    /// Values without a unit use the OSM default for the property,
+    /// being tonnes for weights and meters for lengths.
+    pub fn parse(property: VehicleProperty, value: &str) -> Option<Self> {
+        let value = value.trim();
+        if UNLIMITED.contains(&value) {
+            return Some(DimensionLimit::Unlimited);
+        }
+
+        measure(property, value).map(DimensionLimit::Limit)
+    }
+
+    /// Determines if a vehicle with the given dimension is within the limit.
+    pub fn permits(&self, dimension: Dimension) -> bool {
+        match self {
+            DimensionLimit::Limit(limit) => dimension <= *limit,
+            DimensionLimit::Unlimited => true,
+        }
+    }
+}
+
+/// Converts a measurement of the property, such as `7.5` or `7500 kg`,
+/// into its normalised [`Dimension`].
+pub fn measure(property: VehicleProperty, value: &str) -> Option<Dimension> {
+    let value = value.trim().replace(',', ".");
+
+    // Imperial lengths may be given in feet and inches, like `12'6"`.
+    if let Some((feet, inches)) = value.split_once('\'') {
+        let feet = feet.trim().parse::<f64>().ok()?;
+        let inches = match inches.trim().trim_end_matches('"').trim() {
+            "" => 0.0,
+            inches => inches.parse::<f64>().ok()?,
+        };
+
+        return in_unit(property, feet + inches / 12.0, "ft");
+    }
+
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+
+    let (number, unit) = value.split_at(split);
+    in_unit(property, number.parse::<f64>().ok()?, unit.trim())
+}
+
+/// Normalises a value of the property, given in the unit, into its [`Dimension`].
+pub fn in_unit(property: VehicleProperty, value: f64, unit: &str) -> Option<Dimension> {
+    let factor = match property {
+        VehicleProperty::Weight | VehicleProperty::Axleload => match unit {
+            "" | "t" => 1000.0,
+            "kg" => 1.0,
+            // Short and long tons
+            "st" => 907.185,
+            "lt" => 1016.047,
+            "lbs" => 0.453_592,
+            _ => return None,
+        },
+        VehicleProperty::Length
+        | VehicleProperty::Width
+        | VehicleProperty::Height
+        | VehicleProperty::Draught => match unit {
+            "" | "m" => 100.0,
+            "cm" => 1.0,
+            "ft" => 30.48,
+            "in" => 2.54,
+            _ => return None,
+        },
+        VehicleProperty::Wheels => match unit {
+            "" => 1.0,
+            _ => return None,
+        },
+    };
+
+    (value >= 0.0).then(|| (value * factor).round() as Dimension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weight() {
+        let parse = |value| DimensionLimit::parse(VehicleProperty::Weight, value);
+
+        assert_eq!(parse("7.5"), Some(DimensionLimit::Limit(7500)));
+        assert_eq!(parse("7.5 t"), Some(DimensionLimit::Limit(7500)));
+        assert_eq!(parse("3500 kg"), Some(DimensionLimit::Limit(3500)));
+        assert_eq!(parse("none"), Some(DimensionLimit::Unlimited));
+        assert_eq!(parse("heavy"), None);
+    }
+
+    #[test]
+    fn test_parse_length() {
+        let parse = |value| DimensionLimit::parse(VehicleProperty::Height, value);
+
+        assert_eq!(parse("3.5"), Some(DimensionLimit::Limit(350)));
+        assert_eq!(parse("3,5 m"), Some(DimensionLimit::Limit(350)));
+        assert_eq!(parse("12'6\""), Some(DimensionLimit::Limit(381)));
+        assert_eq!(parse("default"), Some(DimensionLimit::Unlimited));
+    }
+
+    #[test]
+    fn test_permits() {
+        assert!(DimensionLimit::Limit(350).permits(350));
+        assert!(!DimensionLimit::Limit(350).permits(351));
+        assert!(DimensionLimit::Unlimited.permits(Dimension::MAX));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::osm::primitives::Directionality;
+use std::str::FromStr;
+
+/// The incline of a way in the direction it is drawn, such as `incline=10%`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:incline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Incline {
+    /// Ascends, by an unknown grade.
+    Up,
+    /// Descends, by an unknown grade.
+    Down,
+    /// Ascends, or descends should it be negative, by the grade in percent.
+    Grade(i16),
+}
+
+impl Incline {
+    /// The incline in the direction of travel, which is reversed when
+    /// travelling [backward](Directionality::Backward) along the way.
+    pub fn toward(&self, direction: Directionality) -> Incline {
+        match (self, direction) {
+            (_, Directionality::Forward | Directionality::BothWays) => *self,
+            (Incline::Up, _) => Incline::Down,
+            (Incline::Down, _) => Incline::Up,
+            (Incline::Grade(grade), _) => Incline::Grade(-grade),
+        }
+    }
+
+    /// The grade in percent, should it be known.
+    pub fn grade(&self) -> Option<i16> {
+        match self {
+            Incline::Grade(grade) => Some(*grade),
+            _ => None,
+        }
+    }
+
+    /// If the way ascends, in the direction it is drawn.
+    pub fn ascends(&self) -> bool {
+        match self {
+            Incline::Up => true,
+            Incline::Down => false,
+            Incline::Grade(grade) => *grade > 0,
+        }
+    }
+}
+
+impl FromStr for Incline {
+    type Err = ();
+
+    /// Parses an incline, like `up`, `-8%` or `5°`, where values in
+    /// degrees are converted into a grade in percent.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        match value {
+            "up" => return Ok(Incline::Up),
+            "down" => return Ok(Incline::Down),
+            _ => {}
+        }
+
+        let grade = if let Some(percent) = value.strip_suffix('%') {
+            percent.trim().parse::<f64>().map_err(|_| ())?
+        } else if let Some(degrees) = value.strip_suffix('°') {
+            let degrees = degrees.trim().parse::<f64>().map_err(|_| ())?;
+            if degrees.abs() >= 90.0 {
+                return Err(());
+            }
+
+            degrees.to_radians().tan() * 100.0
+        } else {
+            return Err(());
+        };
+
+        Ok(Incline::Grade(
+            grade.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!("up".parse(), Ok(Incline::Up));
+        assert_eq!("-8%".parse(), Ok(Incline::Grade(-8)));
+        assert_eq!("12.4 %".parse(), Ok(Incline::Grade(12)));
+        assert_eq!("45°".parse(), Ok(Incline::Grade(100)));
+        assert_eq!("steep".parse::<Incline>(), Err(()));
+        assert_eq!("10".parse::<Incline>(), Err(()));
+    }
+
+    #[test]
+    fn test_direction() {
+        let incline = Incline::Grade(6);
+        assert!(incline.ascends());
+        assert_eq!(incline.toward(Directionality::Backward), Incline::Grade(-6));
+        assert!(!Incline::Up.toward(Directionality::Backward).ascends());
+        assert_eq!(Incline::Down.grade(), None);
+    }
+}
@@ -1,9 +1,107 @@
+use crate::osm::primitives::Directionality;
 use std::num::NonZeroU8;
+use strum::{AsRefStr, Display, EnumString};
 
+/// The number of lanes of a way, in each direction of travel.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:lanes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Lanes {
+    /// The total lanes of a way travelled in both directions, without
+    /// a known split between them, like `lanes=2`.
     Bidirectional(NonZeroU8),
+    /// The lanes in each direction of travel, like `lanes:forward=2` and
+    /// `lanes:backward=1`, or those of a one-way way.
     Unidirectional {
         forward: Option<NonZeroU8>,
         backward: Option<NonZeroU8>,
     },
 }
+
+impl Lanes {
+    /// The lanes available in the direction of travel,
+    /// or every lane of the way for [`Directionality::BothWays`].
+    ///
+    /// The lanes of a [`Bidirectional`](Lanes::Bidirectional) way are
+    /// assumed to be split evenly, with at least one in each direction.
+    pub fn count(&self, direction: Directionality) -> Option<NonZeroU8> {
+        match (self, direction) {
+            (_, Directionality::BothWays) => self.total(),
+            (Lanes::Bidirectional(total), Directionality::Forward | Directionality::Backward) => {
+                NonZeroU8::new(total.get() / 2).or(Some(NonZeroU8::MIN))
+            }
+            (Lanes::Unidirectional { forward, .. }, Directionality::Forward) => *forward,
+            (Lanes::Unidirectional { backward, .. }, Directionality::Backward) => *backward,
+            _ => None,
+        }
+    }
+
+    /// The total lanes of the way, in either direction.
+    pub fn total(&self) -> Option<NonZeroU8> {
+        match self {
+            Lanes::Bidirectional(total) => Some(*total),
+            Lanes::Unidirectional { forward, backward } => NonZeroU8::new(
+                forward
+                    .map_or(0, NonZeroU8::get)
+                    .saturating_add(backward.map_or(0, NonZeroU8::get)),
+            ),
+        }
+    }
+}
+
+/// A turn indicated upon a lane, typically by a painted arrow,
+/// like each of `left;through` within `turn:lanes=left;through|right`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Turn {
+    SharpLeft,
+    Left,
+    SlightLeft,
+    Through,
+    SlightRight,
+    Right,
+    SharpRight,
+    Reverse,
+    MergeToLeft,
+    MergeToRight,
+}
+
+impl Turn {
+    /// Parses the turns of a single lane, like `left;through`, where
+    /// an empty value or `none` indicates the lane is unmarked.
+    pub fn parse_lane(value: &str) -> Vec<Turn> {
+        value
+            .split(';')
+            .filter_map(|turn| turn.trim().parse().ok())
+            .collect()
+    }
+}
+
+/// The lane changes permitted from a lane, like each of
+/// `not_left|yes|not_right` within `change:lanes`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum LaneChange {
+    Yes,
+    No,
+    NotLeft,
+    NotRight,
+    OnlyLeft,
+    OnlyRight,
+}
+
+impl LaneChange {
+    /// If a change into the lane to the left, in the direction of travel, is permitted.
+    pub fn to_left(&self) -> bool {
+        matches!(self, Self::Yes | Self::NotRight | Self::OnlyLeft)
+    }
+
+    /// If a change into the lane to the right, in the direction of travel, is permitted.
+    pub fn to_right(&self) -> bool {
+        matches!(self, Self::Yes | Self::NotLeft | Self::OnlyRight)
+    }
+}
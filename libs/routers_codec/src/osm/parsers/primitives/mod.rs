@@ -1,14 +1,22 @@
 pub mod condition;
+pub mod country;
+pub mod dimension;
 pub mod direction;
+pub mod incline;
 pub mod lanes;
 pub mod opening_hours;
 pub mod road_class;
 pub mod speed;
+pub mod surface;
 pub mod transport;
 
 pub use condition::Condition;
+pub use country::CountryCode;
+pub use dimension::{Dimension, DimensionLimit};
 pub use direction::Directionality;
-pub use lanes::Lanes;
+pub use incline::Incline;
+pub use lanes::{LaneChange, Lanes, Turn};
 pub use road_class::RoadClass;
 pub use speed::{Speed, SpeedValue};
+pub use surface::{Smoothness, Surface, TrackType};
 pub use transport::TransportMode;
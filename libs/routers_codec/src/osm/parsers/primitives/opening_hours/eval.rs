@@ -0,0 +1,383 @@
+//! Evaluation of [`OpeningHours`] at a local date and time.
+//!
+//! Rules are applied in the order they are declared, such that the
+//! last rule selecting a date determines its times, as in `Mo-Sa 08:00-18:00; Sa off`.
+//! Times which pass midnight, like `22:00-06:00`, continue into the following
+//! day unless it is closed by its own rule.
+
+use super::{ClockTime, OpeningHours, OpeningRule, Weekday};
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use geo::Point;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::BuildHasher;
+
+/// The number of days ahead searched for a change, beyond
+/// which the opening hours are considered not to change.
+const HORIZON: i64 = 366;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Determines which dates are public holidays (`PH`).
+///
+/// Public holidays are region specific, so are provided
+/// by the caller rather than derived from the calendar.
+pub trait Holidays: Send + Sync {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+}
+
+/// Observes no public holidays, such that `PH` rules never apply.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHolidays;
+
+impl Holidays for NoHolidays {
+    fn is_holiday(&self, _: NaiveDate) -> bool {
+        false
+    }
+}
+
+impl<const N: usize> Holidays for [NaiveDate; N] {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl Holidays for Vec<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl Holidays for BTreeSet<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl<S: BuildHasher + Send + Sync> Holidays for HashSet<NaiveDate, S> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+/// Evaluates [`OpeningHours`] at a local date and time.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use routers_codec::osm::primitives::opening_hours::OpeningHoursParser;
+///
+/// let hours = OpeningHoursParser::parse("Mo-Fr 08:00-18:00; PH off").unwrap();
+/// let holidays = [NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()];
+///
+/// let evaluator = hours.evaluator().holidays(&holidays);
+/// let at = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap().and_hms_opt(12, 0, 0).unwrap();
+///
+/// assert!(!evaluator.is_open(at));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Evaluator<'a> {
+    hours: &'a OpeningHours,
+    location: Option<Point>,
+    offset: Option<FixedOffset>,
+    holidays: &'a dyn Holidays,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(hours: &'a OpeningHours) -> Self {
+        Evaluator {
+            hours,
+            location: None,
+            offset: None,
+            holidays: &NoHolidays,
+        }
+    }
+
+    /// The location the opening hours apply to, such as a point upon the way,
+    /// from which the times of sunrise, sunset, dawn and dusk are computed.
+    ///
+    /// Without a location, they are assumed to be 06:00, 18:00, 05:30 and 18:30.
+    pub fn location(self, location: Point) -> Self {
+        Self {
+            location: Some(location),
+            ..self
+        }
+    }
+
+    /// The offset of local time from UTC, used to convert the times of solar events.
+    ///
+    /// If unspecified, it is estimated as the nominal time zone of the
+    /// [location](Self::location), being one hour for every 15° of longitude.
+    pub fn offset(self, offset: FixedOffset) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// The public holidays, upon which `PH` rules apply.
+    pub fn holidays(self, holidays: &'a dyn Holidays) -> Self {
+        Self { holidays, ..self }
+    }
+
+    /// Determines if open at the local date and time.
+    pub fn is_open(&self, at: NaiveDateTime) -> bool {
+        [at.date() - Duration::days(1), at.date()]
+            .into_iter()
+            .flat_map(|date| self.spans(date))
+            .any(|(start, end)| start <= at && at < end)
+    }
+
+    /// The local date and time at which the opening hours next change from open
+    /// to closed, or closed to open, or `None` should they not change within a year.
+    pub fn next_change(&self, at: NaiveDateTime) -> Option<NaiveDateTime> {
+        let horizon = at + Duration::days(HORIZON);
+
+        if !self.is_open(at) {
+            return (0..=HORIZON)
+                .map(|offset| at.date() + Duration::days(offset))
+                .find_map(|date| {
+                    self.spans(date)
+                        .into_iter()
+                        .map(|(start, _)| start)
+                        .filter(|start| *start > at)
+                        .min()
+                })
+                .filter(|start| *start <= horizon);
+        }
+
+        // Extend through any spans which abut or overlap, such as
+        // those either side of midnight in "Mo-Su 18:00-24:00,00:00-02:00".
+        let mut end = at;
+        while let Some(next) = [end.date() - Duration::days(1), end.date()]
+            .into_iter()
+            .flat_map(|date| self.spans(date))
+            .filter(|(start, stop)| *start <= end && end < *stop)
+            .map(|(_, stop)| stop)
+            .max()
+        {
+            end = next;
+            if end > horizon {
+                return None;
+            }
+        }
+
+        Some(end)
+    }
+
+    /// The rule in effect upon the date, being the last which selects it.
+    fn rule(&self, date: NaiveDate) -> Option<&'a OpeningRule> {
+        self.hours.rules.iter().rev().find(|rule| {
+            let dated =
+                rule.dates.is_empty() || rule.dates.iter().any(|range| range.contains(date));
+            let weekday = Weekday::from(date.weekday());
+
+            let day = match (&rule.weekdays, rule.holidays) {
+                (None, false) => true,
+                (None, true) => self.holidays.is_holiday(date),
+                (Some(days), false) => days.contains(weekday),
+                (Some(days), true) => days.contains(weekday) || self.holidays.is_holiday(date),
+            };
+
+            dated && day
+        })
+    }
+
+    /// The spans of time opened by the rule in effect upon the date,
+    /// which may continue past midnight into the following day.
+    fn spans(&self, date: NaiveDate) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let Some(rule) = self.rule(date).filter(|rule| !rule.closed) else {
+            return vec![];
+        };
+
+        let midnight = date.and_time(NaiveTime::MIN);
+        let tomorrow = midnight + Duration::days(1);
+
+        if rule.times.is_empty() {
+            return vec![(midnight, tomorrow)];
+        }
+
+        let closed_tomorrow = self.rule(tomorrow.date()).is_some_and(|rule| rule.closed);
+
+        rule.times
+            .iter()
+            .filter_map(|range| {
+                let start = self.minutes(range.start, date)?;
+                let mut end = self.minutes(range.end, date)?;
+
+                if end <= start {
+                    end += MINUTES_PER_DAY;
+                }
+
+                let start = midnight + Duration::minutes(start);
+                let end = midnight + Duration::minutes(end);
+                let end = if closed_tomorrow {
+                    end.min(tomorrow)
+                } else {
+                    end
+                };
+
+                Some((start, end))
+            })
+            .filter(|(start, end)| start < end)
+            .collect()
+    }
+
+    /// The local minutes since midnight of the time upon the date.
+    fn minutes(&self, time: ClockTime, date: NaiveDate) -> Option<i64> {
+        match time {
+            ClockTime::Fixed(time) => Some(time.minutes() as i64),
+            ClockTime::Solar { event, offset } => {
+                let minutes = match self.location {
+                    Some(location) => {
+                        let utc = event.utc_minutes(date, location)?;
+                        let local = self
+                            .offset
+                            .map(|offset| offset.local_minus_utc() as f64 / 60.0)
+                            .unwrap_or_else(|| (location.x() / 15.0).round() * 60.0);
+
+                        (utc + local).round() as i64
+                    }
+                    None => event.fallback(),
+                };
+
+                Some((minutes + offset as i64).rem_euclid(MINUTES_PER_DAY))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::primitives::opening_hours::OpeningHoursParser;
+
+    /// A date and time within the week of monday the 6th of january, 2025.
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .unwrap()
+    }
+
+    fn parse(input: &str) -> OpeningHours {
+        OpeningHoursParser::parse(input).unwrap()
+    }
+
+    #[test]
+    fn weekday_ranges() {
+        let hours = parse("Mo-Fr 09:00-17:00");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(6, 12, 0)));
+        assert!(!evaluator.is_open(at(6, 8, 59)));
+        assert!(!evaluator.is_open(at(6, 17, 0)));
+        assert!(!evaluator.is_open(at(11, 12, 0)));
+
+        assert_eq!(evaluator.next_change(at(6, 12, 0)), Some(at(6, 17, 0)));
+        assert_eq!(evaluator.next_change(at(10, 18, 0)), Some(at(13, 9, 0)));
+    }
+
+    #[test]
+    fn multiple_spans() {
+        let hours = parse("Mo-Fr 07:00-09:00,16:00-20:00");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(7, 8, 0)));
+        assert!(!evaluator.is_open(at(7, 12, 0)));
+        assert!(evaluator.is_open(at(7, 19, 0)));
+        assert_eq!(evaluator.next_change(at(7, 12, 0)), Some(at(7, 16, 0)));
+    }
+
+    #[test]
+    fn overnight_spans() {
+        let hours = parse("Fr 22:00-06:00; Sa off");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(10, 23, 0)));
+        assert!(!evaluator.is_open(at(11, 1, 0)), "saturday is closed");
+
+        let hours = parse("Mo-Fr 22:00-06:00");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(7, 3, 0)), "continues from monday");
+        assert!(evaluator.is_open(at(11, 5, 0)), "continues from friday");
+        assert!(!evaluator.is_open(at(12, 3, 0)));
+        assert_eq!(evaluator.next_change(at(6, 23, 0)), Some(at(7, 6, 0)));
+    }
+
+    #[test]
+    fn off_rules() {
+        let hours = parse("Mo-Sa 08:00-18:00; We off");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(7, 12, 0)));
+        assert!(!evaluator.is_open(at(8, 12, 0)));
+        assert_eq!(evaluator.next_change(at(7, 19, 0)), Some(at(9, 8, 0)));
+    }
+
+    #[test]
+    fn always_open() {
+        let hours = parse("24/7");
+        let evaluator = hours.evaluator();
+
+        assert!(evaluator.is_open(at(6, 0, 0)));
+        assert!(evaluator.is_open(at(12, 23, 59)));
+        assert_eq!(evaluator.next_change(at(6, 12, 0)), None);
+
+        let hours = parse("closed");
+        assert!(!hours.evaluator().is_open(at(6, 12, 0)));
+        assert_eq!(hours.evaluator().next_change(at(6, 12, 0)), None);
+    }
+
+    #[test]
+    fn date_ranges() {
+        let hours = parse("Nov-Mar Mo-Fr 07:00-19:00; Dec 24-26 off");
+        let evaluator = hours.evaluator();
+
+        let date = |month, day, hour| {
+            NaiveDate::from_ymd_opt(2025, month, day)
+                .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                .unwrap()
+        };
+
+        assert!(evaluator.is_open(date(1, 6, 12)));
+        assert!(!evaluator.is_open(date(7, 7, 12)), "outside of winter");
+        assert!(!evaluator.is_open(date(12, 24, 12)), "christmas eve");
+        assert!(evaluator.is_open(date(12, 23, 12)));
+        assert_eq!(evaluator.next_change(date(3, 31, 20)), Some(date(11, 3, 7)));
+    }
+
+    #[test]
+    fn public_holidays() {
+        let hours = parse("Mo-Fr 08:00-18:00; PH off; Su,PH 10:00-12:00");
+        let holidays = [NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()];
+        let evaluator = hours.evaluator().holidays(&holidays);
+
+        assert!(!evaluator.is_open(at(6, 9, 0)), "holiday monday");
+        assert!(evaluator.is_open(at(6, 11, 0)));
+        assert!(evaluator.is_open(at(7, 9, 0)));
+        assert!(evaluator.is_open(at(12, 11, 0)));
+
+        assert!(hours.evaluator().is_open(at(6, 9, 0)), "without holidays");
+    }
+
+    #[test]
+    fn solar_times() {
+        let hours = parse("sunrise-sunset");
+
+        let fallback = hours.evaluator();
+        assert!(fallback.is_open(at(6, 6, 0)));
+        assert_eq!(fallback.next_change(at(6, 12, 0)), Some(at(6, 18, 0)));
+
+        // Sunrise in london is at 08:06 upon the 6th of january.
+        let london = hours.evaluator().location(Point::new(-0.1278, 51.5074));
+        assert!(!london.is_open(at(6, 8, 0)));
+        assert!(london.is_open(at(6, 8, 10)));
+
+        let sunset = london.next_change(at(6, 12, 0)).unwrap();
+        assert!((at(6, 16, 0)..at(6, 16, 10)).contains(&sunset), "{sunset}");
+
+        let hours = parse("(sunrise+01:00)-(sunset-01:00)");
+        let evaluator = hours.evaluator();
+        assert!(!evaluator.is_open(at(6, 6, 30)));
+        assert_eq!(evaluator.next_change(at(6, 6, 30)), Some(at(6, 7, 0)));
+    }
+}
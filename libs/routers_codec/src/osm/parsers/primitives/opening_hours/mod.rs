@@ -0,0 +1,968 @@
+use either::{Left, Right};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use strum::{Display, EnumString};
+
+mod eval;
+mod solar;
+
+pub use eval::{Evaluator, Holidays, NoHolidays};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum Weekday {
+    #[strum(serialize = "Mo")]
+    Monday,
+    #[strum(serialize = "Tu")]
+    Tuesday,
+    #[strum(serialize = "We")]
+    Wednesday,
+    #[strum(serialize = "Th")]
+    Thursday,
+    #[strum(serialize = "Fr")]
+    Friday,
+    #[strum(serialize = "Sa")]
+    Saturday,
+    #[strum(serialize = "Su")]
+    Sunday,
+}
+
+impl Weekday {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "mo" | "monday" => Some(Weekday::Monday),
+            "tu" | "tuesday" => Some(Weekday::Tuesday),
+            "we" | "wednesday" => Some(Weekday::Wednesday),
+            "th" | "thursday" => Some(Weekday::Thursday),
+            "fr" | "friday" => Some(Weekday::Friday),
+            "sa" | "saturday" => Some(Weekday::Saturday),
+            "su" | "sunday" => Some(Weekday::Sunday),
+            _ => None,
+        }
+    }
+
+    /// The days from monday, such that monday is `0` and sunday is `6`.
+    pub fn index(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(value: chrono::Weekday) -> Self {
+        match value {
+            chrono::Weekday::Mon => Weekday::Monday,
+            chrono::Weekday::Tue => Weekday::Tuesday,
+            chrono::Weekday::Wed => Weekday::Wednesday,
+            chrono::Weekday::Thu => Weekday::Thursday,
+            chrono::Weekday::Fri => Weekday::Friday,
+            chrono::Weekday::Sat => Weekday::Saturday,
+            chrono::Weekday::Sun => Weekday::Sunday,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+pub enum Month {
+    #[strum(serialize = "Jan")]
+    January = 1,
+    #[strum(serialize = "Feb")]
+    February,
+    #[strum(serialize = "Mar")]
+    March,
+    #[strum(serialize = "Apr")]
+    April,
+    #[strum(serialize = "May")]
+    May,
+    #[strum(serialize = "Jun")]
+    June,
+    #[strum(serialize = "Jul")]
+    July,
+    #[strum(serialize = "Aug")]
+    August,
+    #[strum(serialize = "Sep")]
+    September,
+    #[strum(serialize = "Oct")]
+    October,
+    #[strum(serialize = "Nov")]
+    November,
+    #[strum(serialize = "Dec")]
+    December,
+}
+
+impl Month {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "jan" | "january" => Some(Month::January),
+            "feb" | "february" => Some(Month::February),
+            "mar" | "march" => Some(Month::March),
+            "apr" | "april" => Some(Month::April),
+            "may" => Some(Month::May),
+            "jun" | "june" => Some(Month::June),
+            "jul" | "july" => Some(Month::July),
+            "aug" | "august" => Some(Month::August),
+            "sep" | "september" => Some(Month::September),
+            "oct" | "october" => Some(Month::October),
+            "nov" | "november" => Some(Month::November),
+            "dec" | "december" => Some(Month::December),
+            _ => None,
+        }
+    }
+
+    fn from_number(number: u32) -> Self {
+        match number {
+            1 => Month::January,
+            2 => Month::February,
+            3 => Month::March,
+            4 => Month::April,
+            5 => Month::May,
+            6 => Month::June,
+            7 => Month::July,
+            8 => Month::August,
+            9 => Month::September,
+            10 => Month::October,
+            11 => Month::November,
+            _ => Month::December,
+        }
+    }
+
+    /// The number of the month, such that january is `1`.
+    pub fn number(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// A day of the year, or an entire month should the day be unspecified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthDay {
+    pub month: Month,
+    pub day: Option<u8>,
+}
+
+impl Display for MonthDay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.day {
+            Some(day) => write!(f, "{} {:02}", self.month, day),
+            None => write!(f, "{}", self.month),
+        }
+    }
+}
+
+/// A range of dates within each year, such as `Nov-Mar`, `Dec 25` or `May 01-Sep 30`,
+/// which may wrap around the new year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: MonthDay,
+    pub end: Option<MonthDay>,
+}
+
+impl DateRange {
+    /// Determines if the date is within the range, inclusive of both ends.
+    pub fn contains(&self, date: chrono::NaiveDate) -> bool {
+        use chrono::Datelike;
+
+        let key = |month: Month, day: u32| month.number() * 32 + day;
+        let end = self.end.unwrap_or(self.start);
+
+        let at = key(Month::from_number(date.month()), date.day());
+        let start = key(self.start.month, self.start.day.map_or(1, u32::from));
+        let end = key(end.month, end.day.map_or(31, u32::from));
+
+        if start <= end {
+            start <= at && at <= end
+        } else {
+            at >= start || at <= end
+        }
+    }
+}
+
+impl Display for DateRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl Time {
+    fn new(hour: u8, minute: u8) -> Result<Self, String> {
+        if hour > 24 || minute > 59 {
+            Err("Invalid time".to_string())
+        } else {
+            Ok(Time { hour, minute })
+        }
+    }
+
+    /// The minutes since midnight.
+    pub fn minutes(&self) -> u16 {
+        self.hour as u16 * 60 + self.minute as u16
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// The position of the sun, from which a variable time is derived.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:opening_hours/specification#explain:variable_time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum SolarEvent {
+    /// The start of civil twilight, with the sun 6° below the horizon.
+    Dawn,
+    Sunrise,
+    Sunset,
+    /// The end of civil twilight, with the sun 6° below the horizon.
+    Dusk,
+}
+
+/// The time of day, either fixed upon the clock or relative to the sun.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockTime {
+    Fixed(Time),
+    /// The time of the event, offset by a number of minutes,
+    /// like `(sunset-01:00)`.
+    Solar {
+        event: SolarEvent,
+        offset: i16,
+    },
+}
+
+impl From<Time> for ClockTime {
+    fn from(value: Time) -> Self {
+        ClockTime::Fixed(value)
+    }
+}
+
+impl Display for ClockTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockTime::Fixed(time) => write!(f, "{}", time),
+            ClockTime::Solar { event, offset: 0 } => write!(f, "{}", event),
+            ClockTime::Solar { event, offset } => {
+                let sign = if *offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(
+                    f,
+                    "({}{}{:02}:{:02})",
+                    event,
+                    sign,
+                    offset / 60,
+                    offset % 60
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeRange {
+    pub start: ClockTime,
+    pub end: ClockTime,
+}
+
+impl Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeekdayRange {
+    Single(Weekday),
+    Range(Weekday, Weekday),
+    List(Vec<Weekday>),
+}
+
+impl WeekdayRange {
+    /// Determines if the weekday is selected, where a range
+    /// may wrap around the week, like `Fr-Mo`.
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        match self {
+            WeekdayRange::Single(day) => *day == weekday,
+            WeekdayRange::List(days) => days.contains(&weekday),
+            WeekdayRange::Range(start, end) if start.index() <= end.index() => {
+                (start.index()..=end.index()).contains(&weekday.index())
+            }
+            WeekdayRange::Range(start, end) => {
+                weekday.index() >= start.index() || weekday.index() <= end.index()
+            }
+        }
+    }
+
+    fn days(&self) -> Vec<Weekday> {
+        match self {
+            WeekdayRange::Single(day) => vec![*day],
+            WeekdayRange::List(days) => days.clone(),
+            WeekdayRange::Range(start, end) => {
+                let length = (end.index() + 7 - start.index()) % 7;
+                (0..=length)
+                    .map(|offset| WEEK[((start.index() + offset) % 7) as usize])
+                    .collect()
+            }
+        }
+    }
+}
+
+const WEEK: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+impl Display for WeekdayRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WeekdayRange::Single(weekday) => {
+                write!(f, "{}", weekday)
+            }
+            WeekdayRange::Range(start, end) => {
+                write!(f, "{}-{}", start, end)
+            }
+            WeekdayRange::List(weekdays) => {
+                write!(
+                    f,
+                    "{}",
+                    weekdays.iter().map(|weekday| weekday.to_string()).join(",")
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpeningRule {
+    /// The dates within each year the rule applies to, or all dates if empty.
+    pub dates: Vec<DateRange>,
+    pub weekdays: Option<WeekdayRange>,
+    /// If the rule applies to public holidays (`PH`), in addition to any weekdays.
+    pub holidays: bool,
+    pub times: Vec<TimeRange>,
+    pub closed: bool,
+}
+
+impl Display for OpeningRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dates = self.dates.iter().map(|s| s.to_string()).join(",");
+        let days = self
+            .weekdays
+            .iter()
+            .map(|weekday| weekday.to_string())
+            .chain(self.holidays.then(|| "PH".to_string()))
+            .join(",");
+        let times = self.times.iter().map(|s| s.to_string()).join(",");
+        let closed = if self.closed { "off" } else { "" };
+
+        write!(
+            f,
+            "{}",
+            [dates, days, times, closed.to_string()]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .join(" ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningHours {
+    pub rules: Vec<OpeningRule>,
+}
+
+impl Display for OpeningHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.rules.iter().map(|rule| format!("{}", rule)).join(";")
+        )
+    }
+}
+
+pub struct OpeningHoursParser;
+
+impl OpeningHoursParser {
+    pub fn parse(input: &str) -> Result<OpeningHours, String> {
+        let input = input.trim();
+
+        // Handle special cases
+        if input.eq_ignore_ascii_case("24/7") {
+            return Ok(OpeningHours {
+                rules: vec![OpeningRule {
+                    times: vec![OpeningHoursParser::all_day()?],
+                    ..OpeningRule::default()
+                }],
+            });
+        }
+
+        let mut rules = Vec::new();
+        let rule_parts: Vec<&str> = input.split(';').collect();
+
+        for rule_part in rule_parts {
+            let rule = OpeningHoursParser::parse_rule(rule_part.trim())?;
+            rules.push(rule);
+        }
+
+        if rules.is_empty() {
+            return Err("No rules".to_string());
+        }
+
+        Ok(OpeningHours { rules })
+    }
+
+    fn all_day() -> Result<TimeRange, String> {
+        Ok(TimeRange {
+            start: Time::new(0, 0)?.into(),
+            end: Time::new(24, 0)?.into(),
+        })
+    }
+
+    fn parse_rule(rule: &str) -> Result<OpeningRule, String> {
+        let rule = rule.trim();
+
+        // Check if it's a closed rule
+        if rule.eq_ignore_ascii_case("closed") || rule.eq_ignore_ascii_case("off") {
+            return Ok(OpeningRule {
+                closed: true,
+                ..OpeningRule::default()
+            });
+        }
+
+        // Split by space to separate weekdays from times
+        let mut parts: Vec<&str> = rule.split_whitespace().collect();
+
+        if parts.is_empty() {
+            return Err("Empty rule".to_string());
+        }
+
+        // A trailing modifier, like "Sa off"
+        let closed = match parts.last().map(|part| part.to_lowercase()).as_deref() {
+            Some("off" | "closed") => {
+                parts.pop();
+                true
+            }
+            Some("open") => {
+                parts.pop();
+                false
+            }
+            _ => false,
+        };
+
+        let (time_parts, selector_parts): (Vec<_>, Vec<_>) = parts
+            .iter()
+            .scan(false, |parsing_times, part| {
+                *parsing_times |= OpeningHoursParser::looks_like_time(part);
+                Some((part, *parsing_times))
+            })
+            .partition_map(|(part, is_time)| if is_time { Left(*part) } else { Right(*part) });
+
+        // Weekdays follow any dates, which may themselves contain spaces, like "Dec 25"
+        let (weekday_parts, date_parts): (Vec<_>, Vec<_>) = selector_parts
+            .into_iter()
+            .partition(|part| OpeningHoursParser::parse_weekday_selector(part).is_ok());
+
+        let (weekdays, holidays) = match weekday_parts.as_slice() {
+            [] => (None, false),
+            [part] => OpeningHoursParser::parse_weekday_selector(part)?,
+            _ => return Err("Multiple weekday selectors".to_string()),
+        };
+
+        let dates = if date_parts.is_empty() {
+            Vec::new()
+        } else {
+            OpeningHoursParser::parse_date_ranges(&date_parts.join(" "))?
+        };
+
+        let times = match time_parts.as_slice() {
+            [] => Vec::new(),
+            ["24/7"] => vec![OpeningHoursParser::all_day()?],
+            _ => OpeningHoursParser::parse_time_ranges(&time_parts.join(" "))?,
+        };
+
+        if dates.is_empty() && weekdays.is_none() && !holidays && times.is_empty() && !closed {
+            return Err("No applicable values parsed".to_string());
+        }
+
+        Ok(OpeningRule {
+            dates,
+            weekdays,
+            holidays,
+            times,
+            closed,
+        })
+    }
+
+    fn looks_like_time(s: &str) -> bool {
+        let lower = s.to_lowercase();
+
+        (s.contains(':') && s.len() >= 3)
+            || s.starts_with('(')
+            || lower == "24/7"
+            || ["dawn", "sunrise", "sunset", "dusk"]
+                .iter()
+                .any(|event| lower.starts_with(event))
+    }
+
+    /// Parses the days a rule applies to, like `Mo-Fr,PH`, into
+    /// the weekdays and whether it applies to public holidays.
+    fn parse_weekday_selector(input: &str) -> Result<(Option<WeekdayRange>, bool), String> {
+        let mut holidays = false;
+        let mut ranges = Vec::new();
+
+        for part in input.split(',') {
+            if part.eq_ignore_ascii_case("ph") {
+                holidays = true;
+            } else {
+                ranges.push(OpeningHoursParser::parse_weekday_range(part)?);
+            }
+        }
+
+        let weekdays = match ranges.len() {
+            0 => None,
+            1 => ranges.pop(),
+            _ => Some(WeekdayRange::List(
+                ranges
+                    .iter()
+                    .flat_map(WeekdayRange::days)
+                    .unique()
+                    .collect(),
+            )),
+        };
+
+        Ok((weekdays, holidays))
+    }
+
+    fn parse_weekday_range(input: &str) -> Result<WeekdayRange, String> {
+        if input.contains('-') {
+            let parts: Vec<&str> = input.split('-').collect();
+            if parts.len() != 2 {
+                return Err("Invalid weekday range".to_string());
+            }
+            let start = Weekday::from_str(parts[0]).ok_or("Invalid start weekday")?;
+            let end = Weekday::from_str(parts[1]).ok_or("Invalid end weekday")?;
+            Ok(WeekdayRange::Range(start, end))
+        } else if input.contains(',') {
+            let parts: Vec<&str> = input.split(',').collect();
+            let mut weekdays = Vec::new();
+            for part in parts {
+                let weekday = Weekday::from_str(part.trim()).ok_or("Invalid weekday in list")?;
+                weekdays.push(weekday);
+            }
+            Ok(WeekdayRange::List(weekdays))
+        } else {
+            let weekday = Weekday::from_str(input).ok_or("Invalid weekday")?;
+            Ok(WeekdayRange::Single(weekday))
+        }
+    }
+
+    /// Parses a list of date ranges, like `Jan-Mar`, `Dec 24-26` or `Dec 24-Jan 02`.
+    fn parse_date_ranges(input: &str) -> Result<Vec<DateRange>, String> {
+        input
+            .split(',')
+            .map(|range| {
+                let (start, end) = match range.split_once('-') {
+                    Some((start, end)) => (start, Some(end)),
+                    None => (range, None),
+                };
+
+                let start = OpeningHoursParser::parse_month_day(start, None)?;
+                let end = end
+                    .map(|end| OpeningHoursParser::parse_month_day(end, Some(start.month)))
+                    .transpose()?;
+
+                Ok(DateRange { start, end })
+            })
+            .collect()
+    }
+
+    /// Parses a month and optional day, like `Dec 25`, where the month
+    /// may be omitted if it is given by the start of the range.
+    fn parse_month_day(input: &str, month: Option<Month>) -> Result<MonthDay, String> {
+        let mut parts = input.split_whitespace();
+        let first = parts.next().ok_or("Empty date")?;
+
+        let (month, day) = match (Month::from_str(first), month) {
+            (Some(month), _) => (month, parts.next()),
+            (None, Some(month)) => (month, Some(first)),
+            (None, None) => return Err("Invalid month".to_string()),
+        };
+
+        if parts.next().is_some() {
+            return Err("Invalid date".to_string());
+        }
+
+        let day = day
+            .map(|day| day.parse::<u8>().map_err(|_| "Invalid day"))
+            .transpose()?;
+
+        if day.is_some_and(|day| day == 0 || day > 31) {
+            return Err("Invalid day".to_string());
+        }
+
+        Ok(MonthDay { month, day })
+    }
+
+    fn parse_time_ranges(input: &str) -> Result<Vec<TimeRange>, String> {
+        let mut ranges = Vec::new();
+
+        // Split by comma for multiple time ranges
+        let range_parts: Vec<&str> = input.split(',').collect();
+
+        for range_part in range_parts {
+            let range_part = range_part.trim();
+            let (start, rest) = OpeningHoursParser::parse_clock_time(range_part)?;
+
+            if let Some(end) = rest.strip_prefix('-') {
+                let (end, rest) = OpeningHoursParser::parse_clock_time(end.trim())?;
+                if !rest.is_empty() {
+                    return Err("Invalid time range format".to_string());
+                }
+
+                ranges.push(TimeRange { start, end });
+            } else if rest.is_empty() {
+                // Single time point - treat as start time with end time one hour later
+                let ClockTime::Fixed(time) = start else {
+                    return Err("Invalid time range format".to_string());
+                };
+
+                let end_hour = if time.hour == 23 { 0 } else { time.hour + 1 };
+                ranges.push(TimeRange {
+                    start,
+                    end: Time::new(end_hour, time.minute)?.into(),
+                });
+            } else {
+                return Err("Invalid time range format".to_string());
+            }
+        }
+
+        Ok(ranges)
+    }
+
+    /// Parses the time at the start of the input, returning it and the remaining input.
+    fn parse_clock_time(input: &str) -> Result<(ClockTime, &str), String> {
+        // A solar event with an offset, like "(sunset-01:00)"
+        if let Some(inner) = input.strip_prefix('(') {
+            let (inner, rest) = inner.split_once(')').ok_or("Unclosed variable time")?;
+            let (event, offset) =
+                OpeningHoursParser::parse_event(inner.trim()).ok_or("Invalid variable time")?;
+
+            let offset = offset.trim();
+            let sign = match offset.chars().next() {
+                Some('+') => 1,
+                Some('-') => -1,
+                _ => return Err("Invalid variable time offset".to_string()),
+            };
+
+            let offset = OpeningHoursParser::parse_time(&offset[1..])?;
+            let offset = sign * offset.minutes() as i16;
+
+            return Ok((ClockTime::Solar { event, offset }, rest.trim_start()));
+        }
+
+        if let Some((event, rest)) = OpeningHoursParser::parse_event(input) {
+            return Ok((ClockTime::Solar { event, offset: 0 }, rest.trim_start()));
+        }
+
+        let end = input
+            .find(|c: char| !(c.is_ascii_digit() || c == ':'))
+            .unwrap_or(input.len());
+
+        let time = OpeningHoursParser::parse_time(&input[..end])?;
+        Ok((time.into(), input[end..].trim_start()))
+    }
+
+    /// Parses the solar event at the start of the input, returning it and the remaining input.
+    fn parse_event(input: &str) -> Option<(SolarEvent, &str)> {
+        let lower = input.to_lowercase();
+
+        ["dawn", "sunrise", "sunset", "dusk"]
+            .into_iter()
+            .find(|event| lower.starts_with(event))
+            .and_then(|event| Some((event.parse().ok()?, &input[event.len()..])))
+    }
+
+    fn parse_time(input: &str) -> Result<Time, String> {
+        let input = input.trim();
+
+        if input.contains(':') {
+            let parts: Vec<&str> = input.split(':').collect();
+            if parts.len() != 2 {
+                return Err("Invalid time format".to_string());
+            }
+
+            let hour: u8 = parts[0].parse().map_err(|_| "Invalid hour")?;
+            let minute: u8 = parts[1].parse().map_err(|_| "Invalid minute")?;
+
+            Time::new(hour, minute)
+        } else {
+            // Assume it's just hours
+            let hour: u8 = input.parse().map_err(|_| "Invalid hour")?;
+            Time::new(hour, 0)
+        }
+    }
+}
+
+// Utility functions for working with parsed opening hours
+impl OpeningHours {
+    /// Creates an [`Evaluator`] of the opening hours, which determines
+    /// if they are open at a date and time, and when they next change.
+    pub fn evaluator(&self) -> Evaluator<'_> {
+        Evaluator::new(self)
+    }
+
+    /// Determines if open at the time upon the weekday, considering
+    /// only rules which apply regardless of the date and fixed times.
+    ///
+    /// See [`Evaluator::is_open`] to consider all rules.
+    pub fn is_open_at(&self, weekday: &Weekday, time: &Time) -> bool {
+        let rule = self.rules.iter().rev().find(|rule| {
+            rule.dates.is_empty()
+                && (rule.weekdays.is_some() || !rule.holidays)
+                && rule
+                    .weekdays
+                    .as_ref()
+                    .is_none_or(|days| days.contains(*weekday))
+        });
+
+        let Some(rule) = rule.filter(|rule| !rule.closed) else {
+            return false;
+        };
+
+        rule.times.is_empty()
+            || rule
+                .times
+                .iter()
+                .any(|range| match (range.start, range.end) {
+                    (ClockTime::Fixed(start), ClockTime::Fixed(end)) => {
+                        self.time_in_range(time, &start, &end)
+                    }
+                    _ => false,
+                })
+    }
+
+    fn time_in_range(&self, time: &Time, start: &Time, end: &Time) -> bool {
+        let time_minutes = time.minutes();
+        let start_minutes = start.minutes();
+        let end_minutes = end.minutes();
+
+        if start_minutes <= end_minutes {
+            time_minutes >= start_minutes && time_minutes <= end_minutes
+        } else {
+            // Handle overnight ranges
+            time_minutes >= start_minutes || time_minutes <= end_minutes
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_24_7() {
+        let result = OpeningHoursParser::parse("24/7").unwrap();
+        assert_eq!(result.rules.len(), 1);
+        assert!(!result.rules[0].closed);
+    }
+
+    #[test]
+    fn test_simple_time_range() {
+        let result = OpeningHoursParser::parse("09:00-17:00").unwrap();
+        assert_eq!(result.rules.len(), 1);
+        assert_eq!(result.rules[0].times.len(), 1);
+        assert_eq!(
+            result.rules[0].times[0].start,
+            Time::new(9, 0).unwrap().into()
+        );
+        assert_eq!(
+            result.rules[0].times[0].end,
+            Time::new(17, 0).unwrap().into()
+        );
+    }
+
+    #[test]
+    fn test_multiple_hours() {
+        let result = OpeningHoursParser::parse("Mo-Fr 07:00-9:00,16:00-20:00").unwrap();
+        assert_eq!(result.rules.len(), 1);
+        assert!(matches!(
+            result.rules[0].clone().weekdays.unwrap(),
+            WeekdayRange::Range(Weekday::Monday, Weekday::Friday)
+        ));
+
+        assert_eq!(
+            result.rules[0].times[0].start,
+            Time::new(7, 0).unwrap().into()
+        );
+        assert_eq!(
+            result.rules[0].times[0].end,
+            Time::new(9, 0).unwrap().into()
+        );
+
+        assert_eq!(
+            result.rules[0].times[1].start,
+            Time::new(16, 0).unwrap().into()
+        );
+        assert_eq!(
+            result.rules[0].times[1].end,
+            Time::new(20, 0).unwrap().into()
+        );
+    }
+
+    #[test]
+    fn test_weekday_with_time() {
+        let result = OpeningHoursParser::parse("Mo-Fr 09:00-17:00").unwrap();
+        assert_eq!(result.rules.len(), 1);
+        assert!(result.rules[0].weekdays.is_some());
+    }
+
+    #[test]
+    fn test_multiple_rules() {
+        let result = OpeningHoursParser::parse("Mo-Fr 09:00-17:00; Sa 10:00-14:00").unwrap();
+        assert_eq!(result.rules.len(), 2);
+    }
+
+    #[test]
+    fn test_closed() {
+        let result = OpeningHoursParser::parse("closed").unwrap();
+        assert_eq!(result.rules.len(), 1);
+        assert!(result.rules[0].closed);
+    }
+
+    #[test]
+    fn test_is_open_at() {
+        let hours = OpeningHoursParser::parse("Mo-Fr 09:00-17:00").unwrap();
+
+        let monday_noon = Time::new(12, 0).unwrap();
+        assert!(hours.is_open_at(&Weekday::Monday, &monday_noon));
+
+        let monday_early = Time::new(8, 0).unwrap();
+        assert!(!hours.is_open_at(&Weekday::Monday, &monday_early));
+
+        let saturday_noon = Time::new(12, 0).unwrap();
+        assert!(!hours.is_open_at(&Weekday::Saturday, &saturday_noon));
+    }
+
+    #[test]
+    fn test_weekday_off() {
+        let result = OpeningHoursParser::parse("Mo-Sa 08:00-18:00; Su off").unwrap();
+        assert_eq!(result.rules.len(), 2);
+        assert_eq!(
+            result.rules[1],
+            OpeningRule {
+                weekdays: Some(WeekdayRange::Single(Weekday::Sunday)),
+                closed: true,
+                ..OpeningRule::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_holidays() {
+        let result = OpeningHoursParser::parse("Mo-Fr,PH 10:00-12:00; PH off").unwrap();
+        assert!(result.rules[0].holidays);
+        assert_eq!(
+            result.rules[0].weekdays,
+            Some(WeekdayRange::Range(Weekday::Monday, Weekday::Friday))
+        );
+
+        assert!(result.rules[1].holidays && result.rules[1].closed);
+        assert_eq!(result.rules[1].weekdays, None);
+    }
+
+    #[test]
+    fn test_mixed_weekdays() {
+        let result = OpeningHoursParser::parse("Mo-We,Sa 10:00-12:00").unwrap();
+        assert_eq!(
+            result.rules[0].weekdays,
+            Some(WeekdayRange::List(vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Saturday
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_wrapping_weekdays() {
+        let range = WeekdayRange::Range(Weekday::Friday, Weekday::Monday);
+        assert!(range.contains(Weekday::Saturday));
+        assert!(range.contains(Weekday::Monday));
+        assert!(!range.contains(Weekday::Wednesday));
+    }
+
+    #[test]
+    fn test_date_ranges() {
+        let result = OpeningHoursParser::parse("Nov-Mar Mo-Fr 07:00-19:00").unwrap();
+        assert_eq!(
+            result.rules[0].dates,
+            vec![DateRange {
+                start: MonthDay {
+                    month: Month::November,
+                    day: None
+                },
+                end: Some(MonthDay {
+                    month: Month::March,
+                    day: None
+                }),
+            }]
+        );
+
+        let result = OpeningHoursParser::parse("Dec 24-26 off").unwrap();
+        let range = result.rules[0].dates[0];
+        assert_eq!(range.end.unwrap().month, Month::December);
+        assert_eq!(range.end.unwrap().day, Some(26));
+
+        let date = |month, day| chrono::NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        assert!(range.contains(date(12, 25)));
+        assert!(!range.contains(date(12, 27)));
+
+        let result = OpeningHoursParser::parse("May 01-Sep 30 10:00-18:00").unwrap();
+        let range = result.rules[0].dates[0];
+        assert!(range.contains(date(7, 14)));
+        assert!(!range.contains(date(10, 1)));
+    }
+
+    #[test]
+    fn test_solar_times() {
+        let result = OpeningHoursParser::parse("sunrise-(sunset-01:30)").unwrap();
+        assert_eq!(
+            result.rules[0].times[0],
+            TimeRange {
+                start: ClockTime::Solar {
+                    event: SolarEvent::Sunrise,
+                    offset: 0
+                },
+                end: ClockTime::Solar {
+                    event: SolarEvent::Sunset,
+                    offset: -90
+                },
+            }
+        );
+
+        assert_eq!(result.to_string(), "sunrise-(sunset-01:30)");
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "Nov-Mar Mo-Fr,PH 07:00-19:00; Dec 25 off";
+        let result = OpeningHoursParser::parse(input).unwrap();
+        assert_eq!(
+            result.to_string(),
+            "Nov-Mar Mo-Fr,PH 07:00-19:00;Dec 25 off"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! The times of sunrise, sunset, dawn and dusk, using the
+//! approximate equations of the NOAA Global Monitoring Division.
+//!
+//! Source: https://gml.noaa.gov/grad/solcalc/solareqns.PDF
+
+use super::SolarEvent;
+use chrono::{Datelike, NaiveDate};
+use geo::Point;
+use std::f64::consts::PI;
+
+/// The zenith of the sun at sunrise and sunset, accounting for refraction
+/// and the size of the solar disc.
+const HORIZON_ZENITH: f64 = 90.833;
+
+/// The zenith of the sun at the start and end of civil twilight.
+const CIVIL_ZENITH: f64 = 96.0;
+
+impl SolarEvent {
+    /// The local time of the event, in minutes since midnight, assumed
+    /// when the location of the opening hours is unknown.
+    pub(super) fn fallback(&self) -> i64 {
+        match self {
+            SolarEvent::Dawn => 5 * 60 + 30,
+            SolarEvent::Sunrise => 6 * 60,
+            SolarEvent::Sunset => 18 * 60,
+            SolarEvent::Dusk => 18 * 60 + 30,
+        }
+    }
+
+    /// The time of the event upon the date at the location, in minutes since
+    /// midnight UTC, or `None` should the sun not cross the zenith of the event
+    /// that day, such as during the polar summer.
+    pub(super) fn utc_minutes(&self, date: NaiveDate, location: Point) -> Option<f64> {
+        let zenith = match self {
+            SolarEvent::Sunrise | SolarEvent::Sunset => HORIZON_ZENITH,
+            SolarEvent::Dawn | SolarEvent::Dusk => CIVIL_ZENITH,
+        };
+
+        // The fractional year, in radians, at noon
+        let gamma = 2.0 * PI / 365.0 * (date.ordinal0() as f64);
+
+        let equation_of_time = 229.18
+            * (0.000075 + 0.001868 * gamma.cos()
+                - 0.032077 * gamma.sin()
+                - 0.014615 * (2.0 * gamma).cos()
+                - 0.040849 * (2.0 * gamma).sin());
+
+        let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+            - 0.006758 * (2.0 * gamma).cos()
+            + 0.000907 * (2.0 * gamma).sin()
+            - 0.002697 * (3.0 * gamma).cos()
+            + 0.00148 * (3.0 * gamma).sin();
+
+        let latitude = location.y().to_radians();
+        let cos_hour_angle = zenith.to_radians().cos() / (latitude.cos() * declination.cos())
+            - latitude.tan() * declination.tan();
+
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+
+        let hour_angle = cos_hour_angle.acos().to_degrees();
+        let noon = 720.0 - 4.0 * location.x() - equation_of_time;
+
+        Some(match self {
+            SolarEvent::Dawn | SolarEvent::Sunrise => noon - 4.0 * hour_angle,
+            SolarEvent::Sunset | SolarEvent::Dusk => noon + 4.0 * hour_angle,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn london_midsummer() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let london = Point::new(-0.1278, 51.5074);
+
+        // 04:43 and 21:21 BST, being UTC+1
+        let sunrise = SolarEvent::Sunrise.utc_minutes(date, london).unwrap();
+        let sunset = SolarEvent::Sunset.utc_minutes(date, london).unwrap();
+
+        assert!(
+            (sunrise - (3.0 * 60.0 + 43.0)).abs() < 3.0,
+            "sunrise {sunrise}"
+        );
+        assert!(
+            (sunset - (20.0 * 60.0 + 21.0)).abs() < 3.0,
+            "sunset {sunset}"
+        );
+
+        let dawn = SolarEvent::Dawn.utc_minutes(date, london).unwrap();
+        assert!(dawn < sunrise);
+    }
+
+    #[test]
+    fn polar_night() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        let tromso = Point::new(18.9553, 69.6492);
+
+        assert_eq!(SolarEvent::Sunrise.utc_minutes(date, tromso), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::osm::primitives::TransportMode;
 use strum::{AsRefStr, Display, EnumString};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr)]
@@ -111,6 +112,31 @@ pub enum RoadClass {
 }
 
 impl RoadClass {
+    /// Determines if the road class is implicitly accessible to the transport
+    /// mode, in the absence of any explicit access tags on the way.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/OSM_tags_for_routing/Access_restrictions
+    pub fn permits(&self, mode: TransportMode) -> bool {
+        if !mode.is_land() {
+            return false;
+        }
+
+        match self {
+            // Motorways are reserved for motor vehicles.
+            RoadClass::Motorway | RoadClass::MotorwayLink => mode.is_motorized(),
+
+            // Busways are reserved for public service vehicles.
+            RoadClass::Busway | RoadClass::BusGuideway => {
+                mode.hierarchy().any(|mode| mode == TransportMode::Psv)
+            }
+
+            // Pedestrian streets are closed to vehicles.
+            RoadClass::Pedestrian => !mode.is_vehicle(),
+
+            _ => true,
+        }
+    }
+
     #[inline]
     pub const fn weighting(&self) -> u32 {
         match self {
@@ -1,3 +1,4 @@
+use crate::osm::speed_limit::ImplicitSpeed;
 use std::fmt;
 use std::num::NonZeroU16;
 
@@ -19,6 +20,8 @@ pub enum SpeedValue {
     Inherited,
     /// Walk speed (typically 5-6 km/h)
     Walk,
+    /// The default speed limit of a zone within a country, like `DE:urban`
+    Implicit(ImplicitSpeed),
 }
 
 impl SpeedValue {
@@ -32,6 +35,7 @@ impl SpeedValue {
             SpeedValue::Knots(speed) => {
                 Some(NonZeroU16::new(((speed.get() as f64) * 1.852) as u16)?)
             }
+            SpeedValue::Implicit(implicit) => implicit.speed()?.in_kmh(),
             // Non-transformative
             _ => None,
         }
@@ -75,6 +79,7 @@ impl fmt::Display for SpeedValue {
             SpeedValue::Variable => write!(f, "variable"),
             SpeedValue::Inherited => write!(f, "inherited"),
             SpeedValue::Walk => write!(f, "walk"),
+            SpeedValue::Implicit(implicit) => write!(f, "{}", implicit),
         }
     }
 }
@@ -0,0 +1,288 @@
+use crate::osm::primitives::TransportMode;
+use strum::{AsRefStr, Display, EnumString};
+
+/// The physical surface of a way, such as `surface=asphalt`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Surface {
+    // Paved surfaces
+    /// A paved surface, of no more specific material.
+    Paved,
+    Asphalt,
+    /// Asphalt with a coating of stones, also known as tar and chip.
+    Chipseal,
+    Concrete,
+    #[strum(serialize = "concrete:plates")]
+    ConcretePlates,
+    #[strum(serialize = "concrete:lanes")]
+    ConcreteLanes,
+    PavingStones,
+    #[strum(serialize = "paving_stones:lanes")]
+    PavingStonesLanes,
+    /// Hewn stones with a flattened top, often referred to as cobblestones.
+    Sett,
+    /// Natural, rounded stones, unhewn and uneven.
+    UnhewnCobblestone,
+    /// Either sett or unhewn cobblestone, of no more specific material.
+    Cobblestone,
+    Bricks,
+    Metal,
+    MetalGrid,
+    Wood,
+    Rubber,
+    Tiles,
+    GrassPaver,
+    SteppingStones,
+
+    // Unpaved surfaces
+    /// An unpaved surface, of no more specific material.
+    Unpaved,
+    /// A mixture of stones bound into a hard surface.
+    Compacted,
+    FineGravel,
+    Gravel,
+    Shells,
+    Rock,
+    Pebblestone,
+    Ground,
+    Dirt,
+    Earth,
+    Grass,
+    Mud,
+    Sand,
+    Woodchips,
+    Snow,
+    Ice,
+    Salt,
+    Clay,
+}
+
+impl Surface {
+    /// If the surface is paved, being bound into a solid covering.
+    pub fn is_paved(&self) -> bool {
+        matches!(
+            self,
+            Self::Paved
+                | Self::Asphalt
+                | Self::Chipseal
+                | Self::Concrete
+                | Self::ConcretePlates
+                | Self::ConcreteLanes
+                | Self::PavingStones
+                | Self::PavingStonesLanes
+                | Self::Sett
+                | Self::UnhewnCobblestone
+                | Self::Cobblestone
+                | Self::Bricks
+                | Self::Metal
+                | Self::MetalGrid
+                | Self::Wood
+                | Self::Rubber
+                | Self::Tiles
+                | Self::GrassPaver
+                | Self::SteppingStones
+        )
+    }
+
+    /// The roughness of the surface to wheeled vehicles, from
+    /// zero for a smooth paved surface to three for a loose one.
+    fn roughness(&self) -> u8 {
+        match self {
+            Self::Paved
+            | Self::Asphalt
+            | Self::Concrete
+            | Self::ConcretePlates
+            | Self::PavingStones
+            | Self::Metal
+            | Self::Wood
+            | Self::Rubber
+            | Self::Tiles => 0,
+
+            Self::Chipseal
+            | Self::ConcreteLanes
+            | Self::PavingStonesLanes
+            | Self::Sett
+            | Self::Bricks
+            | Self::MetalGrid
+            | Self::Compacted
+            | Self::FineGravel => 1,
+
+            Self::UnhewnCobblestone
+            | Self::Cobblestone
+            | Self::GrassPaver
+            | Self::SteppingStones
+            | Self::Unpaved
+            | Self::Gravel
+            | Self::Shells
+            | Self::Rock
+            | Self::Pebblestone
+            | Self::Ground
+            | Self::Dirt
+            | Self::Earth
+            | Self::Clay => 2,
+
+            Self::Grass
+            | Self::Mud
+            | Self::Sand
+            | Self::Woodchips
+            | Self::Snow
+            | Self::Ice
+            | Self::Salt => 3,
+        }
+    }
+
+    /// The multiplicative penalty of travelling upon the surface in the transport mode.
+    ///
+    /// Non-motorized vehicles, such as bicycles, are penalised most by rough surfaces,
+    /// whereas those travelling on foot are unaffected.
+    pub fn penalty(&self, mode: TransportMode) -> f64 {
+        if !mode.is_vehicle() {
+            return 1.0;
+        }
+
+        let penalties = match mode {
+            // Mountain bikes are built for rough surfaces.
+            TransportMode::Mtb => [1.0, 1.0, 1.2, 1.5],
+            _ if mode.is_motorized() => [1.0, 1.1, 1.5, 3.0],
+            _ => [1.0, 1.3, 2.0, 4.0],
+        };
+
+        penalties[self.roughness() as usize]
+    }
+}
+
+/// The physical usability of a way for wheeled vehicles, such as `smoothness=bad`,
+/// ordered from the smoothest to the least usable.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:smoothness
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumString, AsRefStr,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Smoothness {
+    /// Usable by thin-wheeled vehicles, such as roller blades and skateboards.
+    Excellent,
+    /// Usable by narrow-wheeled vehicles, such as racing bicycles.
+    Good,
+    /// Usable by city bicycles, sports cars and wheelchairs.
+    Intermediate,
+    /// Usable by robust wheeled vehicles, such as trekking bicycles and normal cars.
+    Bad,
+    /// Usable by vehicles with a high clearance, such as light-duty off-road vehicles.
+    VeryBad,
+    /// Usable by off-road vehicles, such as heavy-duty off-road vehicles.
+    Horrible,
+    /// Usable by specialized off-road vehicles, such as tractors.
+    VeryHorrible,
+    /// Not usable by any wheeled vehicle.
+    Impassable,
+}
+
+impl Smoothness {
+    /// The least smooth way upon which the transport mode is usable,
+    /// or `None` should it not travel upon wheels.
+    fn limit(mode: TransportMode) -> Option<Smoothness> {
+        if !mode.is_vehicle() {
+            return None;
+        }
+
+        Some(match mode {
+            TransportMode::Mtb => Smoothness::Horrible,
+            _ => Smoothness::VeryBad,
+        })
+    }
+
+    /// Determines if the way is usable in the transport mode.
+    pub fn permits(&self, mode: TransportMode) -> bool {
+        Self::limit(mode).is_none_or(|limit| *self <= limit)
+    }
+
+    /// The multiplicative penalty of travelling upon the way in the transport mode,
+    /// given it is [permitted](Self::permits), increasing with each grade beyond
+    /// [`Smoothness::Intermediate`].
+    pub fn penalty(&self, mode: TransportMode) -> f64 {
+        if !mode.is_vehicle() {
+            return 1.0;
+        }
+
+        let grades = (*self as i32 - Smoothness::Intermediate as i32).max(0);
+        1.25_f64.powi(grades)
+    }
+}
+
+/// The firmness of the surface of a track, typically
+/// those of agricultural or forestry use, such as `tracktype=grade2`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:tracktype
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumString, AsRefStr,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum TrackType {
+    /// Solid, being paved or heavily compacted.
+    Grade1,
+    /// Solid but unpaved, such as gravel.
+    Grade2,
+    /// A mixture of hard and soft materials.
+    Grade3,
+    /// Mostly soft, such as soil, sand or grass.
+    Grade4,
+    /// Soft, lacking any hard material.
+    Grade5,
+}
+
+impl TrackType {
+    /// The surface typical of the track, should the way not declare one.
+    pub fn surface(&self) -> Surface {
+        match self {
+            TrackType::Grade1 => Surface::Paved,
+            TrackType::Grade2 => Surface::Compacted,
+            TrackType::Grade3 => Surface::Gravel,
+            TrackType::Grade4 => Surface::Ground,
+            TrackType::Grade5 => Surface::Grass,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(Surface::from_str("asphalt"), Ok(Surface::Asphalt));
+        assert_eq!(
+            Surface::from_str("concrete:plates"),
+            Ok(Surface::ConcretePlates)
+        );
+        assert_eq!(Smoothness::from_str("very_bad"), Ok(Smoothness::VeryBad));
+        assert_eq!(TrackType::from_str("grade3"), Ok(TrackType::Grade3));
+        assert!(Surface::from_str("lava").is_err());
+    }
+
+    #[test]
+    fn test_surface_penalty() {
+        assert!(Surface::Asphalt.is_paved() && !Surface::Gravel.is_paved());
+
+        let bicycle = Surface::Gravel.penalty(TransportMode::Bicycle);
+        assert!(bicycle > Surface::Gravel.penalty(TransportMode::Motorcar));
+        assert!(bicycle > Surface::Gravel.penalty(TransportMode::Mtb));
+        assert_eq!(Surface::Asphalt.penalty(TransportMode::Bicycle), 1.0);
+        assert_eq!(Surface::Mud.penalty(TransportMode::Foot), 1.0);
+    }
+
+    #[test]
+    fn test_smoothness() {
+        assert!(Smoothness::Bad.permits(TransportMode::Motorcar));
+        assert!(!Smoothness::Horrible.permits(TransportMode::Motorcar));
+        assert!(Smoothness::Horrible.permits(TransportMode::Mtb));
+        assert!(Smoothness::Impassable.permits(TransportMode::Foot));
+        assert!(!Smoothness::Impassable.permits(TransportMode::Bicycle));
+
+        assert_eq!(Smoothness::Good.penalty(TransportMode::Bicycle), 1.0);
+        assert!(Smoothness::VeryBad.penalty(TransportMode::Bicycle) > 1.5);
+    }
+}
@@ -323,6 +323,98 @@ impl TransportMode {
         )
     }
 
+    /// The more general transport mode this mode belongs to, if any.
+    ///
+    /// Follows the OSM access hierarchy, in which a restriction on a general
+    /// mode applies to all modes beneath it unless they are tagged explicitly.
+    /// For example, `motor_vehicle=no` applies to a `motorcar` unless the way
+    /// is also tagged with `motorcar=*`.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions
+    pub fn parent(&self) -> Option<Self> {
+        match self {
+            Self::LandAccess | Self::WaterAccess | Self::RailAccess => None,
+
+            Self::SkiNordic | Self::SkiAlpine | Self::SkiTelemark => Some(Self::Ski),
+            Self::Foot
+            | Self::Dog
+            | Self::Ski
+            | Self::InlineSkates
+            | Self::Horse
+            | Self::Portage
+            | Self::Vehicle => Some(Self::LandAccess),
+
+            Self::ElectricBicycle | Self::Mtb | Self::CargoBike => Some(Self::Bicycle),
+            Self::Caravan => Some(Self::Trailer),
+            Self::Bicycle
+            | Self::KickScooter
+            | Self::Carriage
+            | Self::CycleRickshaw
+            | Self::HandCart
+            | Self::Trailer
+            | Self::MotorVehicle => Some(Self::Vehicle),
+
+            Self::HgvArticulated | Self::Bdouble => Some(Self::Hgv),
+            Self::SchoolBus => Some(Self::Bus),
+            Self::Bus | Self::Taxi | Self::Minibus | Self::ShareTaxi => Some(Self::Psv),
+            Self::Motorcycle
+            | Self::Moped
+            | Self::SpeedPedelec
+            | Self::Mofa
+            | Self::SmallElectricVehicle
+            | Self::Motorcar
+            | Self::Motorhome
+            | Self::TouristBus
+            | Self::Coach
+            | Self::Goods
+            | Self::Hgv
+            | Self::Agricultural
+            | Self::AutoRickshaw
+            | Self::Nev
+            | Self::GolfCart
+            | Self::Microcar
+            | Self::Atv
+            | Self::Ohv
+            | Self::Snowmobile
+            | Self::Psv
+            | Self::Hov
+            | Self::Carpool
+            | Self::CarSharing
+            | Self::Emergency
+            | Self::Hazmat
+            | Self::Disabled => Some(Self::MotorVehicle),
+
+            Self::Swimming | Self::IceSkates | Self::Boat | Self::Ship => Some(Self::WaterAccess),
+            Self::Motorboat | Self::Sailboat | Self::Canoe => Some(Self::Boat),
+            Self::FishingVessel
+            | Self::Passenger
+            | Self::Cargo
+            | Self::Tanker
+            | Self::Container
+            | Self::Imdg
+            | Self::Isps
+            | Self::HazmatWater => Some(Self::Ship),
+            Self::Bulk => Some(Self::Cargo),
+            Self::TankerGas | Self::TankerOil | Self::TankerChemical | Self::TankerSinglehull => {
+                Some(Self::Tanker)
+            }
+
+            Self::Train | Self::Tram | Self::Metro => Some(Self::RailAccess),
+        }
+    }
+
+    /// Iterates from this transport mode through each of its parents,
+    /// from the most specific to the most general.
+    pub fn hierarchy(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(*self), Self::parent)
+    }
+
+    /// Check if this mode is a vehicle, and is therefore
+    /// subject to vehicle-specific rules such as `oneway=*`.
+    pub fn is_vehicle(&self) -> bool {
+        self.hierarchy().any(|mode| mode == Self::Vehicle)
+    }
+
     /// Check if this is a non-motorized vehicle
     pub fn is_non_motorized(&self) -> bool {
         matches!(
@@ -386,5 +478,38 @@ mod tests {
 
         assert!(!TransportMode::Foot.is_motorized());
         assert!(!TransportMode::Foot.is_non_motorized());
+
+        assert!(TransportMode::Bus.is_vehicle());
+        assert!(TransportMode::Bicycle.is_vehicle());
+        assert!(!TransportMode::Foot.is_vehicle());
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        assert_eq!(
+            TransportMode::Motorcar.hierarchy().collect::<Vec<_>>(),
+            vec![
+                TransportMode::Motorcar,
+                TransportMode::MotorVehicle,
+                TransportMode::Vehicle,
+                TransportMode::LandAccess
+            ]
+        );
+
+        assert_eq!(
+            TransportMode::Bus.hierarchy().collect::<Vec<_>>(),
+            vec![
+                TransportMode::Bus,
+                TransportMode::Psv,
+                TransportMode::MotorVehicle,
+                TransportMode::Vehicle,
+                TransportMode::LandAccess
+            ]
+        );
+
+        assert_eq!(
+            TransportMode::Foot.hierarchy().collect::<Vec<_>>(),
+            vec![TransportMode::Foot, TransportMode::LandAccess]
+        );
     }
 }
@@ -0,0 +1,350 @@
+//! Declarative extraction of values from the tags of an element, such that
+//! a custom [`Metadata`](crate::Metadata) type may describe each of its fields
+//! as a [`TagRule`], rather than hand-writing a parser for it.
+//!
+//! ```
+//! use routers_codec::osm::query::TagRule;
+//! use routers_codec::osm::primitives::Surface;
+//!
+//! // `lit=yes`, falling back to unlit.
+//! let lit = TagRule::values("lit", [("yes", true), ("24/7", true), ("no", false)]).default(false);
+//!
+//! // `surface=*`, or `surface:forward=*` in a single direction.
+//! let surface = TagRule::<Surface>::new("surface").directional();
+//!
+//! // `maxweight=*`, `maxweight:forward=*` or `maxweight:conditional=* @ (...)`.
+//! let max_weight = TagRule::<f64>::new("maxweight").directional().conditional();
+//! ```
+
+use crate::osm::primitives::{Condition, Directionality};
+use crate::osm::{Tags, TraversalConditions};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const CONDITIONAL: &str = "conditional";
+
+type ValueParser<T> = Arc<dyn Fn(&str) -> Option<T> + Send + Sync>;
+
+/// A value which only applies under a [`Condition`], like `maxweight:conditional=3.5 @ (wet)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalValue<T> {
+    /// The direction the value applies in, or both should it be `None`.
+    pub direction: Option<Directionality>,
+    pub value: T,
+    pub condition: Condition,
+}
+
+/// The values extracted by a [`TagRule`] from the tags of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagValue<T> {
+    /// The value of the plain key, or the default of the rule.
+    pub value: Option<T>,
+    /// The value of the `key:forward` key, should the rule be [directional](TagRule::directional).
+    pub forward: Option<T>,
+    /// The value of the `key:backward` key, should the rule be [directional](TagRule::directional).
+    pub backward: Option<T>,
+    /// The values of the `key:conditional` keys, in order of declaration,
+    /// should the rule be [conditional](TagRule::conditional).
+    pub conditional: Vec<ConditionalValue<T>>,
+}
+
+impl<T> Default for TagValue<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            forward: None,
+            backward: None,
+            conditional: vec![],
+        }
+    }
+}
+
+impl<T> TagValue<T> {
+    /// The value in the direction of travel, where a directional value replaces the plain value.
+    pub fn toward(&self, direction: Directionality) -> Option<&T> {
+        match direction {
+            Directionality::Forward => self.forward.as_ref(),
+            Directionality::Backward => self.backward.as_ref(),
+            _ => None,
+        }
+        .or(self.value.as_ref())
+    }
+
+    /// The value under the traversal conditions, in their direction of travel, where the
+    /// last declared applicable conditional value replaces the [directional](Self::toward) value.
+    pub fn under(&self, conditions: &TraversalConditions) -> Option<&T> {
+        self.conditional
+            .iter()
+            .rev()
+            .find(|conditional| {
+                conditional
+                    .direction
+                    .is_none_or(|direction| direction == conditions.directionality)
+                    && conditional.condition.applies(conditions) == Some(true)
+            })
+            .map(|conditional| &conditional.value)
+            .or_else(|| self.toward(conditions.directionality))
+    }
+}
+
+/// A rule extracting a value from one or more keys, such as `maxspeed`, alongside
+/// the `:forward`, `:backward` and `:conditional` forms of the keys should it be
+/// [directional](Self::directional) or [conditional](Self::conditional).
+///
+/// Values are trimmed before being parsed, and those which
+/// cannot be parsed are treated as though they were absent.
+#[derive(Clone)]
+pub struct TagRule<T> {
+    keys: Vec<String>,
+    parser: ValueParser<T>,
+    default: Option<T>,
+    directional: bool,
+    conditional: bool,
+}
+
+impl<T> fmt::Debug for TagRule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TagRule")
+            .field("keys", &self.keys)
+            .field("directional", &self.directional)
+            .field("conditional", &self.conditional)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> TagRule<T>
+where
+    T: FromStr + 'static,
+{
+    /// A rule parsing the value of the key, like a [`Surface`](crate::osm::primitives::Surface)
+    /// from `surface=asphalt`.
+    pub fn new(key: &str) -> Self {
+        Self::with(key, |value| value.parse().ok())
+    }
+}
+
+impl<T> TagRule<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// A rule mapping each of the listed values of the key, such as `yes` and `no`,
+    /// onto its value. Values which are not listed are treated as though they were absent.
+    pub fn values<'a>(key: &str, values: impl IntoIterator<Item = (&'a str, T)>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|(value, mapped)| (value.to_string(), mapped))
+            .collect::<Vec<_>>();
+
+        Self::with(key, move |value| {
+            values
+                .iter()
+                .find(|(candidate, _)| candidate == value)
+                .map(|(_, mapped)| mapped.clone())
+        })
+    }
+}
+
+impl<T> TagRule<T> {
+    /// A rule parsing the value of the key with the given function.
+    pub fn with(key: &str, parser: impl Fn(&str) -> Option<T> + Send + Sync + 'static) -> Self {
+        Self {
+            keys: vec![key.to_string()],
+            parser: Arc::new(parser),
+            default: None,
+            directional: false,
+            conditional: false,
+        }
+    }
+
+    /// Reads the key should the keys before it be absent, such as `maxspeed:advisory`.
+    pub fn or_key(mut self, key: &str) -> Self {
+        self.keys.push(key.to_string());
+        self
+    }
+
+    /// The value should none of the keys be present.
+    pub fn default(self, default: T) -> Self {
+        Self {
+            default: Some(default),
+            ..self
+        }
+    }
+
+    /// Reads the `key:forward` and `key:backward` forms of the keys.
+    pub fn directional(self) -> Self {
+        Self {
+            directional: true,
+            ..self
+        }
+    }
+
+    /// Reads the `key:conditional` form of the keys, alongside
+    /// `key:forward:conditional` should the rule be [directional](Self::directional).
+    pub fn conditional(self) -> Self {
+        Self {
+            conditional: true,
+            ..self
+        }
+    }
+
+    /// The parsed value of the first present key with the given suffix.
+    fn first(&self, tags: &Tags, suffix: Option<&str>) -> Option<T> {
+        self.keys.iter().find_map(|key| {
+            let value = match suffix {
+                Some(suffix) => tags.get(&format!("{key}:{suffix}")),
+                None => tags.get(key),
+            }?;
+
+            (self.parser)(value.trim())
+        })
+    }
+
+    /// The conditional values of the first present key with the given direction.
+    fn conditional_values(
+        &self,
+        tags: &Tags,
+        direction: Option<Directionality>,
+    ) -> Vec<ConditionalValue<T>> {
+        self.keys
+            .iter()
+            .find_map(|key| match direction {
+                Some(direction) => tags.get(&format!("{key}:{direction}:{CONDITIONAL}")),
+                None => tags.get(&format!("{key}:{CONDITIONAL}")),
+            })
+            .map(|value| {
+                Condition::parse_conditional(value)
+                    .into_iter()
+                    .filter_map(|(value, condition)| {
+                        Some(ConditionalValue {
+                            direction,
+                            value: (self.parser)(value)?,
+                            condition,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<T> TagRule<T>
+where
+    T: Clone,
+{
+    /// The value of the plain keys, or the default of the rule should none be present.
+    pub fn value(&self, tags: &Tags) -> Option<T> {
+        self.first(tags, None).or_else(|| self.default.clone())
+    }
+
+    /// Extracts every value of the rule from the tags.
+    pub fn extract(&self, tags: &Tags) -> TagValue<T> {
+        let directed = |direction: Directionality| {
+            self.directional
+                .then(|| self.first(tags, Some(direction.as_ref())))
+                .flatten()
+        };
+
+        let mut conditional = vec![];
+        if self.conditional {
+            conditional.extend(self.conditional_values(tags, None));
+
+            if self.directional {
+                for direction in [Directionality::Forward, Directionality::Backward] {
+                    conditional.extend(self.conditional_values(tags, Some(direction)));
+                }
+            }
+        }
+
+        TagValue {
+            value: self.value(tags),
+            forward: directed(Directionality::Forward),
+            backward: directed(Directionality::Backward),
+            conditional,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::TripContext;
+    use crate::osm::primitives::condition::RoadCondition;
+    use crate::osm::primitives::{Surface, TransportMode};
+
+    #[test]
+    fn test_values() {
+        let lit = TagRule::values("lit", [("yes", true), ("no", false)]).default(false);
+
+        assert_eq!(lit.value(&Tags::from_iter([("lit", "yes")])), Some(true));
+        assert_eq!(lit.value(&Tags::from_iter([("lit", "no")])), Some(false));
+        assert_eq!(lit.value(&Tags::default()), Some(false));
+
+        // Values which are not mapped are treated as absent.
+        let unmapped = TagRule::values("lit", [("yes", true)]);
+        assert_eq!(
+            unmapped.value(&Tags::from_iter([("lit", "sunset-sunrise")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fallback_keys() {
+        let advisory = TagRule::<u16>::new("maxspeed").or_key("maxspeed:advisory");
+
+        assert_eq!(
+            advisory.value(&Tags::from_iter([("maxspeed:advisory", "40")])),
+            Some(40)
+        );
+        assert_eq!(
+            advisory.value(&Tags::from_iter([
+                ("maxspeed", "50"),
+                ("maxspeed:advisory", "40")
+            ])),
+            Some(50)
+        );
+    }
+
+    #[test]
+    fn test_directional() {
+        let surface = TagRule::<Surface>::new("surface").directional();
+        let value = surface.extract(&Tags::from_iter([
+            ("surface", "asphalt"),
+            ("surface:backward", "gravel"),
+        ]));
+
+        assert_eq!(
+            value.toward(Directionality::Forward),
+            Some(&Surface::Asphalt)
+        );
+        assert_eq!(
+            value.toward(Directionality::Backward),
+            Some(&Surface::Gravel)
+        );
+
+        // Directional keys are ignored unless requested.
+        let plain = TagRule::<Surface>::new("surface")
+            .extract(&Tags::from_iter([("surface:forward", "sett")]));
+        assert_eq!(plain, TagValue::default());
+    }
+
+    #[test]
+    fn test_conditional() {
+        let weight = TagRule::<f64>::new("maxweight").directional().conditional();
+        let value = weight.extract(&Tags::from_iter([
+            ("maxweight", "7.5"),
+            ("maxweight:conditional", "3.5 @ wet"),
+            ("maxweight:forward:conditional", "12 @ (Mo-Fr)"),
+        ]));
+
+        assert_eq!(value.conditional.len(), 2);
+
+        let dry = TraversalConditions::new(TransportMode::Hgv);
+        assert_eq!(value.under(&dry), Some(&7.5));
+
+        let wet = dry
+            .clone()
+            .trip(TripContext::default().road([RoadCondition::Wet]));
+        assert_eq!(value.under(&wet), Some(&3.5));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::osm::element::{TagString, Tags};
+use crate::osm::primitives::{Speed, SpeedValue};
 use crate::osm::speed_limit::limit::{SpeedLimitEntry, SpeedLimitVariant};
-use crate::osm::speed_limit::{PossiblyConditionalSpeedLimit, SpeedLimitExt};
+use crate::osm::speed_limit::restriction::Restriction;
+use crate::osm::speed_limit::{ImplicitSpeed, PossiblyConditionalSpeedLimit, SpeedLimitExt};
 use crate::osm::{Parser, TraversalConditions};
 use std::ops::Deref;
 
@@ -15,6 +17,35 @@ impl Deref for SpeedLimitCollection {
     }
 }
 
+impl SpeedLimitCollection {
+    /// The speed limit, in kilometers per hour, which applies regardless of any condition.
+    pub fn unconditional(&self, conditions: &TraversalConditions) -> Option<Speed> {
+        self.relevant_limits(conditions.clone())
+            .into_iter()
+            .find(|limit| limit.condition.is_none())
+            .and_then(|limit| limit.speed.in_kmh())
+    }
+
+    /// The speed limit, in kilometers per hour, under the traversal conditions,
+    /// where the last declared applicable conditional limit replaces the
+    /// [unconditional](Self::unconditional) limit.
+    pub fn limit_under(&self, conditions: &TraversalConditions) -> Option<Speed> {
+        let limits = self.relevant_limits(conditions.clone());
+
+        limits
+            .iter()
+            .rev()
+            .find(|limit| {
+                limit
+                    .condition
+                    .as_ref()
+                    .is_some_and(|condition| condition.applies(conditions) == Some(true))
+            })
+            .or_else(|| limits.iter().find(|limit| limit.condition.is_none()))
+            .and_then(|limit| limit.speed.in_kmh())
+    }
+}
+
 impl SpeedLimitExt for SpeedLimitCollection {
     fn relevant_limits(
         &self,
@@ -38,9 +69,10 @@ impl SpeedLimitExt for SpeedLimitCollection {
             })
             .filter_map(|SpeedLimitEntry { limit, .. }| match limit {
                 SpeedLimitVariant::Blanket(blanket) => Some(blanket),
+                // Lanes are numbered from one, in the direction of travel.
                 SpeedLimitVariant::PerLane(per_lane) => per_lane
                     .0
-                    .get(traversal_conditions.lane?.get() as usize)
+                    .get(traversal_conditions.lane?.get() as usize - 1)
                     .cloned()
                     .and_then(|x| x),
             })
@@ -51,12 +83,41 @@ impl SpeedLimitExt for SpeedLimitCollection {
 impl Parser for SpeedLimitCollection {
     fn parse(tags: &Tags) -> Option<Self> {
         // Standard structure follows:
-        let known_limits = tags
+        let mut known_limits = tags
             .iter()
             .filter(|(key, _)| key.starts_with(TagString::MAX_SPEED))
+            .filter(|(key, _)| key.as_str() != TagString::MAX_SPEED_TYPE)
             .filter_map(|(l, v)| SpeedLimitEntry::parse_tag(l, v))
             .collect::<Vec<_>>();
 
+        // Without a signposted limit, the implicit limit of the zone applies,
+        // like `maxspeed:type=DE:urban` or `source:maxspeed=DE:urban`.
+        let signposted = known_limits.iter().any(|entry| {
+            entry.restriction.transport_mode.is_none()
+                && entry.restriction.directionality.is_none()
+                && matches!(&entry.limit, SpeedLimitVariant::Blanket(limit) if limit.condition.is_none())
+        });
+
+        if !signposted {
+            let implicit = [
+                TagString::MAX_SPEED_TYPE,
+                TagString::SOURCE_MAX_SPEED,
+                TagString::ZONE_MAX_SPEED,
+            ]
+            .into_iter()
+            .find_map(|key| tags.r#as::<ImplicitSpeed>(key));
+
+            if let Some(implicit) = implicit {
+                known_limits.push(SpeedLimitEntry {
+                    restriction: Restriction::parse(TagString::MAX_SPEED),
+                    limit: SpeedLimitVariant::Blanket(PossiblyConditionalSpeedLimit {
+                        speed: SpeedValue::Implicit(implicit),
+                        condition: None,
+                    }),
+                });
+            }
+        }
+
         if known_limits.is_empty() {
             return None;
         }
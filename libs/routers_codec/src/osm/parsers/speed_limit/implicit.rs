@@ -0,0 +1,439 @@
+//! Implicit speed limits, being the default limits of a country
+//! which apply where no limit is signposted, such as `maxspeed=DE:urban`.
+//!
+//! Source: https://wiki.openstreetmap.org/wiki/Default_speed_limits
+
+use crate::osm::primitives::{CountryCode, RoadClass, Speed, SpeedValue};
+use std::fmt;
+use std::num::NonZeroU16;
+use std::str::FromStr;
+
+/// The zone, or class of road, to which an implicit speed limit applies.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:maxspeed:type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpeedZone {
+    /// Within a built-up area.
+    Urban,
+    /// Outside of a built-up area.
+    Rural,
+    /// An expressway, or a trunk road outside of a built-up area.
+    Trunk,
+    Motorway,
+    LivingStreet,
+    /// A road designated for bicycles, such as a German `Fahrradstraße`.
+    BicycleRoad,
+    /// The national speed limit upon a single carriageway, in Great Britain.
+    NslSingle,
+    /// The national speed limit upon a dual carriageway, in Great Britain.
+    NslDual,
+    /// The restricted national speed limit, being that of lit roads, in Great Britain.
+    NslRestricted,
+    /// A zone of the signposted speed, like `DE:zone30`, in the unit of the country.
+    Zone(Speed),
+}
+
+impl FromStr for SpeedZone {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "urban" => SpeedZone::Urban,
+            "rural" => SpeedZone::Rural,
+            "trunk" => SpeedZone::Trunk,
+            "motorway" => SpeedZone::Motorway,
+            "living_street" => SpeedZone::LivingStreet,
+            "bicycle_road" => SpeedZone::BicycleRoad,
+            "nsl_single" => SpeedZone::NslSingle,
+            "nsl_dual" => SpeedZone::NslDual,
+            "nsl_restricted" => SpeedZone::NslRestricted,
+            _ => {
+                // Both `zone30` and `zone:30` are in use.
+                let speed = value.strip_prefix("zone").ok_or(())?;
+                let speed = speed.strip_prefix(':').unwrap_or(speed);
+                SpeedZone::Zone(speed.parse().map_err(|_| ())?)
+            }
+        })
+    }
+}
+
+impl fmt::Display for SpeedZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeedZone::Urban => write!(f, "urban"),
+            SpeedZone::Rural => write!(f, "rural"),
+            SpeedZone::Trunk => write!(f, "trunk"),
+            SpeedZone::Motorway => write!(f, "motorway"),
+            SpeedZone::LivingStreet => write!(f, "living_street"),
+            SpeedZone::BicycleRoad => write!(f, "bicycle_road"),
+            SpeedZone::NslSingle => write!(f, "nsl_single"),
+            SpeedZone::NslDual => write!(f, "nsl_dual"),
+            SpeedZone::NslRestricted => write!(f, "nsl_restricted"),
+            SpeedZone::Zone(speed) => write!(f, "zone{}", speed),
+        }
+    }
+}
+
+/// The default speed limit of a zone within a country, like `DE:urban`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImplicitSpeed {
+    pub country: CountryCode,
+    pub zone: SpeedZone,
+}
+
+const fn kmh(speed: u16) -> SpeedValue {
+    SpeedValue::Kmh(NonZeroU16::new(speed).unwrap())
+}
+
+const fn mph(speed: u16) -> SpeedValue {
+    SpeedValue::Mph(NonZeroU16::new(speed).unwrap())
+}
+
+/// The default speed limits of each country, by zone.
+const LIMITS: &[(CountryCode, &[(SpeedZone, SpeedValue)])] = &[
+    (
+        CountryCode::new(b"DE"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(100)),
+            (SpeedZone::Motorway, SpeedValue::None),
+            (SpeedZone::LivingStreet, SpeedValue::Walk),
+            (SpeedZone::BicycleRoad, kmh(30)),
+        ],
+    ),
+    (
+        CountryCode::new(b"AT"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(100)),
+            (SpeedZone::Motorway, kmh(130)),
+            (SpeedZone::LivingStreet, SpeedValue::Walk),
+        ],
+    ),
+    (
+        CountryCode::new(b"CH"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Trunk, kmh(100)),
+            (SpeedZone::Motorway, kmh(120)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"FR"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"IT"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"ES"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(100)),
+            (SpeedZone::Motorway, kmh(120)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"PT"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(100)),
+            (SpeedZone::Motorway, kmh(120)),
+        ],
+    ),
+    (
+        CountryCode::new(b"NL"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Trunk, kmh(100)),
+            (SpeedZone::Motorway, kmh(100)),
+            (SpeedZone::LivingStreet, kmh(15)),
+        ],
+    ),
+    (
+        CountryCode::new(b"BE"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(70)),
+            (SpeedZone::Motorway, kmh(120)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"LU"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"DK"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"SE"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(70)),
+            (SpeedZone::Motorway, kmh(110)),
+        ],
+    ),
+    (
+        CountryCode::new(b"NO"),
+        &[(SpeedZone::Urban, kmh(50)), (SpeedZone::Rural, kmh(80))],
+    ),
+    (
+        CountryCode::new(b"FI"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Motorway, kmh(120)),
+        ],
+    ),
+    (
+        CountryCode::new(b"PL"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(120)),
+            (SpeedZone::Motorway, kmh(140)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"CZ"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"SK"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"HU"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"SI"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+            (SpeedZone::LivingStreet, kmh(10)),
+        ],
+    ),
+    (
+        CountryCode::new(b"HR"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"GR"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Trunk, kmh(110)),
+            (SpeedZone::Motorway, kmh(130)),
+        ],
+    ),
+    (
+        CountryCode::new(b"GB"),
+        &[
+            (SpeedZone::Urban, mph(30)),
+            (SpeedZone::NslRestricted, mph(30)),
+            (SpeedZone::Rural, mph(60)),
+            (SpeedZone::NslSingle, mph(60)),
+            (SpeedZone::NslDual, mph(70)),
+            (SpeedZone::Motorway, mph(70)),
+        ],
+    ),
+    (
+        CountryCode::new(b"IE"),
+        &[
+            (SpeedZone::Urban, kmh(50)),
+            (SpeedZone::Rural, kmh(80)),
+            (SpeedZone::Trunk, kmh(100)),
+            (SpeedZone::Motorway, kmh(120)),
+        ],
+    ),
+    (
+        CountryCode::new(b"RU"),
+        &[
+            (SpeedZone::Urban, kmh(60)),
+            (SpeedZone::Rural, kmh(90)),
+            (SpeedZone::Motorway, kmh(110)),
+            (SpeedZone::LivingStreet, kmh(20)),
+        ],
+    ),
+    (
+        CountryCode::new(b"AU"),
+        &[(SpeedZone::Urban, kmh(50)), (SpeedZone::Rural, kmh(100))],
+    ),
+    (
+        CountryCode::new(b"NZ"),
+        &[(SpeedZone::Urban, kmh(50)), (SpeedZone::Rural, kmh(100))],
+    ),
+];
+
+/// The countries whose signposted speeds are in miles per hour.
+const MPH: &[CountryCode] = &[CountryCode::new(b"GB"), CountryCode::new(b"US")];
+
+impl ImplicitSpeed {
+    pub fn new(country: CountryCode, zone: SpeedZone) -> Self {
+        Self { country, zone }
+    }
+
+    /// The implicit speed limit of the class of road within the country,
+    /// assuming residential and service roads lie within a built-up area.
+    pub fn for_road(country: CountryCode, road_class: RoadClass) -> Self {
+        let zone = match road_class {
+            RoadClass::Motorway | RoadClass::MotorwayLink => SpeedZone::Motorway,
+            RoadClass::Trunk | RoadClass::TrunkLink => SpeedZone::Trunk,
+            RoadClass::LivingStreet => SpeedZone::LivingStreet,
+            RoadClass::Residential | RoadClass::Service => SpeedZone::Urban,
+            _ => SpeedZone::Rural,
+        };
+
+        Self::new(country, zone)
+    }
+
+    /// The speed limit of the zone, should the country's default be known.
+    ///
+    /// A trunk road, or British national speed limit, without a specific default
+    /// of its own falls back to the rural default of the country.
+    pub fn speed(&self) -> Option<SpeedValue> {
+        if let SpeedZone::Zone(speed) = self.zone {
+            return Some(if MPH.contains(&self.country) {
+                SpeedValue::Mph(speed)
+            } else {
+                SpeedValue::Kmh(speed)
+            });
+        }
+
+        let (_, limits) = LIMITS
+            .iter()
+            .find(|(country, _)| *country == self.country)?;
+        let limit = |zone: SpeedZone| {
+            limits
+                .iter()
+                .find(|(candidate, _)| *candidate == zone)
+                .map(|(_, speed)| *speed)
+        };
+
+        limit(self.zone).or_else(|| match self.zone {
+            SpeedZone::Trunk | SpeedZone::NslSingle | SpeedZone::NslDual => limit(SpeedZone::Rural),
+            SpeedZone::NslRestricted => limit(SpeedZone::Urban),
+            _ => None,
+        })
+    }
+}
+
+impl FromStr for ImplicitSpeed {
+    type Err = ();
+
+    /// Parses an implicit speed limit, like `DE:urban` or `GB:nsl_single`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (country, zone) = value.trim().split_once(':').ok_or(())?;
+        Ok(Self::new(country.parse()?, zone.parse()?))
+    }
+}
+
+impl fmt::Display for ImplicitSpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.country, self.zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_kmh(value: &str) -> Option<u16> {
+        let implicit = value.parse::<ImplicitSpeed>().ok()?;
+        implicit.speed()?.in_kmh().map(|speed| speed.get())
+    }
+
+    #[test]
+    fn test_parsing() {
+        let implicit = "DE:urban".parse::<ImplicitSpeed>().unwrap();
+        assert_eq!(implicit.country, CountryCode::new(b"DE"));
+        assert_eq!(implicit.zone, SpeedZone::Urban);
+        assert_eq!(implicit.to_string(), "DE:urban");
+
+        assert_eq!(
+            "DE:zone:30".parse::<ImplicitSpeed>().map(|i| i.zone),
+            Ok(SpeedZone::Zone(NonZeroU16::new(30).unwrap()))
+        );
+        assert!("urban".parse::<ImplicitSpeed>().is_err());
+        assert!("DE:sign".parse::<ImplicitSpeed>().is_err());
+    }
+
+    #[test]
+    fn test_speeds() {
+        assert_eq!(in_kmh("DE:urban"), Some(50));
+        assert_eq!(in_kmh("AU:rural"), Some(100));
+        assert_eq!(in_kmh("RU:living_street"), Some(20));
+        assert_eq!(in_kmh("GB:nsl_single"), Some(96));
+        assert_eq!(in_kmh("GB:zone20"), Some(32));
+        assert_eq!(in_kmh("DE:zone30"), Some(30));
+
+        // Falling back upon the rural default
+        assert_eq!(in_kmh("DE:trunk"), Some(100));
+
+        // Unrestricted, or of unknown country
+        assert_eq!(in_kmh("DE:motorway"), None);
+        assert_eq!(in_kmh("XX:urban"), None);
+    }
+
+    #[test]
+    fn test_road_class() {
+        let country = CountryCode::new(b"FR");
+
+        let speed = |class| ImplicitSpeed::for_road(country, class).speed();
+        assert!(matches!(speed(RoadClass::Motorway), Some(SpeedValue::Kmh(s)) if s.get() == 130));
+        assert!(matches!(speed(RoadClass::Residential), Some(SpeedValue::Kmh(s)) if s.get() == 50));
+        assert!(matches!(speed(RoadClass::Secondary), Some(SpeedValue::Kmh(s)) if s.get() == 80));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::osm::element::TagString;
 use crate::osm::primitives::*;
+use crate::osm::speed_limit::ImplicitSpeed;
 use crate::osm::speed_limit::restriction::Restriction;
 use crate::osm::speed_limit::subtypes;
 
@@ -43,7 +44,22 @@ impl PossiblyConditionalSpeedLimit {
     /// It extracts the speed limit and passes it to the `Speed` structure,
     /// attempting to parse the content inside. None is returned if this
     /// operation fails.
+    ///
+    /// Values without a number, such as `none` or the implicit
+    /// limit of a zone like `DE:urban`, are recognised by name.
     fn parse_speed(value: &str) -> Option<SpeedValue> {
+        let named = value.split('@').next().unwrap_or_default().trim();
+        match named {
+            "none" => return Some(SpeedValue::None),
+            "walk" => return Some(SpeedValue::Walk),
+            "signals" | "variable" => return Some(SpeedValue::Variable),
+            _ => {}
+        }
+
+        if let Ok(implicit) = named.parse::<ImplicitSpeed>() {
+            return Some(SpeedValue::Implicit(implicit));
+        }
+
         let re = regex::Regex::new(subtypes::VALUE_PATTERN).ok()?;
         let captures = re.captures(value)?;
 
@@ -1,4 +1,5 @@
 pub mod collection;
+pub mod implicit;
 pub mod limit;
 pub mod restriction;
 #[cfg(test)]
@@ -8,6 +9,7 @@ use crate::osm::element::Tags;
 use crate::osm::speed_limit::limit::PossiblyConditionalSpeedLimit;
 use crate::osm::{Parser, TraversalConditions};
 pub use collection::SpeedLimitCollection;
+pub use implicit::{ImplicitSpeed, SpeedZone};
 
 pub(super) mod subtypes {
     pub const LANES: &str = "lanes";
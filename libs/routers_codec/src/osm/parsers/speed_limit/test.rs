@@ -14,10 +14,7 @@ use std::num::NonZeroU16;
 
 #[cfg(test)]
 fn parse_singular(key: &str, value: &str) -> SpeedLimitEntry {
-    let mut tags = HashMap::new();
-    tags.insert(TagString::from(key), TagString::from(value));
-
-    let as_tags = Tags::new(tags);
+    let as_tags = Tags::from_iter([(key, value)]);
     let limit = SpeedLimitCollection::parse(&as_tags);
     assert!(limit.is_some(), "must parse successfully");
 
@@ -144,11 +141,11 @@ fn test_parsing_conditional() {
                 start: Time {
                     hour: 19,
                     minute: 0,
-                },
+                }.into(),
                 end: Time {
                     hour: 6,
                     minute: 0,
-                }
+                }.into()
             }
         })
     }));
@@ -269,8 +266,8 @@ fn test_parsing_lanes_conditional() {
             lane_one_condition.unwrap().clone().condition_type,
             ConditionType::TimeDate(date) if {
                 date.opening_hours.rules[0].times[0] == TimeRange {
-                    start: Time { hour: 22, minute: 0 },
-                    end: Time { hour: 6, minute: 0}
+                    start: Time { hour: 22, minute: 0 }.into(),
+                    end: Time { hour: 6, minute: 0}.into()
                 }
             }
         ));
@@ -287,12 +284,12 @@ fn test_parsing_lanes_conditional() {
 
                 rule.times == vec![
                     TimeRange {
-                        start: Time { hour: 7, minute: 0 },
-                        end: Time { hour: 9, minute: 0 },
+                        start: Time { hour: 7, minute: 0 }.into(),
+                        end: Time { hour: 9, minute: 0 }.into(),
                     },
                     TimeRange {
-                        start: Time { hour: 16, minute: 0},
-                        end: Time { hour: 20, minute: 0},
+                        start: Time { hour: 16, minute: 0}.into(),
+                        end: Time { hour: 20, minute: 0}.into(),
                     }
                 ] && rule.weekdays == Some(WeekdayRange::Range(Weekday::Monday, Weekday::Friday))
             }
@@ -308,3 +305,78 @@ fn test_parsing_lanes_conditional() {
         );
     }
 }
+
+#[test]
+fn test_relevant_lane_limits() {
+    use crate::osm::TraversalConditions;
+    use crate::osm::speed_limit::SpeedLimitExt;
+    use std::num::NonZeroU8;
+
+    let tags = Tags::new(HashMap::from([
+        (
+            TagString::from("maxspeed:lanes:forward"),
+            TagString::from("100|80"),
+        ),
+        (
+            TagString::from("maxspeed:lanes:backward"),
+            TagString::from("60"),
+        ),
+    ]));
+
+    let limits = SpeedLimitCollection::parse(&tags).unwrap();
+    let speed = |direction, lane| {
+        let conditions = TraversalConditions::default()
+            .toward(direction)
+            .lane(NonZeroU8::new(lane).unwrap());
+
+        limits
+            .relevant_limits(conditions)
+            .first()
+            .and_then(|limit| limit.speed.in_kmh())
+            .map(|speed| speed.get())
+    };
+
+    assert_eq!(speed(Directionality::Forward, 1), Some(100));
+    assert_eq!(speed(Directionality::Forward, 2), Some(80));
+    assert_eq!(speed(Directionality::Forward, 3), None);
+    assert_eq!(speed(Directionality::Backward, 1), Some(60));
+}
+
+#[test]
+fn test_implicit_speed_limits() {
+    let in_kmh = |pairs: &[(&str, &str)]| {
+        let tags = pairs.iter().copied().collect();
+
+        SpeedLimitCollection::parse(&tags)
+            .and_then(|limits| match &limits.0[..] {
+                [entry] => match &entry.limit {
+                    Blanket(limit) => limit.speed.in_kmh(),
+                    PerLane(_) => None,
+                },
+                _ => None,
+            })
+            .map(|speed| speed.get())
+    };
+
+    assert_eq!(in_kmh(&[("maxspeed", "DE:urban")]), Some(50));
+    assert_eq!(in_kmh(&[("maxspeed", "AU:rural")]), Some(100));
+    assert_eq!(in_kmh(&[("maxspeed", "RU:living_street")]), Some(20));
+    assert_eq!(in_kmh(&[("maxspeed:type", "GB:nsl_dual")]), Some(112));
+    assert_eq!(in_kmh(&[("source:maxspeed", "DE:rural")]), Some(100));
+
+    // The signposted limit is preferred over that of the zone.
+    assert_eq!(
+        in_kmh(&[("maxspeed", "30"), ("maxspeed:type", "DE:urban")]),
+        Some(30)
+    );
+    assert_eq!(
+        in_kmh(&[("maxspeed", "30"), ("source:maxspeed", "sign")]),
+        Some(30)
+    );
+
+    let unrestricted = parse_singular("maxspeed", "none");
+    assert!(matches!(
+        unrestricted.limit,
+        Blanket(limit) if matches!(limit.speed, SpeedValue::None)
+    ));
+}
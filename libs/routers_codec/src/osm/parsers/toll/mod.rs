@@ -0,0 +1,85 @@
+use crate::osm::primitives::TransportMode;
+use crate::osm::{Parser, TagString, Tags};
+use std::str::FromStr;
+
+/// The transport modes charged a toll to travel along a way,
+/// such as `toll=yes` or `toll:hgv=yes`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:toll
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tolls(pub Vec<(TransportMode, bool)>);
+
+impl Tolls {
+    fn value(value: &str) -> Option<bool> {
+        match value {
+            "yes" => Some(true),
+            "no" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn from_tag((key, value): (&TagString, &TagString)) -> Option<(TransportMode, bool)> {
+        let mode = match key.strip_prefix(TagString::TOLL)? {
+            "" => TransportMode::LandAccess,
+            mode => TransportMode::from_str(mode.strip_prefix(':')?).ok()?,
+        };
+
+        Some((mode, Self::value(value)?))
+    }
+
+    /// Determines if the transport mode is charged a toll, where the most
+    /// specific tag within the mode's [hierarchy](TransportMode::hierarchy) decides.
+    pub fn applies(&self, mode: TransportMode) -> bool {
+        mode.hierarchy()
+            .find_map(|mode| {
+                self.0
+                    .iter()
+                    .find(|(tolled, _)| *tolled == mode)
+                    .map(|(_, toll)| *toll)
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl Parser for Tolls {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let tolls = tags.iter().filter_map(Self::from_tag).collect::<Vec<_>>();
+
+        (!tolls.is_empty()).then_some(Tolls(tolls))
+    }
+}
+
+pub trait Toll {
+    fn tolls(&self) -> Tolls;
+}
+
+impl Toll for Tags {
+    fn tolls(&self) -> Tolls {
+        Tolls::parse(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolls() {
+        let motorway = Tags::from_iter([("toll", "yes")]).tolls();
+        assert!(motorway.applies(TransportMode::Motorcar));
+        assert!(motorway.applies(TransportMode::Bicycle));
+
+        let lorries = Tags::from_iter([("toll:hgv", "yes"), ("tollway", "yes")]).tolls();
+        assert!(lorries.applies(TransportMode::Hgv));
+        assert!(!lorries.applies(TransportMode::Motorcar));
+
+        let exempt = Tags::from_iter([("toll", "yes"), ("toll:bicycle", "no")]).tolls();
+        assert!(!exempt.applies(TransportMode::Bicycle));
+        assert!(exempt.applies(TransportMode::Motorcycle));
+
+        assert_eq!(
+            Tags::from_iter([("highway", "primary")]).tolls(),
+            Tolls::default()
+        );
+    }
+}
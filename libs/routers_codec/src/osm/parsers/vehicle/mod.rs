@@ -0,0 +1,313 @@
+use crate::osm::TraversalConditions;
+use crate::osm::access_tag::access::AccessValue;
+use crate::osm::primitives::DimensionLimit;
+use crate::osm::primitives::condition::{Condition, VehicleProperty};
+use crate::osm::{Parser, Tags};
+use std::str::FromStr;
+
+const HAZMAT: &str = "hazmat";
+const PHYSICAL: &str = ":physical";
+const CONDITIONAL: &str = ":conditional";
+
+/// The keys limiting each vehicle property.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Key:maxweight
+const LIMITS: [(&str, VehicleProperty); 5] = [
+    ("maxweight", VehicleProperty::Weight),
+    ("maxaxleload", VehicleProperty::Axleload),
+    ("maxheight", VehicleProperty::Height),
+    ("maxwidth", VehicleProperty::Width),
+    ("maxlength", VehicleProperty::Length),
+];
+
+/// The multiplicative penalty applied to ways which discourage hazardous materials.
+const HAZMAT_DISCOURAGED_PENALTY: f64 = 4.0;
+
+/// A restriction which only applies under a [`Condition`],
+/// like `maxweight:conditional=none @ (delivery)`.
+///
+/// Source: https://wiki.openstreetmap.org/wiki/Conditional_restrictions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalVehicleRestriction {
+    pub value: ConditionalVehicleValue,
+    pub condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalVehicleValue {
+    /// Replaces the signed limit of the property.
+    Limit(VehicleProperty, DimensionLimit),
+    /// Replaces the hazardous materials access.
+    Hazmat(AccessValue),
+}
+
+/// The physical and legal restrictions a way places upon vehicles,
+/// such as `maxheight=3.5` beneath a low bridge, or `hazmat=no` in a tunnel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VehicleRestrictions {
+    /// The signed limits of the way, which may be replaced
+    /// by a [conditional](#field.conditional) restriction.
+    pub limits: Vec<(VehicleProperty, DimensionLimit)>,
+
+    /// The physical limits of the way, like `maxheight:physical=3.2`,
+    /// which always apply regardless of any condition.
+    pub physical: Vec<(VehicleProperty, DimensionLimit)>,
+
+    /// The access of vehicles carrying hazardous materials.
+    ///
+    /// Source: https://wiki.openstreetmap.org/wiki/Key:hazmat
+    pub hazmat: Option<AccessValue>,
+
+    /// Restrictions which apply only under a condition.
+    pub conditional: Vec<ConditionalVehicleRestriction>,
+}
+
+impl VehicleRestrictions {
+    /// Parses a conditional value, like `none @ (delivery); 3.5 @ (Mo-Fr)`,
+    /// into each value and the condition under which it applies.
+    fn parse_conditional<T>(
+        value: &str,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> impl Iterator<Item = (T, Condition)> {
+        Condition::parse_conditional(value)
+            .into_iter()
+            .filter_map(move |(value, condition)| Some((parse(value)?, condition)))
+    }
+
+    /// The conditional values which apply to the vehicle and trip, in order of declaration.
+    fn applicable<'a>(
+        &'a self,
+        conditions: &'a TraversalConditions,
+    ) -> impl DoubleEndedIterator<Item = &'a ConditionalVehicleValue> {
+        self.conditional
+            .iter()
+            .filter(|restriction| restriction.condition.applies(conditions) == Some(true))
+            .map(|restriction| &restriction.value)
+    }
+
+    /// The signed limit of the property for the vehicle, considering any
+    /// applicable conditional restriction, of which the last declared wins.
+    pub fn limit(
+        &self,
+        property: VehicleProperty,
+        conditions: &TraversalConditions,
+    ) -> Option<DimensionLimit> {
+        self.applicable(conditions)
+            .rev()
+            .find_map(|value| match value {
+                ConditionalVehicleValue::Limit(prop, limit) if *prop == property => Some(*limit),
+                _ => None,
+            })
+            .or_else(|| {
+                self.limits
+                    .iter()
+                    .find(|(prop, _)| *prop == property)
+                    .map(|(_, limit)| *limit)
+            })
+    }
+
+    /// The hazardous materials access for the vehicle, considering any
+    /// applicable conditional restriction.
+    pub fn hazmat(&self, conditions: &TraversalConditions) -> Option<AccessValue> {
+        self.applicable(conditions)
+            .rev()
+            .find_map(|value| match value {
+                ConditionalVehicleValue::Hazmat(access) => Some(access.clone()),
+                _ => None,
+            })
+            .or_else(|| self.hazmat.clone())
+    }
+
+    /// Determines if the vehicle may traverse the way.
+    pub fn permits(&self, conditions: &TraversalConditions) -> bool {
+        let vehicle = &conditions.vehicle;
+        let within_limits = LIMITS.iter().all(|(_, property)| {
+            let Some(dimension) = vehicle.dimension(*property) else {
+                return true;
+            };
+
+            let physical = self
+                .physical
+                .iter()
+                .filter(|(prop, _)| prop == property)
+                .all(|(_, limit)| limit.permits(dimension));
+
+            physical
+                && self
+                    .limit(*property, conditions)
+                    .is_none_or(|limit| limit.permits(dimension))
+        });
+
+        within_limits
+            && (!vehicle.hazmat
+                || self
+                    .hazmat(conditions)
+                    .is_none_or(|access| access.permits()))
+    }
+
+    /// The multiplicative penalty of traversing the way with the vehicle,
+    /// given it is [permitted](Self::permits).
+    ///
+    /// Ways which discourage hazardous materials, or only permit them
+    /// to reach a destination, are penalised for vehicles carrying them.
+    pub fn penalty(&self, conditions: &TraversalConditions) -> f64 {
+        match conditions
+            .vehicle
+            .hazmat
+            .then(|| self.hazmat(conditions))
+            .flatten()
+        {
+            Some(AccessValue::Discouraged | AccessValue::Destination) => HAZMAT_DISCOURAGED_PENALTY,
+            _ => 1.0,
+        }
+    }
+}
+
+impl Parser for VehicleRestrictions {
+    fn parse(tags: &Tags) -> Option<Self> {
+        let mut restrictions = VehicleRestrictions::default();
+
+        for (key, property) in LIMITS {
+            if let Some(limit) = tags
+                .get(key)
+                .and_then(|value| DimensionLimit::parse(property, value))
+            {
+                restrictions.limits.push((property, limit));
+            }
+
+            if let Some(limit) = tags
+                .get(&format!("{key}{PHYSICAL}"))
+                .and_then(|value| DimensionLimit::parse(property, value))
+            {
+                restrictions.physical.push((property, limit));
+            }
+
+            if let Some(value) = tags.get(&format!("{key}{CONDITIONAL}")) {
+                restrictions.conditional.extend(
+                    Self::parse_conditional(value, |v| DimensionLimit::parse(property, v)).map(
+                        |(limit, condition)| ConditionalVehicleRestriction {
+                            value: ConditionalVehicleValue::Limit(property, limit),
+                            condition,
+                        },
+                    ),
+                );
+            }
+        }
+
+        restrictions.hazmat = tags
+            .get(HAZMAT)
+            .and_then(|value| AccessValue::from_str(value).ok());
+
+        if let Some(value) = tags.get(&format!("{HAZMAT}{CONDITIONAL}")) {
+            restrictions.conditional.extend(
+                Self::parse_conditional(value, |v| AccessValue::from_str(v).ok()).map(
+                    |(access, condition)| ConditionalVehicleRestriction {
+                        value: ConditionalVehicleValue::Hazmat(access),
+                        condition,
+                    },
+                ),
+            );
+        }
+
+        Some(restrictions)
+    }
+}
+
+pub trait VehicleRestriction {
+    fn vehicle_restrictions(&self) -> VehicleRestrictions;
+}
+
+impl VehicleRestriction for Tags {
+    fn vehicle_restrictions(&self) -> VehicleRestrictions {
+        VehicleRestrictions::parse(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::primitives::TransportMode;
+    use crate::osm::{TripContext, VehicleProfile};
+    use chrono::NaiveDate;
+
+    fn hgv(vehicle: VehicleProfile) -> TraversalConditions {
+        TraversalConditions::new(TransportMode::Hgv).vehicle(vehicle)
+    }
+
+    fn truck() -> VehicleProfile {
+        VehicleProfile::default()
+            .weight(18.0)
+            .height(4.0)
+            .width(2.55)
+            .length(12.0)
+    }
+
+    #[test]
+    fn test_dimension_limits() {
+        let bridge = Tags::from_iter([("maxheight", "3.8")]).vehicle_restrictions();
+        assert!(!bridge.permits(&hgv(truck())));
+        assert!(bridge.permits(&hgv(truck().height(3.8))));
+
+        // Vehicles of unknown dimensions are never restricted
+        assert!(bridge.permits(&hgv(VehicleProfile::default())));
+
+        let road =
+            Tags::from_iter([("maxweight", "7.5"), ("maxlength", "none")]).vehicle_restrictions();
+        assert!(!road.permits(&hgv(truck())));
+        assert!(road.permits(&hgv(truck().weight(7.5))));
+    }
+
+    #[test]
+    fn test_physical_limits() {
+        let bridge = Tags::from_iter([("maxheight", "default"), ("maxheight:physical", "13'0\"")])
+            .vehicle_restrictions();
+
+        assert!(!bridge.permits(&hgv(truck())));
+        assert!(bridge.permits(&hgv(truck().height(3.9))));
+    }
+
+    #[test]
+    fn test_conditional_limits() {
+        let road = Tags::from_iter([
+            ("maxweight", "3.5"),
+            (
+                "maxweight:conditional",
+                "none @ (length<=10); 40 @ (Mo-Fr 06:00-20:00)",
+            ),
+        ])
+        .vehicle_restrictions();
+
+        assert!(!road.permits(&hgv(truck())));
+        assert!(road.permits(&hgv(truck().length(9.0))));
+
+        // The weekday limit applies only at a known time within it
+        let at = |day, hour| {
+            let time = NaiveDate::from_ymd_opt(2025, 1, day)
+                .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                .unwrap();
+
+            hgv(truck()).trip(TripContext::default().time(time))
+        };
+
+        assert!(road.permits(&at(6, 12)));
+        assert!(!road.permits(&at(6, 21)));
+        assert!(!road.permits(&at(11, 12)));
+    }
+
+    #[test]
+    fn test_hazmat() {
+        let tunnel = Tags::from_iter([("hazmat", "no")]).vehicle_restrictions();
+        assert!(tunnel.permits(&hgv(truck())));
+        assert!(!tunnel.permits(&hgv(truck().hazmat(true))));
+
+        let road =
+            Tags::from_iter([("hazmat:conditional", "no @ (weight>12)")]).vehicle_restrictions();
+        assert!(!road.permits(&hgv(truck().hazmat(true))));
+        assert!(road.permits(&hgv(truck().weight(7.5).hazmat(true))));
+
+        let road = Tags::from_iter([("hazmat", "discouraged")]).vehicle_restrictions();
+        assert!(road.permits(&hgv(truck().hazmat(true))));
+        assert_eq!(road.penalty(&hgv(truck())), 1.0);
+        assert!(road.penalty(&hgv(truck().hazmat(true))) > 1.0);
+    }
+}
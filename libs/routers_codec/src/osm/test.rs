@@ -1,12 +1,27 @@
 #![cfg(test)]
 
+use bytes::Bytes;
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
 use log::error;
+use prost::Message;
 use rayon::iter::{ParallelBridge, ParallelIterator};
+use std::io::Write;
+use std::path::PathBuf;
 use std::time::Instant;
 
 use crate::osm::blob::iterator::BlobIterator;
 use crate::osm::block::item::BlockItem;
 use crate::osm::block::iterator::BlockIterator;
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::{OsmEntryId, Tags};
+use crate::osm::error::CodecError;
+use crate::osm::model::blob::Data;
+use crate::osm::writer::PbfWriter;
+use crate::osm::{Blob, BlobHeader, HeaderBlock, PrimitiveBlock, StringTable};
+use crate::osm::{DecodeMode, Parallel, ProcessedElementIterator};
+use crate::primitive::Node;
+use geo::{Rect, coord, point};
 
 use routers_fixtures::{BADEN_WUERTTEMBERG, DISTRICT_OF_COLUMBIA, fixture_path};
 
@@ -20,7 +35,7 @@ fn iterate_blobs_each() {
     let total_data_size = iterator.map(|f| {
         f.map(|blob| {
             // println!("Have blob: {}. Type: {}", blob.header.datasize, blob.header.r#type);
-            blob.header.datasize
+            blob.expect("!").header.datasize
         })
         .reduce(|a, b| a + b)
     });
@@ -31,7 +46,7 @@ fn iterate_blobs_each() {
         }
         Err(err) => {
             error!(
-                "Failed to load file, {:?}. Got error: {err}",
+                "Failed to load file, {:?}. Got error: {err:?}",
                 path.as_os_str().to_str()
             );
         }
@@ -52,7 +67,7 @@ fn iterate_blocks_each() {
     match iterator {
         Ok(iter) => {
             for block in iter {
-                match block {
+                match block.expect("!") {
                     BlockItem::HeaderBlock(_) => header_blocks += 1,
                     BlockItem::PrimitiveBlock(_) => primitive_blocks += 1,
                 }
@@ -60,7 +75,7 @@ fn iterate_blocks_each() {
         }
         Err(err) => {
             error!(
-                "Failed to load file, {:?}. Got error: {err}",
+                "Failed to load file, {:?}. Got error: {err:?}",
                 path.as_os_str().to_str()
             );
         }
@@ -79,7 +94,7 @@ fn parallel_iterate_blocks_each() {
     let elements = block_iter
         .into_iter()
         .par_bridge()
-        .map(|block| match block {
+        .map(|block| match block.expect("!") {
             BlockItem::HeaderBlock(_) => (0, 1),
             BlockItem::PrimitiveBlock(_) => (1, 0),
         })
@@ -87,3 +102,477 @@ fn parallel_iterate_blocks_each() {
 
     assert_eq!(elements, (237, 1));
 }
+
+/// A compression of an encoded block into the data of a blob.
+type Compress = fn(&[u8]) -> Data;
+
+/// Compresses the block using zlib, as the majority of files are.
+fn zlib(block: &[u8]) -> Data {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder.write_all(block).unwrap();
+    Data::ZlibData(Bytes::from(encoder.finish().unwrap()))
+}
+
+/// Encodes the block as a blob of the given type, prefixed by its header.
+fn encode_blob(r#type: &str, block: Vec<u8>, compress: fn(&[u8]) -> Data) -> Vec<u8> {
+    let raw_size = Some(block.len() as i32);
+    encode_sized_blob(r#type, block, compress, raw_size)
+}
+
+/// Encodes the block as a blob, declaring the given uncompressed size.
+fn encode_sized_blob(
+    r#type: &str,
+    block: Vec<u8>,
+    compress: fn(&[u8]) -> Data,
+    raw_size: Option<i32>,
+) -> Vec<u8> {
+    let blob = Blob {
+        raw_size,
+        data: Some(compress(&block)),
+    }
+    .encode_to_vec();
+
+    let header = BlobHeader {
+        r#type: r#type.to_string(),
+        indexdata: None,
+        datasize: blob.len() as i32,
+    }
+    .encode_to_vec();
+
+    [(header.len() as u32).to_be_bytes().to_vec(), header, blob].concat()
+}
+
+/// Writes the file contents to a temporary path, returning the path.
+fn write_file(name: &str, file: Vec<u8>) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{name}-{}.osm.pbf", std::process::id()));
+    std::fs::write(&path, file).unwrap();
+    path
+}
+
+/// Encodes a file containing a header block and the given number of (empty) primitive blocks.
+fn synthetic_file_bytes(primitive_blocks: usize) -> Vec<u8> {
+    let mut file = encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), zlib);
+    for _ in 0..primitive_blocks {
+        file.extend(encode_blob(
+            "OSMData",
+            PrimitiveBlock::default().encode_to_vec(),
+            zlib,
+        ));
+    }
+
+    file
+}
+
+/// Writes a file containing a header block and the given number of (empty)
+/// primitive blocks to a temporary path, returning the path.
+fn synthetic_file(name: &str, primitive_blocks: usize) -> PathBuf {
+    write_file(name, synthetic_file_bytes(primitive_blocks))
+}
+
+/// Decodes the only primitive block of a file compressed using the scheme.
+fn decode_compressed(
+    name: &str,
+    compress: fn(&[u8]) -> Data,
+) -> Result<PrimitiveBlock, CodecError> {
+    let block = PrimitiveBlock {
+        stringtable: StringTable {
+            s: vec![Bytes::from_static(b""), Bytes::from_static(b"highway")],
+        },
+        ..PrimitiveBlock::default()
+    };
+
+    let path = write_file(
+        name,
+        encode_blob("OSMData", block.encode_to_vec(), compress),
+    );
+    let decoded = BlockIterator::new(path.clone()).unwrap().next().unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    decoded.map(|decoded| match decoded {
+        BlockItem::PrimitiveBlock(decoded) => {
+            assert_eq!(decoded, block);
+            decoded
+        }
+        BlockItem::HeaderBlock(_) => panic!("expected a primitive block"),
+    })
+}
+
+#[test]
+fn decode_raw_blobs() {
+    decode_compressed("raw", |block| Data::Raw(Bytes::copy_from_slice(block))).unwrap();
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn decode_zstd_blobs() {
+    decode_compressed("zstd", |block| {
+        Data::ZstdData(Bytes::from(ruzstd::encoding::compress_to_vec(
+            block,
+            ruzstd::encoding::CompressionLevel::Fastest,
+        )))
+    })
+    .unwrap();
+}
+
+#[test]
+#[cfg(feature = "lz4")]
+fn decode_lz4_blobs() {
+    decode_compressed("lz4", |block| {
+        Data::Lz4Data(Bytes::from(lz4_flex::block::compress(block)))
+    })
+    .unwrap();
+}
+
+#[test]
+#[cfg(feature = "lzma")]
+fn decode_lzma_blobs() {
+    decode_compressed("lzma", |block| {
+        let mut compressed = vec![];
+        lzma_rs::lzma_compress(&mut &block[..], &mut compressed).unwrap();
+        Data::LzmaData(Bytes::from(compressed))
+    })
+    .unwrap();
+}
+
+#[test]
+fn reject_unsupported_blobs() {
+    #[allow(deprecated)]
+    let bzip2 = decode_compressed("bzip2", |block| {
+        Data::ObsoleteBzip2Data(Bytes::copy_from_slice(block))
+    });
+    assert!(matches!(
+        bzip2.as_ref().map_err(CodecError::cause),
+        Err(CodecError::UnsupportedCompression("bzip2"))
+    ));
+
+    let corrupt = decode_compressed("corrupt", |_| {
+        Data::ZlibData(Bytes::from_static(b"corrupt"))
+    });
+    assert!(matches!(
+        corrupt,
+        Err(CodecError::Blob { index: 0, offset: 0, ref cause })
+            if matches!(**cause, CodecError::DecompressionError("zlib", _))
+    ));
+}
+
+/// A header block followed by three primitive blocks, the second of which is corrupt.
+fn corrupt_file() -> Vec<u8> {
+    let block = || PrimitiveBlock::default().encode_to_vec();
+    let corrupt = |_: &[u8]| Data::ZlibData(Bytes::from_static(b"corrupt"));
+
+    [
+        encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), zlib),
+        encode_blob("OSMData", block(), zlib),
+        encode_blob("OSMData", block(), corrupt),
+        encode_blob("OSMData", block(), zlib),
+    ]
+    .concat()
+}
+
+fn decode_all(file: Vec<u8>, mode: DecodeMode) -> (Vec<Result<BlockItem, CodecError>>, usize) {
+    let blobs = BlobIterator::with_existing(Bytes::from(file)).unwrap();
+    let mut blocks = BlockIterator::from_blobs(blobs).mode(mode);
+    let skipped = blocks.skipped();
+
+    let decoded = blocks.by_ref().collect::<Vec<_>>();
+    (decoded, skipped.count())
+}
+
+#[test]
+fn strict_corrupt_blob() {
+    let (blocks, skipped) = decode_all(corrupt_file(), DecodeMode::Strict);
+
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(skipped, 0);
+    assert!(matches!(
+        &blocks[2],
+        Err(CodecError::Blob { index: 2, cause, .. })
+            if matches!(**cause, CodecError::DecompressionError("zlib", _))
+    ));
+
+    // The offset is that of the blob within the file.
+    let file = corrupt_file();
+    let Err(CodecError::Blob { offset, .. }) = &blocks[2] else {
+        panic!("expected a blob error");
+    };
+    let expected = encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), zlib).len()
+        + encode_blob("OSMData", PrimitiveBlock::default().encode_to_vec(), zlib).len();
+    assert_eq!(*offset as usize, expected);
+    assert!(*offset < file.len() as u64);
+}
+
+#[test]
+fn lenient_corrupt_blob() {
+    let (blocks, skipped) = decode_all(corrupt_file(), DecodeMode::Lenient);
+
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks.iter().all(Result::is_ok));
+    assert_eq!(skipped, 1);
+}
+
+#[test]
+fn corrupt_header_blob() {
+    let corrupt = |_: &[u8]| Data::ZlibData(Bytes::from_static(b"corrupt"));
+    let header = HeaderBlock {
+        required_features: vec!["DenseNodes".to_string()],
+        ..Default::default()
+    };
+    let file = [
+        encode_blob("OSMHeader", header.encode_to_vec(), corrupt),
+        encode_blob("OSMData", PrimitiveBlock::default().encode_to_vec(), zlib),
+    ]
+    .concat();
+
+    // The header is decoded strictly, as the features it requires cannot otherwise be known.
+    let result = BlobIterator::with_existing(Bytes::from(file));
+    assert!(matches!(
+        result.as_ref().map_err(CodecError::cause),
+        Err(CodecError::DecompressionError("zlib", _))
+    ));
+}
+
+#[test]
+fn truncated_file() {
+    let mut file = synthetic_file_bytes(4);
+    file.truncate(file.len() - 3);
+
+    let (blocks, _) = decode_all(file.clone(), DecodeMode::Strict);
+    assert_eq!(blocks.len(), 5);
+
+    // The error reports the three bytes which were lost.
+    let error = blocks.last().unwrap().as_ref().map_err(CodecError::cause);
+    assert!(matches!(
+        error,
+        Err(CodecError::Truncated { expected, available }) if expected - available == 3
+    ));
+
+    // A truncated file is not mistaken for a smaller, valid file.
+    let path = write_file("truncated", file.clone());
+    let result = ProcessedElementIterator::new(path.clone())
+        .unwrap()
+        .for_each(|_| {});
+    std::fs::remove_file(path).unwrap();
+    assert!(result.is_err());
+
+    let (blocks, skipped) = decode_all(file, DecodeMode::Lenient);
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(skipped, 1);
+}
+
+#[test]
+fn oversized_blob_header() {
+    let file = [u32::MAX.to_be_bytes().to_vec(), vec![0; 16]].concat();
+    let result = BlobIterator::with_existing(Bytes::from(file));
+
+    assert!(matches!(
+        result.as_ref().map_err(CodecError::cause),
+        Err(CodecError::OversizedBlob {
+            kind: "blob header",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn invalid_raw_size() {
+    let block = || PrimitiveBlock::default().encode_to_vec();
+
+    for raw_size in [Some(-1), Some(i32::MAX), None] {
+        let file = [
+            encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), zlib),
+            encode_sized_blob("OSMData", block(), zlib, raw_size),
+            encode_blob("OSMData", block(), zlib),
+        ]
+        .concat();
+
+        let (blocks, _) = decode_all(file.clone(), DecodeMode::Strict);
+        let cause = blocks[1].as_ref().map(|_| ()).map_err(CodecError::cause);
+        match raw_size {
+            Some(i32::MAX) => assert!(matches!(
+                cause,
+                Err(CodecError::OversizedBlob {
+                    kind: "uncompressed blob",
+                    ..
+                })
+            )),
+            _ => {
+                assert!(matches!(cause, Err(CodecError::InvalidRawSize(size)) if *size == raw_size))
+            }
+        }
+
+        let (blocks, skipped) = decode_all(file, DecodeMode::Lenient);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(skipped, 1);
+    }
+
+    // Uncompressed blobs need not declare their size.
+    let raw = |block: &[u8]| Data::Raw(Bytes::copy_from_slice(block));
+    let file = [
+        encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), raw),
+        encode_sized_blob("OSMData", block(), raw, None),
+    ]
+    .concat();
+    let (blocks, _) = decode_all(file, DecodeMode::Strict);
+    assert!(blocks.iter().all(Result::is_ok));
+}
+
+#[test]
+fn misstated_raw_size() {
+    let block = PrimitiveBlock {
+        stringtable: StringTable {
+            s: vec![Bytes::from_static(b""), Bytes::from_static(b"highway")],
+        },
+        ..PrimitiveBlock::default()
+    }
+    .encode_to_vec();
+
+    let compressors: Vec<(&str, Compress)> = vec![
+        ("zlib", zlib),
+        #[cfg(feature = "zstd")]
+        ("zstd", |block| {
+            Data::ZstdData(Bytes::from(ruzstd::encoding::compress_to_vec(
+                block,
+                ruzstd::encoding::CompressionLevel::Fastest,
+            )))
+        }),
+        #[cfg(feature = "lz4")]
+        ("lz4", |block| {
+            Data::Lz4Data(Bytes::from(lz4_flex::block::compress(block)))
+        }),
+        #[cfg(feature = "lzma")]
+        ("lzma", |block| {
+            let mut compressed = vec![];
+            lzma_rs::lzma_compress(&mut &block[..], &mut compressed).unwrap();
+            Data::LzmaData(Bytes::from(compressed))
+        }),
+    ];
+
+    // A blob inflating beyond its declared size is rejected, rather than
+    // decompressed in full, as is one inflating short of it.
+    for (name, compress) in compressors {
+        for raw_size in [block.len() / 2, block.len() - 1, block.len() + 1] {
+            let file = [
+                encode_blob("OSMHeader", HeaderBlock::default().encode_to_vec(), zlib),
+                encode_sized_blob("OSMData", block.clone(), compress, Some(raw_size as i32)),
+            ]
+            .concat();
+
+            let (blocks, _) = decode_all(file, DecodeMode::Strict);
+            let cause = blocks[1].as_ref().map(|_| ()).map_err(CodecError::cause);
+            assert!(
+                matches!(cause, Err(CodecError::DecompressionError(kind, _)) if *kind == name),
+                "{name} with a raw size of {raw_size}: {cause:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn stream_blobs_matches_in_memory() {
+    let path = synthetic_file("stream_blobs", 8);
+
+    let streamed = BlobIterator::new(path.clone())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let buffered = BlobIterator::with_existing(Bytes::from(std::fs::read(&path).unwrap()))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(streamed.len(), 9);
+    assert_eq!(streamed.len(), buffered.len());
+
+    for (a, b) in streamed.iter().zip(buffered.iter()) {
+        assert_eq!(a.range, b.range);
+        assert_eq!(a.data, b.data);
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn stream_blocks_with_bounded_readahead() {
+    let path = synthetic_file("stream_blocks", 32);
+
+    let elements = BlockIterator::new(path.clone())
+        .unwrap()
+        .readahead(1)
+        .par_iter()
+        .map(|block| match block.expect("!") {
+            BlockItem::HeaderBlock(_) => (0, 1),
+            BlockItem::PrimitiveBlock(_) => (1, 0),
+        })
+        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    assert_eq!(elements, (32, 1));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn mmap_blocks_each() {
+    let path = synthetic_file("mmap_blocks", 4);
+
+    // SAFETY: The file is not written to until the iterator is dropped.
+    let blocks = unsafe { BlockIterator::mmap(path.clone()) }
+        .unwrap()
+        .count();
+    assert_eq!(blocks, 5);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_header() {
+    let bbox = Rect::new(
+        coord! { x: -77.12, y: 38.79 },
+        coord! { x: -76.90, y: 38.99 },
+    );
+
+    let mut writer = PbfWriter::new(vec![]).bbox(bbox);
+    writer
+        .write(&ProcessedElement::Node(
+            Node::new(point! { x: -77.03, y: 38.89 }, OsmEntryId::node(1)),
+            Tags::default(),
+        ))
+        .unwrap();
+
+    let path = write_file("read_header", writer.finish().unwrap());
+    let iter = BlockIterator::new(path.clone()).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    let header = iter.header().expect("The file has a header");
+    assert!(header.required_features.contains(&"DenseNodes".to_string()));
+    assert!(header.writing_program.is_some());
+
+    let read = header.bbox.unwrap();
+    assert!((read.min().x - bbox.min().x).abs() < 1e-9);
+    assert!((read.max().y - bbox.max().y).abs() < 1e-9);
+
+    // The header block is still yielded, alongside the node.
+    assert_eq!(iter.count(), 2);
+}
+
+#[test]
+fn reject_unsupported_features() {
+    let header = HeaderBlock {
+        required_features: vec![
+            "OsmSchema-V0.6".to_string(),
+            "HistoricalInformation".to_string(),
+        ],
+        ..HeaderBlock::default()
+    };
+
+    let path = write_file(
+        "unsupported_features",
+        encode_blob("OSMHeader", header.encode_to_vec(), zlib),
+    );
+    let result = ProcessedElementIterator::new(path.clone());
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(CodecError::UnsupportedFeature(feature)) if feature == "HistoricalInformation"
+    ));
+}
@@ -0,0 +1,206 @@
+//! Accumulates processed elements into a `PrimitiveBlock`, interning
+//! their strings and delta coding their identifiers and positions.
+
+use bytes::Bytes;
+use std::collections::HashMap;
+
+use crate::Entry;
+use crate::osm;
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::{OsmEntryId, Relation, Tags, Way};
+use crate::primitive::Node;
+
+/// The granularity of written positions, in nanodegrees,
+/// being the default of the format (1e-7 degrees).
+pub(crate) const GRANULARITY: i32 = 100;
+
+/// Converts degrees into units of the [`GRANULARITY`].
+#[inline]
+pub(crate) fn to_granular(degrees: f64) -> i64 {
+    (degrees * 1e9 / GRANULARITY as f64).round() as i64
+}
+
+/// Builds a single `PrimitiveBlock`, in which each element variant is
+/// written into its own `PrimitiveGroup`, as is required by the format.
+///
+/// Nodes are written as `DenseNodes`, including their tags.
+pub struct BlockBuilder {
+    /// The index of each string in the table.
+    strings: HashMap<String, u32>,
+    table: Vec<Bytes>,
+
+    dense: osm::DenseNodes,
+    /// The last node written, as `(id, lat, lon)`, to delta code against.
+    last_node: (i64, i64, i64),
+    /// Whether any node is tagged, otherwise `keys_vals` is omitted.
+    tagged_nodes: bool,
+
+    ways: Vec<osm::Way>,
+    relations: Vec<osm::Relation>,
+}
+
+impl Default for BlockBuilder {
+    fn default() -> Self {
+        BlockBuilder {
+            // The first string is reserved, since dense nodes delimit their tags
+            // by a `0`, so is never interned, even for an empty string.
+            strings: HashMap::new(),
+            table: vec![Bytes::new()],
+            dense: osm::DenseNodes::default(),
+            last_node: (0, 0, 0),
+            tagged_nodes: false,
+            ways: vec![],
+            relations: vec![],
+        }
+    }
+}
+
+impl BlockBuilder {
+    /// The number of elements within the block.
+    pub fn len(&self) -> usize {
+        self.dense.id.len() + self.ways.len() + self.relations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The index of the string in the table, inserting it if not yet present.
+    fn intern(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.strings.get(string) {
+            return *index;
+        }
+
+        let index = self.table.len() as u32;
+        self.table.push(Bytes::copy_from_slice(string.as_bytes()));
+        self.strings.insert(string.to_string(), index);
+        index
+    }
+
+    /// The interned keys and values of the tags, ordered by key.
+    fn tags(&mut self, tags: &Tags) -> (Vec<u32>, Vec<u32>) {
+        let mut pairs = tags.iter().collect::<Vec<_>>();
+        pairs.sort_unstable_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        pairs
+            .into_iter()
+            .map(|(key, value)| (self.intern(key), self.intern(value)))
+            .unzip()
+    }
+
+    pub fn push(&mut self, element: &ProcessedElement) {
+        match element {
+            ProcessedElement::Node(node, tags) => self.push_node(node, tags),
+            ProcessedElement::Way(way) => self.push_way(way),
+            ProcessedElement::Relation(relation) => self.push_relation(relation),
+        }
+    }
+
+    fn push_node(&mut self, node: &Node<OsmEntryId>, tags: &Tags) {
+        let (id, lat, lon) = (
+            node.id.identifier(),
+            to_granular(node.position.y()),
+            to_granular(node.position.x()),
+        );
+
+        let (last_id, last_lat, last_lon) = self.last_node;
+        self.dense.id.push(id - last_id);
+        self.dense.lat.push(lat - last_lat);
+        self.dense.lon.push(lon - last_lon);
+
+        self.last_node = (id, lat, lon);
+
+        // The tags of each node are delimited by a `0`.
+        let (keys, vals) = self.tags(tags);
+        for (key, val) in keys.into_iter().zip(vals) {
+            self.dense.keys_vals.extend([key as i32, val as i32]);
+        }
+
+        self.dense.keys_vals.push(0);
+        self.tagged_nodes |= !tags.is_empty();
+    }
+
+    fn push_way(&mut self, way: &Way) {
+        let (keys, vals) = self.tags(way.tags());
+
+        let refs = way
+            .refs()
+            .iter()
+            .scan(0, |last, reference| {
+                let delta = reference.id.identifier() - *last;
+                *last = reference.id.identifier();
+                Some(delta)
+            })
+            .collect();
+
+        self.ways.push(osm::Way {
+            id: way.id().identifier(),
+            keys,
+            vals,
+            refs,
+            ..osm::Way::default()
+        });
+    }
+
+    fn push_relation(&mut self, relation: &Relation) {
+        let (keys, vals) = self.tags(&relation.tags);
+
+        let mut last = 0;
+        let mut raw = osm::Relation {
+            id: relation.id,
+            keys,
+            vals,
+            ..osm::Relation::default()
+        };
+
+        for reference in relation.refs.iter() {
+            let role = reference.role.as_ref().map_or("", |role| role.0.as_str());
+            raw.roles_sid.push(self.intern(role) as i32);
+
+            raw.memids.push(reference.id.identifier() - last);
+            last = reference.id.identifier();
+
+            raw.types.push(reference.member_type as i32);
+        }
+
+        self.relations.push(raw);
+    }
+
+    /// Builds the block from the pushed elements, resetting the builder.
+    pub fn build(&mut self) -> osm::PrimitiveBlock {
+        let mut builder = std::mem::take(self);
+        let mut groups = vec![];
+
+        if !builder.tagged_nodes {
+            builder.dense.keys_vals.clear();
+        }
+
+        if !builder.dense.id.is_empty() {
+            groups.push(osm::PrimitiveGroup {
+                dense: Some(builder.dense),
+                ..osm::PrimitiveGroup::default()
+            });
+        }
+
+        if !builder.ways.is_empty() {
+            groups.push(osm::PrimitiveGroup {
+                ways: builder.ways,
+                ..osm::PrimitiveGroup::default()
+            });
+        }
+
+        if !builder.relations.is_empty() {
+            groups.push(osm::PrimitiveGroup {
+                relations: builder.relations,
+                ..osm::PrimitiveGroup::default()
+            });
+        }
+
+        osm::PrimitiveBlock {
+            stringtable: osm::StringTable { s: builder.table },
+            primitivegroup: groups,
+            granularity: Some(GRANULARITY),
+            ..osm::PrimitiveBlock::default()
+        }
+    }
+}
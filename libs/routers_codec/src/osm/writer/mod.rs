@@ -0,0 +1,187 @@
+//! The `.osm.pbf` writer, encoding processed elements back into blobs
+
+pub mod block;
+
+#[doc(hidden)]
+mod test;
+
+use bytes::Bytes;
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use geo::Rect;
+use prost::Message;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::error::CodecError;
+use crate::osm::model::blob::Data;
+use crate::osm::{Blob, BlobHeader, HeaderBBox, HeaderBlock};
+
+#[doc(inline)]
+pub use block::BlockBuilder;
+
+/// The features a reader must support to read the written file.
+const REQUIRED_FEATURES: [&str; 2] = ["OsmSchema-V0.6", "DenseNodes"];
+
+/// The default number of elements written into each block,
+/// keeping blocks well within the recommended size.
+const DEFAULT_BLOCK_SIZE: usize = 8000;
+
+/// Writes processed elements into a spec-compliant `.osm.pbf` file.
+///
+/// Elements are accumulated into blocks of the [block size](Self::block_size),
+/// which are zlib compressed and written as they fill. The header block is
+/// written before the first block, so the [bounding box](Self::bbox) must be
+/// set before any element is written.
+///
+/// ```rust
+/// use routers_codec::osm::PbfWriter;
+/// use routers_codec::osm::element::{OsmEntryId, ProcessedElement, Tags};
+/// use routers_codec::primitive::Node;
+/// use geo::point;
+///
+/// let mut writer = PbfWriter::new(vec![]);
+/// writer
+///     .write(&ProcessedElement::Node(
+///         Node::new(point! { x: -77.03, y: 38.89 }, OsmEntryId::node(1)),
+///         Tags::default(),
+///     ))
+///     .expect("Could not write node");
+///
+/// let bytes = writer.finish().expect("Could not finish file");
+/// ```
+pub struct PbfWriter<W: Write> {
+    writer: W,
+    block: BlockBuilder,
+    bbox: Option<Rect>,
+
+    /// The number of elements written into each block.
+    block_size: usize,
+    header_written: bool,
+}
+
+impl PbfWriter<BufWriter<File>> {
+    /// Creates (or truncates) the file at the given path, and writes into it.
+    pub fn create(path: PathBuf) -> Result<Self, CodecError> {
+        Ok(PbfWriter::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> PbfWriter<W> {
+    pub fn new(writer: W) -> Self {
+        PbfWriter {
+            writer,
+            block: BlockBuilder::default(),
+            bbox: None,
+            block_size: DEFAULT_BLOCK_SIZE,
+            header_written: false,
+        }
+    }
+
+    /// Sets the bounding box of the file, written into its header block.
+    pub fn bbox(self, bbox: Rect) -> Self {
+        Self {
+            bbox: Some(bbox),
+            ..self
+        }
+    }
+
+    /// Sets the number of elements written into each block.
+    pub fn block_size(self, block_size: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+            ..self
+        }
+    }
+
+    /// Writes the element, flushing the current block if it is full.
+    pub fn write(&mut self, element: &ProcessedElement) -> Result<(), CodecError> {
+        self.block.push(element);
+
+        if self.block.len() >= self.block_size {
+            self.flush_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes each of the elements, in order.
+    pub fn write_all<'a>(
+        &mut self,
+        elements: impl IntoIterator<Item = &'a ProcessedElement>,
+    ) -> Result<(), CodecError> {
+        elements
+            .into_iter()
+            .try_for_each(|element| self.write(element))
+    }
+
+    /// Flushes any remaining elements, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, CodecError> {
+        self.write_header()?;
+        if !self.block.is_empty() {
+            self.flush_block()?;
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn header(&self) -> HeaderBlock {
+        HeaderBlock {
+            bbox: self.bbox.map(|bbox| HeaderBBox {
+                left: (bbox.min().x * 1e9).round() as i64,
+                right: (bbox.max().x * 1e9).round() as i64,
+                top: (bbox.max().y * 1e9).round() as i64,
+                bottom: (bbox.min().y * 1e9).round() as i64,
+            }),
+            required_features: REQUIRED_FEATURES.map(String::from).to_vec(),
+            writingprogram: Some(format!("routers_codec/{}", env!("CARGO_PKG_VERSION"))),
+            ..HeaderBlock::default()
+        }
+    }
+
+    fn write_header(&mut self) -> Result<(), CodecError> {
+        if self.header_written {
+            return Ok(());
+        }
+
+        self.header_written = true;
+        self.write_blob("OSMHeader", self.header().encode_to_vec())
+    }
+
+    fn flush_block(&mut self) -> Result<(), CodecError> {
+        self.write_header()?;
+
+        let block = self.block.build();
+        self.write_blob("OSMData", block.encode_to_vec())
+    }
+
+    /// Compresses the data into a blob of the given type, and writes it
+    /// after its header and the (big-endian) length of its header.
+    fn write_blob(&mut self, r#type: &str, data: Vec<u8>) -> Result<(), CodecError> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder.write_all(&data)?;
+
+        let blob = Blob {
+            raw_size: Some(data.len() as i32),
+            data: Some(Data::ZlibData(Bytes::from(encoder.finish()?))),
+        }
+        .encode_to_vec();
+
+        let header = BlobHeader {
+            r#type: r#type.to_string(),
+            indexdata: None,
+            datasize: blob.len() as i32,
+        }
+        .encode_to_vec();
+
+        self.writer
+            .write_all(&(header.len() as u32).to_be_bytes())?;
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&blob)?;
+
+        Ok(())
+    }
+}
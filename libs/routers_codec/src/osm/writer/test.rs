@@ -0,0 +1,303 @@
+#![cfg(test)]
+
+use bytes::Bytes;
+use geo::{Rect, coord, point};
+use std::collections::HashMap;
+
+use crate::Entry;
+use crate::osm::block::item::BlockItem;
+use crate::osm::element::BlockStrings;
+use crate::osm::element::item::{Element, ProcessedElement};
+use crate::osm::element::variants::{
+    OsmEntryId, Reference, References, Relation, Role, TagString, Tags, Way,
+};
+use crate::osm::relation::MemberType;
+use crate::osm::{BlobIterator, BlockIterator, PbfWriter};
+use crate::primitive::Node;
+use routers_fixtures::{DISTRICT_OF_COLUMBIA, fixture_path};
+
+fn nodes() -> Vec<Node<OsmEntryId>> {
+    vec![
+        Node::new(point! { x: -77.0365, y: 38.8977 }, OsmEntryId::node(100)),
+        Node::new(point! { x: -77.0091, y: 38.8899 }, OsmEntryId::node(105)),
+        Node::new(point! { x: -77.0502, y: 38.8893 }, OsmEntryId::node(42)),
+    ]
+}
+
+fn way() -> Way {
+    let refs = [100, 105, 42]
+        .map(|id| Reference::without_role(OsmEntryId::node(id), MemberType::Node))
+        .to_vec();
+
+    Way::new(
+        OsmEntryId::way(7),
+        References::from(refs),
+        Tags::from_iter([("highway", "primary"), ("name", "Pennsylvania Avenue")]),
+    )
+}
+
+fn relation() -> Relation {
+    Relation {
+        id: 3,
+        tags: Tags::from_iter([("type", "route"), ("route", "bus")]),
+        refs: References::from(vec![
+            Reference::with_role(
+                OsmEntryId::node(100),
+                MemberType::Node,
+                Role(TagString::from("stop")),
+            ),
+            Reference::without_role(OsmEntryId::way(7), MemberType::Way),
+        ]),
+    }
+}
+
+/// Reads each block of the encoded file.
+fn read(bytes: Vec<u8>) -> Vec<BlockItem> {
+    let blobs = BlobIterator::with_existing(Bytes::from(bytes)).unwrap();
+    BlockIterator::from_blobs(blobs)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Could not decode written blocks")
+}
+
+#[test]
+fn round_trip_elements() {
+    let elements = nodes()
+        .into_iter()
+        .map(|node| ProcessedElement::Node(node, Tags::default()))
+        .chain([
+            ProcessedElement::Way(way()),
+            ProcessedElement::Relation(relation()),
+        ])
+        .collect::<Vec<_>>();
+
+    let bbox = Rect::new(coord! { x: -77.1, y: 38.8 }, coord! { x: -76.9, y: 39.0 });
+    let mut writer = PbfWriter::new(vec![]).bbox(bbox);
+    writer.write_all(&elements).unwrap();
+
+    let blocks = read(writer.finish().unwrap());
+    assert_eq!(blocks.len(), 2);
+
+    let BlockItem::HeaderBlock(header) = &blocks[0] else {
+        panic!("The header block must be written first");
+    };
+    assert!(header.required_features.contains(&"DenseNodes".to_string()));
+    assert_eq!(header.bbox.map(|bbox| bbox.left), Some(-77_100_000_000));
+
+    let BlockItem::PrimitiveBlock(block) = &blocks[1] else {
+        panic!("Expected a primitive block");
+    };
+
+    let strings = BlockStrings::new(block);
+    let mut decoded = (vec![], vec![], vec![]);
+    for element in blocks[1].raw_element_iter() {
+        match element {
+            Element::DenseNodes(dense) => decoded.0.extend(Node::from_dense(dense, 100)),
+            Element::Way(way) => decoded.1.push(Way::from_raw(way, &strings)),
+            Element::Relation(relation) => decoded.2.push(Relation::from_raw(relation, &strings)),
+            Element::Node(_) => panic!("Nodes must be written densely"),
+        }
+    }
+
+    let (decoded_nodes, decoded_ways, decoded_relations) = decoded;
+    assert_eq!(decoded_nodes.len(), 3);
+    for (decoded, node) in decoded_nodes.iter().zip(nodes()) {
+        assert_eq!(decoded.id, node.id);
+        assert!((decoded.position.x() - node.position.x()).abs() < 1e-7);
+        assert!((decoded.position.y() - node.position.y()).abs() < 1e-7);
+    }
+
+    let [decoded_way] = decoded_ways.as_slice() else {
+        panic!("Expected a single way");
+    };
+    assert_eq!(decoded_way.id(), way().id());
+    assert_eq!(
+        decoded_way
+            .refs()
+            .iter()
+            .map(|r| r.id.identifier())
+            .collect::<Vec<_>>(),
+        vec![100, 105, 42]
+    );
+    assert_eq!(decoded_way.tags(), way().tags());
+
+    let [decoded_relation] = decoded_relations.as_slice() else {
+        panic!("Expected a single relation");
+    };
+    assert_eq!(decoded_relation.tags, relation().tags);
+
+    let members = decoded_relation
+        .refs
+        .iter()
+        .map(|r| {
+            (
+                r.id.identifier(),
+                r.member_type,
+                r.role.as_ref().map(|role| role.0.to_string()),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        members,
+        vec![
+            (100, MemberType::Node, Some("stop".to_string())),
+            (7, MemberType::Way, Some(String::new())),
+        ]
+    );
+}
+
+#[test]
+fn round_trip_node_tags() {
+    let signals = Tags::from_iter([("highway", "traffic_signals")]);
+    let gate = Tags::from_iter([("barrier", "gate"), ("access", "private")]);
+
+    let [a, b, c] = nodes().try_into().unwrap();
+    let elements = [
+        ProcessedElement::Node(a, signals.clone()),
+        ProcessedElement::Node(b, Tags::default()),
+        ProcessedElement::Node(c, gate.clone()),
+    ];
+
+    let mut writer = PbfWriter::new(vec![]);
+    writer.write_all(&elements).unwrap();
+
+    let blocks = read(writer.finish().unwrap());
+    let decoded = |node_tags| {
+        blocks
+            .iter()
+            .flat_map(|block| block.element_iter_with(node_tags))
+            .filter_map(|element| match element {
+                ProcessedElement::Node(node, tags) => Some((node.id, tags.len())),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        decoded(true),
+        vec![
+            (OsmEntryId::node(100), 1),
+            (OsmEntryId::node(105), 0),
+            (OsmEntryId::node(42), 2)
+        ]
+    );
+
+    // Tags are only decoded when requested.
+    assert!(decoded(false).iter().all(|(_, tags)| *tags == 0));
+
+    let tagged = blocks
+        .iter()
+        .flat_map(|block| block.element_iter_with(true))
+        .find_map(|element| match element {
+            ProcessedElement::Node(node, tags) if node.id == OsmEntryId::node(42) => Some(tags),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(tagged.get("barrier"), gate.get("barrier"));
+    assert_eq!(tagged.get("access"), gate.get("access"));
+}
+
+#[test]
+fn round_trip_empty_tags() {
+    // Empty strings must not be confused with the delimiter of dense node tags.
+    let crossing = Tags::from_iter([("highway", "crossing"), ("crossing", ""), ("", "zebra")]);
+
+    let [a, b, _] = nodes().try_into().unwrap();
+    let elements = [
+        ProcessedElement::Node(a, crossing.clone()),
+        ProcessedElement::Node(b, Tags::default()),
+    ];
+
+    let mut writer = PbfWriter::new(vec![]);
+    writer.write_all(&elements).unwrap();
+
+    let decoded = read(writer.finish().unwrap())
+        .iter()
+        .flat_map(|block| block.element_iter_with(true))
+        .filter_map(|element| match element {
+            ProcessedElement::Node(node, tags) => Some((node.id, tags)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        decoded,
+        vec![
+            (OsmEntryId::node(100), crossing),
+            (OsmEntryId::node(105), Tags::default())
+        ]
+    );
+}
+
+#[test]
+fn split_into_blocks() {
+    let mut writer = PbfWriter::new(vec![]).block_size(8);
+    for id in 0..20 {
+        let node = Node::new(point! { x: id as f64 * 1e-3, y: 0.0 }, OsmEntryId::node(id));
+        writer
+            .write(&ProcessedElement::Node(node, Tags::default()))
+            .unwrap();
+    }
+
+    let blocks = read(writer.finish().unwrap());
+    let nodes = blocks
+        .iter()
+        .flat_map(|block| block.element_iter())
+        .filter(|element| matches!(element, ProcessedElement::Node(..)))
+        .count();
+
+    // A header block, followed by blocks of 8, 8 and 4 nodes.
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(nodes, 20);
+}
+
+#[test]
+fn round_trip_fixture() {
+    let path = fixture_path(DISTRICT_OF_COLUMBIA);
+
+    let elements = BlockIterator::new(path)
+        .unwrap()
+        .map(|block| block.expect("!"))
+        .flat_map(|block| block.element_iter().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut writer = PbfWriter::new(vec![]);
+    writer.write_all(&elements).unwrap();
+
+    let written = read(writer.finish().unwrap())
+        .iter()
+        .flat_map(|block| block.element_iter().collect::<Vec<_>>())
+        .map(|element| match element {
+            ProcessedElement::Node(node, _) => ((MemberType::Node, node.id.identifier()), element),
+            ProcessedElement::Way(ref way) => ((MemberType::Way, way.id().identifier()), element),
+            ProcessedElement::Relation(ref relation) => {
+                ((MemberType::Relation, relation.id), element)
+            }
+        })
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(written.len(), elements.len());
+    for element in &elements {
+        let written = match element {
+            ProcessedElement::Node(node, _) => &written[&(MemberType::Node, node.id.identifier())],
+            ProcessedElement::Way(way) => &written[&(MemberType::Way, way.id().identifier())],
+            ProcessedElement::Relation(relation) => &written[&(MemberType::Relation, relation.id)],
+        };
+
+        match (written, element) {
+            (ProcessedElement::Node(a, _), ProcessedElement::Node(b, _)) => {
+                assert!((a.position.x() - b.position.x()).abs() < 1e-6);
+                assert!((a.position.y() - b.position.y()).abs() < 1e-6);
+            }
+            (ProcessedElement::Way(a), ProcessedElement::Way(b)) => {
+                assert_eq!(**a.refs(), **b.refs());
+                assert_eq!(a.tags(), b.tags());
+            }
+            (ProcessedElement::Relation(a), ProcessedElement::Relation(b)) => {
+                assert_eq!(*a.refs, *b.refs);
+                assert_eq!(a.tags, b.tags);
+            }
+            _ => unreachable!("Elements are keyed by their type"),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Iterator over the elements of an `.osm` XML document
+
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::Tags;
+use crate::osm::error::CodecError;
+use crate::osm::parallel::Parallel;
+use crate::osm::xml::{open, read_element};
+
+/// Streams the [`ProcessedElement`]s of an `.osm` XML document, such as those
+/// exported by JOSM, as the [`ProcessedElementIterator`](crate::osm::ProcessedElementIterator)
+/// does for `.osm.pbf` files.
+///
+/// The document is read sequentially, whilst its elements
+/// are processed in parallel through the [`Parallel`] trait.
+pub struct XmlElementIterator {
+    elements: Elements,
+}
+
+impl XmlElementIterator {
+    /// Streams the elements of the file at the given path,
+    /// which is decompressed should it be gzipped (such as `.osm.gz`).
+    pub fn new(path: PathBuf) -> Result<XmlElementIterator, CodecError> {
+        Ok(Self::from_reader(open(&path)?))
+    }
+
+    /// Streams the elements of an uncompressed document.
+    pub fn from_reader(reader: impl BufRead + Send + 'static) -> XmlElementIterator {
+        let reader: Box<dyn BufRead + Send> = Box::new(reader);
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+
+        XmlElementIterator {
+            elements: Elements {
+                reader,
+                buf: Vec::new(),
+                node_tags: false,
+                exhausted: false,
+            },
+        }
+    }
+
+    /// Sets whether the tags of nodes are retained, as with
+    /// [`ProcessedElementIterator::node_tags`](crate::osm::ProcessedElementIterator::node_tags).
+    pub fn node_tags(mut self, node_tags: bool) -> Self {
+        self.elements.node_tags = node_tags;
+        self
+    }
+
+    /// Reads the elements in the order of the document, without parallelism.
+    /// Reading stops after the first element which cannot be read.
+    pub fn sequential(self) -> impl Iterator<Item = Result<ProcessedElement, CodecError>> {
+        self.elements
+    }
+}
+
+/// The sequential reader of the elements of a document.
+struct Elements {
+    reader: Reader<Box<dyn BufRead + Send>>,
+    buf: Vec<u8>,
+
+    /// Whether the tags of nodes are retained.
+    node_tags: bool,
+    exhausted: bool,
+}
+
+impl Elements {
+    fn read(&mut self) -> Result<Option<ProcessedElement>, CodecError> {
+        loop {
+            self.buf.clear();
+
+            let (start, empty) = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) => (start, false),
+                Event::Empty(start) => (start, true),
+                Event::Eof => return Ok(None),
+                _ => continue,
+            };
+
+            // Descend into the root, whose children are the elements
+            if start.name().as_ref() == b"osm" {
+                continue;
+            }
+
+            let element = match read_element(&mut self.reader, &start, empty, true)? {
+                Some(ProcessedElement::Node(node, _)) if !self.node_tags => {
+                    ProcessedElement::Node(node, Tags::default())
+                }
+                Some(element) => element,
+                None => continue,
+            };
+
+            return Ok(Some(element));
+        }
+    }
+}
+
+impl Iterator for Elements {
+    type Item = Result<ProcessedElement, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let element = self.read().transpose();
+        self.exhausted = !matches!(element, Some(Ok(_)));
+        element
+    }
+}
+
+impl Parallel for XmlElementIterator {
+    type Item<'a> = ProcessedElement;
+
+    fn for_each<F>(self, f: F) -> Result<(), CodecError>
+    where
+        F: Fn(ProcessedElement) + Send + Sync,
+    {
+        self.elements.par_bridge().try_for_each(|element| {
+            f(element?);
+            Ok(())
+        })
+    }
+
+    fn map_red<Map, Reduce, Identity, T>(
+        self,
+        map_op: Map,
+        red_op: Reduce,
+        ident: Identity,
+    ) -> Result<T, CodecError>
+    where
+        Map: Fn(ProcessedElement) -> T + Send + Sync,
+        Reduce: Fn(T, T) -> T + Send + Sync,
+        Identity: Fn() -> T + Send + Sync,
+        T: Send,
+    {
+        self.elements
+            .par_bridge()
+            .map(|element| element.map(&map_op))
+            .try_reduce(&ident, |a, b| Ok(red_op(a, b)))
+    }
+
+    fn par_red<Reduce, Identity, Combine, T>(
+        self,
+        fold_op: Reduce,
+        combine: Combine,
+        ident: Identity,
+    ) -> Result<T, CodecError>
+    where
+        Reduce: Fn(T, ProcessedElement) -> T + Send + Sync,
+        Identity: Fn() -> T + Send + Sync,
+        Combine: Fn(T, T) -> T + Send + Sync,
+        T: Send,
+    {
+        self.elements
+            .par_bridge()
+            .try_fold(&ident, |acc, element| Ok(fold_op(acc, element?)))
+            .try_reduce(&ident, |a, b| Ok(combine(a, b)))
+    }
+}
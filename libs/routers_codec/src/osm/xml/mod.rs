@@ -0,0 +1,175 @@
+//! Parses the elements of `OSM` XML documents, such as `.osm` and change (`.osc`) files,
+//! into the same [`ProcessedElement`]s as are decoded from `.osm.pbf` files.
+//!
+//! Source: https://wiki.openstreetmap.org/wiki/OSM_XML
+
+pub mod iterator;
+
+#[doc(hidden)]
+mod test;
+
+pub use iterator::XmlElementIterator;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use flate2::read::GzDecoder;
+use geo::point;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::{
+    OsmEntryId, Reference, References, Relation, Role, TagString, Tags, Way,
+};
+use crate::osm::error::CodecError;
+use crate::osm::relation::MemberType;
+use crate::primitive::Node;
+
+/// Opens the document at the path, which is decompressed should it be gzipped.
+pub(crate) fn open(path: &Path) -> Result<Box<dyn BufRead + Send>, CodecError> {
+    let file = File::open(path)?;
+
+    Ok(match path.extension().is_some_and(|ext| ext == "gz") {
+        true => Box::new(BufReader::new(GzDecoder::new(file))),
+        false => Box::new(BufReader::new(file)),
+    })
+}
+
+/// Recovers the unescaped value of the attribute, should it exist.
+pub(crate) fn attribute(start: &BytesStart, key: &str) -> Result<Option<String>, CodecError> {
+    let Some(attribute) = start
+        .try_get_attribute(key)
+        .map_err(quick_xml::Error::from)?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(attribute.unescape_value()?.into_owned()))
+}
+
+/// Parses the attribute, failing should it be missing or malformed.
+fn required<F: FromStr>(start: &BytesStart, key: &str) -> Result<F, CodecError> {
+    attribute(start, key)?
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| {
+            let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+            CodecError::MalformedXml(format!("<{name}> is missing a valid `{key}` attribute"))
+        })
+}
+
+/// Parses the attribute should it exist, failing only if it is malformed.
+fn optional<F: FromStr>(start: &BytesStart, key: &str) -> Result<Option<F>, CodecError> {
+    match attribute(start, key)? {
+        Some(_) => required(start, key).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn member(start: &BytesStart) -> Result<Reference, CodecError> {
+    let id: i64 = required(start, "ref")?;
+    let (id, member_type) = match attribute(start, "type")?.as_deref() {
+        Some("node") => (OsmEntryId::node(id), MemberType::Node),
+        Some("way") => (OsmEntryId::way(id), MemberType::Way),
+        Some("relation") => (OsmEntryId::relation(id), MemberType::Relation),
+        other => {
+            return Err(CodecError::MalformedXml(format!(
+                "<member> has an unknown type {other:?}"
+            )));
+        }
+    };
+
+    let role = attribute(start, "role")?.map(|role| Role(TagString::from(role)));
+    Ok(Reference::new(id, member_type, role))
+}
+
+/// Reads the `<node>`, `<way>` or `<relation>` opened by `start`, including its
+/// tags, node references and members, up to and including its closing tag.
+///
+/// Should `empty` be set, the element was self-closing, so has no children.
+/// Should `positioned` be set, a node missing its `lat` or `lon` is malformed,
+/// which is the case for all nodes but those deleted by a change.
+/// Other elements are skipped, yielding `None`.
+pub(crate) fn read_element<R: BufRead>(
+    reader: &mut Reader<R>,
+    start: &BytesStart,
+    empty: bool,
+    positioned: bool,
+) -> Result<Option<ProcessedElement>, CodecError> {
+    let mut buf = Vec::new();
+
+    let name = start.name();
+    if !matches!(name.as_ref(), b"node" | b"way" | b"relation") {
+        if !empty {
+            reader.read_to_end_into(name, &mut buf)?;
+        }
+
+        return Ok(None);
+    }
+
+    let id: i64 = required(start, "id")?;
+    let mut tags = HashMap::new();
+    let mut refs = vec![];
+
+    let mut open = !empty;
+    while open {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(child) | Event::Empty(child) => match child.name().as_ref() {
+                b"tag" => {
+                    let key: String = required(&child, "k")?;
+                    let value: String = required(&child, "v")?;
+                    tags.insert(TagString::from(key), TagString::from(value));
+                }
+                b"nd" => refs.push(Reference::without_role(
+                    OsmEntryId::node(required(&child, "ref")?),
+                    MemberType::Node,
+                )),
+                b"member" => refs.push(member(&child)?),
+                _ => {}
+            },
+            Event::End(end) if end.name() == name => open = false,
+            Event::Eof => {
+                return Err(CodecError::MalformedXml(format!(
+                    "unexpected end of file within element {id}"
+                )));
+            }
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    let tags = Tags::new(tags);
+    let element = match name.as_ref() {
+        b"node" => {
+            // Only deleted nodes need not carry their position
+            let (lat, lon) = if positioned {
+                (
+                    required::<f64>(start, "lat")?,
+                    required::<f64>(start, "lon")?,
+                )
+            } else {
+                (
+                    optional::<f64>(start, "lat")?.unwrap_or_default(),
+                    optional::<f64>(start, "lon")?.unwrap_or_default(),
+                )
+            };
+
+            let node = Node::new(point! { x: lon, y: lat }, OsmEntryId::node(id));
+            ProcessedElement::Node(node, tags)
+        }
+        b"way" => {
+            ProcessedElement::Way(Way::new(OsmEntryId::way(id), References::from(refs), tags))
+        }
+        _ => ProcessedElement::Relation(Relation {
+            id,
+            tags,
+            refs: References::from(refs),
+        }),
+    };
+
+    Ok(Some(element))
+}
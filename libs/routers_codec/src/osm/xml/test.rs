@@ -0,0 +1,118 @@
+#![cfg(test)]
+
+use crate::osm::element::item::ProcessedElement;
+use crate::osm::element::variants::OsmEntryId;
+use crate::osm::{Parallel, XmlElementIterator};
+
+const NETWORK: &str = r#"<?xml version='1.0' encoding='UTF-8'?>
+<osm version='0.6' generator='JOSM'>
+  <bounds minlat='38.89' minlon='-77.04' maxlat='38.90' maxlon='-77.03' />
+  <node id='-1' action='modify' visible='true' lat='38.8977' lon='-77.0365'>
+    <tag k='highway' v='traffic_signals' />
+  </node>
+  <node id='-2' action='modify' visible='true' lat='38.8990' lon='-77.0300' />
+  <node id='-3' action='modify' visible='true' lat='38.8999' lon='-77.0310' />
+  <way id='-4' action='modify' visible='true'>
+    <nd ref='-1' />
+    <nd ref='-2' />
+    <nd ref='-3' />
+    <tag k='highway' v='residential' />
+  </way>
+  <relation id='-5' action='modify' visible='true'>
+    <member type='way' ref='-4' role='' />
+    <tag k='type' v='route' />
+    <tag k='route' v='bus' />
+  </relation>
+</osm>
+"#;
+
+fn elements(node_tags: bool) -> Vec<ProcessedElement> {
+    XmlElementIterator::from_reader(NETWORK.as_bytes())
+        .node_tags(node_tags)
+        .sequential()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("could not read document")
+}
+
+#[test]
+fn read_elements() {
+    let elements = elements(true);
+    assert_eq!(elements.len(), 5);
+
+    let ProcessedElement::Node(node, tags) = &elements[0] else {
+        panic!("expected a node");
+    };
+
+    assert_eq!(node.id, OsmEntryId::node(-1));
+    assert_eq!(node.position.x(), -77.0365);
+    assert_eq!(
+        tags.get("highway").map(|v| v.as_str()),
+        Some("traffic_signals")
+    );
+
+    let ProcessedElement::Way(way) = &elements[3] else {
+        panic!("expected a way");
+    };
+
+    assert_eq!(way.refs().len(), 3);
+    assert_eq!(way.tags().road_tag(), Some("residential"));
+
+    let ProcessedElement::Relation(relation) = &elements[4] else {
+        panic!("expected a relation");
+    };
+
+    assert_eq!(relation.route(), Some("bus"));
+}
+
+#[test]
+fn omit_node_tags() {
+    let tagged = elements(false)
+        .iter()
+        .filter(|element| matches!(element, ProcessedElement::Node(_, tags) if !tags.is_empty()))
+        .count();
+
+    assert_eq!(tagged, 0);
+}
+
+#[test]
+fn parallel_elements() {
+    let (nodes, ways) = XmlElementIterator::from_reader(NETWORK.as_bytes())
+        .par_red(
+            |(nodes, ways), element| match element {
+                ProcessedElement::Node(..) => (nodes + 1, ways),
+                ProcessedElement::Way(_) => (nodes, ways + 1),
+                _ => (nodes, ways),
+            },
+            |a, b| (a.0 + b.0, a.1 + b.1),
+            || (0, 0),
+        )
+        .expect("could not read document");
+
+    assert_eq!((nodes, ways), (3, 1));
+
+    let relations = XmlElementIterator::from_reader(NETWORK.as_bytes())
+        .map_red(
+            |element| matches!(element, ProcessedElement::Relation(_)) as usize,
+            |a, b| a + b,
+            || 0,
+        )
+        .expect("could not read document");
+
+    assert_eq!(relations, 1);
+}
+
+#[test]
+fn reject_malformed_document() {
+    let truncated = "<osm><way id='1'><nd ref='2'/>";
+    let mut iter = XmlElementIterator::from_reader(truncated.as_bytes()).sequential();
+
+    assert!(matches!(iter.next(), Some(Err(_))));
+    assert!(iter.next().is_none());
+
+    let result = XmlElementIterator::from_reader(truncated.as_bytes()).for_each(|_| {});
+    assert!(result.is_err());
+
+    let unpositioned = "<osm><node id='1' lon='2'/></osm>";
+    let mut iter = XmlElementIterator::from_reader(unpositioned.as_bytes()).sequential();
+    assert!(matches!(iter.next(), Some(Err(_))));
+}
@@ -1,3 +1,4 @@
+use crate::osm::primitives::Directionality;
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -18,5 +19,59 @@ pub trait Metadata: Clone + Debug + Send + Sync {
     where
         Self: 'a;
 
+    /// The conditions under which an edge is traversed at runtime,
+    /// such as the transport mode of the trip being matched or routed.
+    type Runtime: Default + Clone + Debug + Eq + Hash + Send + Sync;
+
+    /// The metadata of the nodes joining edges, such as barriers or traffic signals.
+    type Node: NodeMetadata<Runtime = Self::Runtime>;
+
     fn pick(raw: Self::Raw<'_>) -> Self;
+
+    /// The runtime conditions by which the accessibility of edges may be cached and
+    /// shared between traversals, or `None` should it depend upon the trip itself,
+    /// such as a restriction conditional upon the time of travel.
+    fn cache_key(runtime: &Self::Runtime) -> Option<Self::Runtime> {
+        Some(runtime.clone())
+    }
+
+    /// Determines if the edge described by the metadata may be traversed
+    /// under the given runtime conditions, in the given direction.
+    ///
+    /// The direction is relative to how the edge was drawn, where
+    /// [`Directionality::Forward`] follows it from source to target.
+    fn accessible(&self, runtime: &Self::Runtime, direction: Directionality) -> bool;
+
+    /// The multiplicative penalty of traversing the edge under the given runtime
+    /// conditions, given it is [accessible](Self::accessible). Edges which may be
+    /// traversed, but are undesirable to, have a penalty above `1.0`.
+    fn penalty(&self, _runtime: &Self::Runtime) -> f64 {
+        1.0
+    }
+}
+
+/// The metadata of a node which affects the traversal of the edges leading into it,
+/// such as a barrier or traffic signal. Nodes without such metadata are not stored.
+pub trait NodeMetadata: Clone + Debug + Send + Sync {
+    type Raw<'a>
+    where
+        Self: 'a;
+
+    type Runtime;
+
+    /// Picks the metadata from the raw node, should the node carry any.
+    fn pick(raw: Self::Raw<'_>) -> Option<Self>;
+
+    /// Determines if the node may be passed through under the given runtime
+    /// conditions, such as a bollard which cannot be passed by car.
+    fn accessible(&self, _runtime: &Self::Runtime) -> bool {
+        true
+    }
+
+    /// The fixed cost of passing through the node under the given runtime conditions,
+    /// in units of edge weight, which is added to the weight of the edge into the node
+    /// regardless of its length. Nodes which delay the trip have a cost above `0`.
+    fn cost(&self, _runtime: &Self::Runtime) -> u32 {
+        0
+    }
 }
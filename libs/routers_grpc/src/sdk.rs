@@ -1,16 +1,18 @@
 //! Defines internal translations and relevant utilities
 //! in order to make the model useful as an SDK.
 
-use crate::r#match::{MatchRequest, MatchResponse, SnapRequest};
+use crate::r#match::{MatchRequest, MatchResponse, SnapRequest, TraceCleaning};
 use crate::model::{Coordinate, EdgeIdentifier, EdgeMetadata, NodeIdentifier};
 
 use codec::osm::TraversalConditions;
 use codec::osm::meta::OsmEdgeMetadata;
-use codec::osm::speed_limit::SpeedLimitExt;
+use codec::osm::primitives::Turn;
 use codec::{Entry, Node};
 use geo::{Coord, LineString, coord};
+use routers::{CleanedTrace, TraceCleaner};
 use std::fmt::Error as StdError;
 use std::ops::Deref;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 impl From<Coord> for Coordinate {
     fn from(value: Coord) -> Self {
@@ -81,17 +83,41 @@ type MetadataAndTraversal<'a> = (&'a OsmEdgeMetadata, &'a TraversalConditions);
 
 impl From<MetadataAndTraversal<'_>> for EdgeMetadata {
     fn from((meta, cond): MetadataAndTraversal<'_>) -> Self {
-        // TODO: Fill all the information out here...
+        let destinations = meta.names.destinations(cond.directionality);
+
         EdgeMetadata {
-            lane_count: meta.lane_count.map(|v| v.get() as u32),
-            speed_limit: meta
-                .speed_limit
-                .as_ref()
-                .map(|v| v.relevant_limits(cond.clone()))
-                .and_then(|v| v.first().map(|elem| elem.speed))
-                .and_then(|v| v.in_kmh())
-                .map(|speed| speed.get() as u32),
-            names: vec![],
+            lane_count: meta
+                .lanes
+                .count(cond.directionality)
+                .map(|v| v.get() as u32),
+            speed_limit: meta.max_speed(cond).map(|speed| speed.get() as u32),
+            toll: meta.tolls.applies(cond.transport_mode),
+            ferry: meta.ferry.is_some(),
+            turn_lanes: meta
+                .lanes
+                .directed(cond.directionality)
+                .map(|lanes| {
+                    lanes
+                        .turns
+                        .iter()
+                        .map(|turns| turns.iter().map(Turn::as_ref).collect::<Vec<_>>().join(";"))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            names: meta.names.names().map(String::from).collect(),
+            refs: meta.names.refs().map(String::from).collect(),
+            destinations: destinations
+                .map(|destinations| {
+                    destinations
+                        .destination
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            destination_refs: destinations
+                .map(|destinations| destinations.refs.iter().map(|d| d.to_string()).collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -114,16 +140,87 @@ where
     }
 }
 
+/// Converts the timestamps of a request into offsets from the unix epoch.
+/// Returns `None` if no timestamps were given, or any are not representable.
+fn durations(timestamps: &[prost_types::Timestamp]) -> Option<Vec<Duration>> {
+    if timestamps.is_empty() {
+        return None;
+    }
+
+    timestamps
+        .iter()
+        .map(|timestamp| {
+            SystemTime::try_from(*timestamp)
+                .ok()?
+                .duration_since(UNIX_EPOCH)
+                .ok()
+        })
+        .collect()
+}
+
+impl From<&TraceCleaning> for TraceCleaner {
+    fn from(cleaning: &TraceCleaning) -> Self {
+        let mut cleaner = TraceCleaner::default();
+
+        if let Some(max_speed) = cleaning.max_speed {
+            cleaner = cleaner.max_speed(max_speed);
+        }
+        if let Some(min_distance) = cleaning.min_distance {
+            cleaner = cleaner.min_distance(min_distance);
+        }
+        if let Some(min_interval) = cleaning
+            .min_interval
+            .and_then(|d| Duration::try_from(d).ok())
+        {
+            cleaner = cleaner.min_interval(min_interval);
+        }
+
+        cleaner
+    }
+}
+
+/// Cleans the trace of a request should it ask to be, otherwise retaining every point as given.
+fn trace(
+    linestring: LineString,
+    durations: Option<Vec<Duration>>,
+    cleaning: Option<&TraceCleaning>,
+) -> CleanedTrace {
+    match cleaning {
+        Some(cleaning) => TraceCleaner::from(cleaning).clean(linestring, durations.as_deref()),
+        None => CleanedTrace::from(linestring),
+    }
+}
+
 impl MatchRequest {
     pub fn linestring(self) -> LineString {
         Into::<LineString>::into(Coordinates(self.data))
     }
+
+    pub fn durations(&self) -> Option<Vec<Duration>> {
+        durations(&self.timestamps)
+    }
+
+    /// The trace to be matched, cleaned only if the request asks for it.
+    pub fn trace(self) -> CleanedTrace {
+        let (durations, cleaning) = (self.durations(), self.cleaning);
+        trace(self.linestring(), durations, cleaning.as_ref())
+    }
 }
 
 impl SnapRequest {
     pub fn linestring(self) -> LineString {
         Into::<LineString>::into(Coordinates(self.data))
     }
+
+    pub fn durations(&self) -> Option<Vec<Duration>> {
+        durations(&self.timestamps)
+    }
+
+    /// The trace to be snapped, cleaned only if the request asks for it.
+    pub fn trace(self) -> CleanedTrace {
+        let (durations, cleaning) = (self.durations(), self.cleaning);
+        trace(self.linestring(), durations, cleaning.as_ref())
+    }
 }
 
 impl TryFrom<MatchResponse> for LineString {
@@ -8,7 +8,7 @@ use crate::definition::model::*;
 
 use crate::services::{RouteService, RuntimeContext};
 use codec::{Entry, Metadata};
-use routers::{Match, Path, RoutedPath};
+use routers::{CleanedTrace, Match, Path, PathElement, RoutedPath};
 #[cfg(feature = "telemetry")]
 use tracing::Level;
 
@@ -19,41 +19,76 @@ impl<Ctx> Util<Ctx> {
         <geo::Point as Into<Coord>>::into(point).into()
     }
 
+    fn route_element<E: Entry, M: Metadata>(
+        entry: &PathElement<E, M>,
+        ctx: &Ctx,
+    ) -> Option<RouteElement>
+    where
+        Ctx: RuntimeContext,
+        EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
+    {
+        let edge = EdgeBuilder::default()
+            .id(entry.edge.id().identifier())
+            .source(entry.edge.source)
+            .target(entry.edge.target)
+            .metadata(EdgeMetadata::from((
+                &entry.metadata,
+                &ctx.toward(entry.edge.id.directionality()),
+            )))
+            .length(Geodesic.distance(entry.edge.source.position, entry.edge.target.position))
+            .build()
+            .unwrap();
+
+        RouteElementBuilder::default()
+            .coordinate(Util::<Ctx>::coordinate_from_point(entry.point))
+            .edge(RouteEdge {
+                edge: Some(edge),
+                ..RouteEdge::default()
+            })
+            .build()
+            .ok()
+    }
+
     fn route_from_path<E: Entry, M: Metadata>(input: Path<E, M>, ctx: &Ctx) -> Vec<RouteElement>
     where
+        Ctx: RuntimeContext,
         EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
     {
         input
             .iter()
-            .flat_map(|entry| {
-                let edge = EdgeBuilder::default()
-                    .id(entry.edge.id().identifier())
-                    .source(entry.edge.source)
-                    .target(entry.edge.target)
-                    .metadata(EdgeMetadata::from((&entry.metadata, ctx)))
-                    .length(
-                        Geodesic.distance(entry.edge.source.position, entry.edge.target.position),
-                    )
-                    .build()
-                    .unwrap();
-
-                RouteElementBuilder::default()
-                    .coordinate(Util::<Ctx>::coordinate_from_point(entry.point))
-                    .edge(RouteEdge {
-                        edge: Some(edge),
-                        ..RouteEdge::default()
-                    })
-                    .build()
-            })
+            .flat_map(|entry| Util::route_element(entry, ctx))
             .collect::<Vec<_>>()
     }
 
-    fn process<E: Entry, M: Metadata>(result: RoutedPath<E, M>, ctx: Ctx) -> Vec<MatchedRoute>
+    #[allow(clippy::result_large_err)]
+    fn process<E: Entry, M: Metadata>(
+        result: RoutedPath<E, M>,
+        trace: &CleanedTrace,
+        ctx: Ctx,
+    ) -> Result<Vec<MatchedRoute>, Status>
     where
+        Ctx: RuntimeContext,
         EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
     {
         let interpolated = Util::route_from_path(result.interpolated, &ctx);
-        let discretized = Util::route_from_path(result.discretized, &ctx);
+
+        // Align the discretized route with the retained points, leaving an empty
+        // element for each point which could not be matched, such that expanding
+        // it reports every original input point against its own match.
+        let retained = result
+            .discretized
+            .by_layer(trace.linestring.0.len())
+            .into_iter()
+            .map(|entry| {
+                entry
+                    .and_then(|entry| Util::route_element(entry, &ctx))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let discretized = trace
+            .expand(&retained)
+            .ok_or_else(|| Status::internal("Could not align the match with the trace"))?;
 
         let matched_route = MatchedRoute {
             interpolated,
@@ -61,14 +96,14 @@ impl<Ctx> Util<Ctx> {
             cost: 0,
         };
 
-        vec![matched_route]
+        Ok(vec![matched_route])
     }
 }
 
 #[tonic::async_trait]
 impl<E, M, Ctx> MatchService for RouteService<E, M, Ctx>
 where
-    M: Metadata + 'static,
+    M: Metadata<Runtime = Ctx> + 'static,
     E: Entry + 'static,
     Ctx: RuntimeContext + 'static,
     EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
@@ -79,17 +114,18 @@ where
         request: Request<MatchRequest>,
     ) -> Result<Response<MatchResponse>, Status> {
         let map_match = request.into_inner();
-        let coordinates = map_match.linestring();
+        let runtime = Ctx::from_costing(map_match.costing_method(), map_match.vehicle.as_ref());
+        let trace = map_match.trace();
 
         let result = self
             .graph
-            .r#match(coordinates)
+            .r#match(trace.linestring.clone(), &runtime)
             .map_err(|e| e.to_string())
             .map_err(Status::internal)?;
 
         // TODO: Vector to allow trip-splitting in the future.
         Ok(Response::new(MatchResponse {
-            matches: Util::<Ctx>::process(result, Ctx::new()),
+            matches: Util::<Ctx>::process(result, &trace, runtime)?,
         }))
     }
 
@@ -99,16 +135,114 @@ where
         request: Request<SnapRequest>,
     ) -> Result<Response<SnapResponse>, Status> {
         let map_match = request.into_inner();
-        let coordinates = map_match.linestring();
+        let runtime = Ctx::from_costing(map_match.costing_method(), map_match.vehicle.as_ref());
+        let trace = map_match.trace();
 
         let result = self
             .graph
-            .snap(coordinates)
+            .snap(trace.linestring.clone(), &runtime)
             .map_err(|e| e.to_string())
             .map_err(Status::internal)?;
 
         Ok(Response::new(SnapResponse {
-            matches: Util::<Ctx>::process(result, Ctx::new()),
+            matches: Util::<Ctx>::process(result, &trace, runtime)?,
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::osm::meta::OsmEdgeMetadata;
+    use codec::osm::primitives::TransportMode;
+    use codec::osm::{OsmEntryId, TraversalConditions};
+    use geo::LineString;
+    use routers::{Graph, TraceCleaner};
+    use std::io::Write;
+
+    /// A road drawn from west to east along the equator.
+    fn road() -> Graph<OsmEntryId, OsmEdgeMetadata> {
+        let nodes = (1..=11)
+            .map(|id| {
+                format!(
+                    "<node id='{id}' lat='0.0' lon='{}' />",
+                    0.002 * (id - 1) as f64
+                )
+            })
+            .collect::<String>();
+        let refs = (1..=11)
+            .map(|id| format!("<nd ref='{id}' />"))
+            .collect::<String>();
+
+        let network = format!(
+            "<?xml version='1.0' encoding='UTF-8'?><osm version='0.6'>{nodes}\
+             <way id='100'>{refs}<tag k='highway' v='primary' /></way></osm>"
+        );
+
+        let mut file = tempfile::Builder::new().suffix(".osm").tempfile().unwrap();
+        file.write_all(network.as_bytes()).unwrap();
+        Graph::load(file.path().into()).unwrap()
+    }
+
+    #[test]
+    fn aligns_unmatched_points() {
+        let graph = road();
+        let runtime = TraversalConditions::new(TransportMode::Motorcar);
+
+        // A duplicated first point, which is cleaned away, and a
+        // middle point far beyond the reach of the road.
+        let input = LineString::from(vec![
+            (0.003, 0.0001),
+            (0.003, 0.0001),
+            (0.007, 0.0001),
+            (0.011, 0.05),
+            (0.015, 0.0001),
+            (0.019, 0.0001),
+        ]);
+
+        let trace = TraceCleaner::default().clean(input, None);
+        let result = graph.r#match(trace.linestring.clone(), &runtime).unwrap();
+        let matched = Util::process(result, &trace, runtime).unwrap().remove(0);
+
+        // Each original point is reported against the match of its own retained point.
+        let expected = [
+            Some(0.003),
+            Some(0.003),
+            Some(0.007),
+            None,
+            Some(0.015),
+            Some(0.019),
+        ];
+        assert_eq!(matched.discretized.len(), expected.len());
+
+        for (element, expected) in matched.discretized.iter().zip(expected) {
+            let longitude = element.coordinate.map(|coordinate| coordinate.longitude);
+
+            match (longitude, expected) {
+                (Some(longitude), Some(expected)) => assert!((longitude - expected).abs() < 0.0015),
+                (longitude, expected) => assert_eq!(longitude, expected),
+            }
+        }
+    }
+
+    #[test]
+    fn cleans_on_request() {
+        let coordinate = |longitude| Coordinate {
+            longitude,
+            latitude: 0.0,
+        };
+        let request = MatchRequest {
+            data: vec![coordinate(0.003), coordinate(0.003), coordinate(0.007)],
+            ..MatchRequest::default()
+        };
+
+        // Without asking to be cleaned, every point is matched as given.
+        assert_eq!(request.clone().trace().indices(), &[0, 1, 2]);
+
+        let cleaned = MatchRequest {
+            cleaning: Some(TraceCleaning::default()),
+            ..request
+        };
+        assert_eq!(cleaned.trace().indices(), &[0, 2]);
+    }
+}
@@ -1,5 +1,6 @@
+use crate::model::{Costing, Vehicle};
 use codec::osm::meta::OsmEdgeMetadata;
-use codec::osm::{OsmEntryId, TraversalConditions};
+use codec::osm::{Avoidance, OsmEntryId, TraversalConditions, VehicleProfile};
 use codec::{Entry, Metadata};
 use routers::Graph;
 use std::marker::PhantomData;
@@ -13,6 +14,15 @@ pub mod proximity;
 
 pub trait RuntimeContext: Send + Sync {
     fn new() -> Self;
+
+    /// Creates the context for the costing method and vehicle requested.
+    fn from_costing(costing: Costing, vehicle: Option<&Vehicle>) -> Self;
+
+    /// The context of travel along an edge in the given direction.
+    fn toward(&self, direction: Directionality) -> Self;
+
+    /// The context which avoids the tolls, ferries and motorways requested.
+    fn avoid(self, avoid: Avoidance) -> Self;
 }
 
 pub struct RouteService<E, M, Ctx>
@@ -43,7 +53,52 @@ impl RuntimeContext for TraversalConditions {
         TraversalConditions {
             directionality: Directionality::BothWays,
             transport_mode: TransportMode::Vehicle,
-            lane: None,
+            ..TraversalConditions::default()
+        }
+    }
+
+    fn from_costing(costing: Costing, vehicle: Option<&Vehicle>) -> Self {
+        let transport_mode = match costing {
+            Costing::Unspecified | Costing::Car => TransportMode::Motorcar,
+            Costing::Bicycle => TransportMode::Bicycle,
+            Costing::Foot => TransportMode::Foot,
+            Costing::Bus => TransportMode::Bus,
+            Costing::Truck => TransportMode::Hgv,
+        };
+
+        TraversalConditions::new(transport_mode)
+            .vehicle(vehicle.map(VehicleProfile::from).unwrap_or_default())
+    }
+
+    fn toward(&self, direction: Directionality) -> Self {
+        TraversalConditions::toward(self, direction)
+    }
+
+    fn avoid(self, avoid: Avoidance) -> Self {
+        TraversalConditions::avoid(self, avoid)
+    }
+}
+
+impl From<&Vehicle> for VehicleProfile {
+    fn from(vehicle: &Vehicle) -> Self {
+        let mut profile = VehicleProfile::default().hazmat(vehicle.hazmat);
+
+        if let Some(weight) = vehicle.weight {
+            profile = profile.weight(weight);
         }
+        if let Some(axleload) = vehicle.axleload {
+            profile = profile.axleload(axleload);
+        }
+        if let Some(height) = vehicle.height {
+            profile = profile.height(height);
+        }
+        if let Some(width) = vehicle.width {
+            profile = profile.width(width);
+        }
+        if let Some(length) = vehicle.length {
+            profile = profile.length(length);
+        }
+
+        profile
     }
 }
@@ -1,4 +1,4 @@
-use geo::{Point, coord};
+use geo::{Distance, Geodesic, Point, coord};
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
@@ -6,17 +6,144 @@ use crate::definition::model::*;
 use crate::definition::optimise::*;
 
 use crate::services::{RouteService, RuntimeContext};
-use codec::{Entry, Metadata};
-use routers::Route;
+use codec::osm::Avoidance;
+use codec::{Entry, Metadata, Node};
+use routers::{AlternativeOptions, Route};
 #[cfg(feature = "telemetry")]
 use tracing::Level;
 
+impl RouteRequest {
+    /// The start and end points of the request.
+    ///
+    /// The error is the [`Status`] the service responds with, hence is not boxed.
+    #[allow(clippy::result_large_err)]
+    fn points(&self) -> Result<(Point, Point), Status> {
+        let start = self
+            .start
+            .map_or(
+                Err(Status::invalid_argument("Missing Start Coordinate")),
+                |v| Ok(coord! { x: v.longitude, y: v.latitude }),
+            )
+            .map_err(|err| Status::internal(format!("{:?}", err)))?;
+
+        let end = self
+            .end
+            .map_or(
+                Err(Status::invalid_argument("Missing End Coordinate")),
+                |v| Ok(coord! { x: v.longitude, y: v.latitude }),
+            )
+            .map_err(|err| Status::internal(format!("{:?}", err)))?;
+
+        Ok((Point(start), Point(end)))
+    }
+
+    /// The tolls, ferries and motorways the request avoids.
+    fn avoidance(&self) -> Avoidance {
+        Avoidance::default()
+            .tolls(self.avoid_tolls)
+            .ferries(self.avoid_ferries)
+            .motorways(self.avoid_motorways)
+    }
+
+    /// The runtime context of the request.
+    fn runtime<Ctx: RuntimeContext>(&self) -> Ctx {
+        Ctx::from_costing(self.costing_method(), self.vehicle.as_ref()).avoid(self.avoidance())
+    }
+}
+
+/// The maximum number of alternatives generated for a request,
+/// as each may cost several searches of the graph.
+const MAX_ALTERNATIVES: u32 = 5;
+
+impl AlternativesRequest {
+    /// The options of the request, limiting the count to [`MAX_ALTERNATIVES`].
+    ///
+    /// The error is the [`Status`] the service responds with, hence is not boxed.
+    #[allow(clippy::result_large_err)]
+    fn options(&self) -> Result<AlternativeOptions, Status> {
+        let mut options = AlternativeOptions::default().method(match self.method() {
+            AlternativeMethod::Unspecified | AlternativeMethod::Plateau => {
+                routers::AlternativeMethod::Plateau
+            }
+            AlternativeMethod::Penalty => routers::AlternativeMethod::Penalty,
+        });
+
+        if let Some(count) = self.count {
+            options = options.count(count.min(MAX_ALTERNATIVES) as usize);
+        }
+        if let Some(max_overlap) = self.max_overlap {
+            if !(0.0..=1.0).contains(&max_overlap) {
+                return Err(Status::invalid_argument(
+                    "Max Overlap must be within [0, 1]",
+                ));
+            }
+
+            options = options.max_overlap(max_overlap);
+        }
+        if let Some(max_stretch) = self.max_stretch {
+            if !max_stretch.is_finite() || max_stretch < 1.0 {
+                return Err(Status::invalid_argument(
+                    "Max Stretch must be finite and at least 1",
+                ));
+            }
+
+            options = options.max_stretch(max_stretch);
+        }
+
+        Ok(options)
+    }
+}
+
+impl<E, M, Ctx> RouteService<E, M, Ctx>
+where
+    M: Metadata<Runtime = Ctx>,
+    E: Entry,
+    Ctx: RuntimeContext,
+    EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
+{
+    /// The response describing the route, including the
+    /// metadata of each edge as traversed under the runtime.
+    fn respond(&self, (cost, route): (u32, Vec<Node<E>>), runtime: &Ctx) -> RouteResponse {
+        let shape = route
+            .iter()
+            .map(|node| Coordinate {
+                latitude: node.position.y(),
+                longitude: node.position.x(),
+            })
+            .collect();
+
+        let edges = route
+            .windows(2)
+            .filter_map(|pair| {
+                let [source, target] = pair else {
+                    return None;
+                };
+
+                let (edge, metadata) = self.graph.edge_between(source.id, target.id)?;
+                let runtime = runtime.toward(edge.directionality());
+
+                EdgeBuilder::default()
+                    .id(edge.index().identifier())
+                    .source(*source)
+                    .target(*target)
+                    .metadata(EdgeMetadata::from((metadata, &runtime)))
+                    .length(Geodesic.distance(source.position, target.position))
+                    .build()
+                    .ok()
+            })
+            .collect();
+
+        RouteResponse { cost, shape, edges }
+    }
+}
+
 #[tonic::async_trait]
 impl<E, M, Ctx> OptimiseService for RouteService<E, M, Ctx>
 where
-    M: Metadata + 'static,
+    M: Metadata<Runtime = Ctx> + 'static,
     E: Entry + 'static,
     Ctx: RuntimeContext + 'static,
+    EdgeMetadata: for<'a> From<(&'a M, &'a Ctx)>,
 {
     #[cfg_attr(feature="telemetry", tracing::instrument(skip_all, err(level = Level::INFO)))]
     async fn route(
@@ -24,36 +151,113 @@ where
         request: Request<RouteRequest>,
     ) -> Result<Response<RouteResponse>, Status> {
         let (_, _, routing) = request.into_parts();
+        let (start, end) = routing.points()?;
+        let runtime = routing.runtime::<Ctx>();
 
-        let start = routing
-            .start
-            .map_or(
-                Err(Status::invalid_argument("Missing Start Coordinate")),
-                |v| Ok(coord! { x: v.longitude, y: v.latitude }),
-            )
-            .map_err(|err| Status::internal(format!("{:?}", err)))?;
+        self.graph
+            .route_points(start, end, &runtime)
+            .map(|route| Response::new(self.respond(route, &runtime)))
+            .ok_or_else(|| Status::internal("Could not route"))
+    }
 
-        let end = routing
-            .end
-            .map_or(
-                Err(Status::invalid_argument("Missing End Coordinate")),
-                |v| Ok(coord! { x: v.longitude, y: v.latitude }),
-            )
-            .map_err(|err| Status::internal(format!("{:?}", err)))?;
+    #[cfg_attr(feature="telemetry", tracing::instrument(skip_all, err(level = Level::INFO)))]
+    async fn alternatives(
+        self: Arc<Self>,
+        request: Request<AlternativesRequest>,
+    ) -> Result<Response<AlternativesResponse>, Status> {
+        let (_, _, alternatives) = request.into_parts();
+        let routing = alternatives
+            .route
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("Missing Route"))?;
+
+        let (start, end) = routing.points()?;
+        let runtime = routing.runtime::<Ctx>();
+
+        let routes = self
+            .graph
+            .route_alternatives(start, end, &runtime, &alternatives.options()?)
+            .into_iter()
+            .map(|route| self.respond(route, &runtime))
+            .collect::<Vec<_>>();
+
+        if routes.is_empty() {
+            return Err(Status::internal("Could not route"));
+        }
+
+        Ok(Response::new(AlternativesResponse { routes }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    fn options(request: AlternativesRequest) -> Result<AlternativeOptions, Code> {
+        request.options().map_err(|status| status.code())
+    }
+
+    #[test]
+    fn limits_count() {
+        let request = AlternativesRequest {
+            count: Some(u32::MAX),
+            ..AlternativesRequest::default()
+        };
+
+        assert_eq!(options(request).unwrap().count, MAX_ALTERNATIVES as usize);
+    }
+
+    #[test]
+    fn rejects_non_finite_values() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let overlap = AlternativesRequest {
+                max_overlap: Some(value),
+                ..AlternativesRequest::default()
+            };
+            let stretch = AlternativesRequest {
+                max_stretch: Some(value),
+                ..AlternativesRequest::default()
+            };
+
+            assert_eq!(options(overlap).unwrap_err(), Code::InvalidArgument);
+            assert_eq!(options(stretch).unwrap_err(), Code::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn rejects_overlap_out_of_range() {
+        for max_overlap in [-0.1, 1.1] {
+            let request = AlternativesRequest {
+                max_overlap: Some(max_overlap),
+                ..AlternativesRequest::default()
+            };
+
+            assert_eq!(options(request).unwrap_err(), Code::InvalidArgument);
+        }
+
+        for max_overlap in [0.0, 1.0] {
+            let request = AlternativesRequest {
+                max_overlap: Some(max_overlap),
+                ..AlternativesRequest::default()
+            };
+
+            assert_eq!(options(request).unwrap().max_overlap, max_overlap);
+        }
+    }
+
+    #[test]
+    fn rejects_stretch_below_one() {
+        let request = AlternativesRequest {
+            max_stretch: Some(0.9),
+            ..AlternativesRequest::default()
+        };
+        assert_eq!(options(request).unwrap_err(), Code::InvalidArgument);
 
-        self.graph.route_points(Point(start), Point(end)).map_or(
-            Err(Status::internal("Could not route")),
-            |(cost, route)| {
-                let shape = route
-                    .iter()
-                    .map(|node| Coordinate {
-                        latitude: node.position.y(),
-                        longitude: node.position.x(),
-                    })
-                    .collect();
-
-                Ok(Response::new(RouteResponse { cost, shape }))
-            },
-        )
+        let request = AlternativesRequest {
+            max_stretch: Some(1.0),
+            ..AlternativesRequest::default()
+        };
+        assert_eq!(options(request).unwrap().max_stretch, 1.0);
     }
 }
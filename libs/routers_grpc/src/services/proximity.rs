@@ -33,15 +33,14 @@ where
             None => return Err(Status::invalid_argument("Missing Coordinate")),
         };
 
-        let nearest_point = self.graph.scan_node(point).map_or(
-            Err(Status::internal("Could not find appropriate point")),
-            |coord| {
-                Ok(Coordinate {
-                    longitude: coord.position.x(),
-                    latitude: coord.position.y(),
-                })
-            },
-        )?;
+        let nearest_point = self
+            .graph
+            .scan_node(point)
+            .map(|coord| Coordinate {
+                longitude: coord.position.x(),
+                latitude: coord.position.y(),
+            })
+            .ok_or_else(|| Status::internal("Could not find appropriate point"))?;
 
         Ok(Response::new(PointResponse {
             coordinate: Some(nearest_point),
@@ -106,15 +105,13 @@ where
             dist_to_a.partial_cmp(&dist_to_b).unwrap_or(Ordering::Equal)
         });
 
-        let nearest_point = nearest_points.first().map_or(
-            Err(Status::internal("Could not find appropriate point")),
-            |(coord, _)| {
-                Ok(Coordinate {
-                    longitude: coord.0.x,
-                    latitude: coord.0.y,
-                })
-            },
-        )?;
+        let nearest_point = nearest_points
+            .first()
+            .map(|(coord, _)| Coordinate {
+                longitude: coord.0.x,
+                latitude: coord.0.y,
+            })
+            .ok_or_else(|| Status::internal("Could not find appropriate point"))?;
 
         Ok(Response::new(PointSnappedResponse {
             coordinate: Some(nearest_point),
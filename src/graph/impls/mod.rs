@@ -1 +1,5 @@
 pub mod osm;
+pub mod update;
+
+#[cfg(test)]
+mod test;
@@ -1,16 +1,17 @@
 use crate::graph::item::{Graph, GraphStructure};
 
 use codec::osm::OsmEntryId;
+use codec::osm::country::{CoarseBoundaries, CountryResolver};
 use codec::osm::element::ProcessedElement;
-use codec::osm::{Parallel, ProcessedElementIterator};
-use codec::{Metadata, Node};
+use codec::osm::{Parallel, ProcessedElementIterator, XmlElementIterator};
+use codec::{Node, NodeMetadata};
 
 use log::{debug, info};
 use rstar::RTree;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{DirectionAwareEdgeId, Edge, FatEdge, PredicateCache};
-use codec::osm::meta::OsmEdgeMetadata;
+use crate::{DirectionAwareEdgeId, Edge, FatEdge};
+use codec::osm::meta::{OsmEdgeMetadata, OsmMetadata};
 use std::error::Error;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
@@ -19,38 +20,68 @@ use std::time::Instant;
 pub type OsmGraph = Graph<OsmEntryId, OsmEdgeMetadata>;
 
 impl OsmGraph {
-    /// Creates a graph from a `.osm.pbf` file, using the `ProcessedElementIterator`
+    /// Creates a graph from a `.osm.pbf` file, using the `ProcessedElementIterator`,
+    /// or from an `.osm` XML file (optionally gzipped), using the `XmlElementIterator`.
+    ///
+    /// The format is decided by the extension of the file.
     pub fn new(filename: std::ffi::OsString) -> Result<Self, Box<dyn Error>> {
-        let mut start_time = Instant::now();
-        let fixed_start_time = Instant::now();
+        Self::load(filename)
+    }
+}
 
+/// Ingestion of any [`OsmMetadata`], such that a custom metadata type,
+/// whose fields are extracted by [`TagRule`](codec::osm::query::TagRule)s,
+/// may be routed upon in place of [`OsmEdgeMetadata`].
+impl<M> Graph<OsmEntryId, M>
+where
+    M: OsmMetadata,
+{
+    /// Creates a graph of the metadata from a file, as [`OsmGraph::new`] does.
+    pub fn load(filename: std::ffi::OsString) -> Result<Self, Box<dyn Error>> {
+        let start_time = Instant::now();
         let path = PathBuf::from(filename);
 
-        let reader = ProcessedElementIterator::new(path).map_err(|err| format!("{err:?}"))?;
+        let name = path.to_string_lossy();
+        if name.ends_with(".osm") || name.ends_with(".osm.gz") || name.ends_with(".xml") {
+            let reader = XmlElementIterator::new(path)?.node_tags(true);
+
+            debug!("Iterator warming took: {:?}", start_time.elapsed());
+            return Self::ingest(reader);
+        }
+
+        let reader = ProcessedElementIterator::new(path)?.node_tags(true);
 
         debug!("Iterator warming took: {:?}", start_time.elapsed());
-        start_time = Instant::now();
+        Self::ingest(reader)
+    }
+
+    /// Creates a graph from the elements of the reader.
+    fn ingest<R>(reader: R) -> Result<Self, Box<dyn Error>>
+    where
+        R: for<'a> Parallel<Item<'a> = ProcessedElement>,
+    {
+        let mut start_time = Instant::now();
+        let fixed_start_time = Instant::now();
 
         info!("Ingesting...");
 
         let global_graph = Mutex::new(GraphStructure::new());
         let meta = Mutex::new(FxHashMap::default());
+        let node_meta = Mutex::new(FxHashMap::default());
 
         let (nodes, edges): (Vec<Node<OsmEntryId>>, Vec<Edge<OsmEntryId>>) = reader.par_red(
             |mut trees: (Vec<Node<OsmEntryId>>, Vec<Edge<OsmEntryId>>),
              element: ProcessedElement| {
                 match element {
                     ProcessedElement::Way(way) => {
-                        let metadata = OsmEdgeMetadata::pick(way.tags());
-                        // If way is not traversable (/ is not road)
-                        if metadata.road_class.is_none() {
-                            return trees;
-                        }
+                        let metadata = M::pick(way.tags());
 
-                        // Get the weight from the weight table
-                        let weight = metadata.road_class.unwrap().weighting();
+                        // If way is not traversable (/ is neither road nor ferry)
+                        let segments = way.refs().len().saturating_sub(1);
+                        let Some(weight) = metadata.weighting(segments) else {
+                            return trees;
+                        };
 
-                        let bidirectional = !way.tags().unidirectional();
                         let _ = meta.lock().unwrap().insert(way.id(), metadata);
 
                         // Update with all adjacent nodes
@@ -63,18 +94,22 @@ impl OsmGraph {
                                 trees.1.push(Edge::from((a.id, b.id, &w)));
                                 lock.add_edge(a.id, b.id, w);
 
-                                // If way is bidi, add opposite edge with a DirAw backward.
-                                if bidirectional {
-                                    let w = (weight, direction_aware.backward());
-                                    trees.1.push(Edge::from((b.id, a.id, &w)));
-                                    lock.add_edge(b.id, a.id, w);
-                                }
+                                // Add the opposite edge with a DirAw backward. Whether either
+                                // direction may be traversed (i.e. one-way streets) depends on
+                                // the transport mode, so is decided by the metadata at runtime.
+                                let w = (weight, direction_aware.backward());
+                                trees.1.push(Edge::from((b.id, a.id, &w)));
+                                lock.add_edge(b.id, a.id, w);
                             } else {
                                 debug!("Edge windowing produced odd-sized entry: {edge:?}");
                             }
                         });
                     }
-                    ProcessedElement::Node(node) => {
+                    ProcessedElement::Node(node, tags) => {
+                        if let Some(metadata) = M::Node::pick(&tags) {
+                            let _ = node_meta.lock().unwrap().insert(node.id, metadata);
+                        }
+
                         // Add the node to the graph
                         trees.0.push(node);
                     }
@@ -89,13 +124,21 @@ impl OsmGraph {
                 a_tree
             },
             || (Vec::new(), Vec::new()),
-        );
+        )?;
 
         let graph = global_graph.into_inner().unwrap();
 
         debug!("Graphical ingestion took: {:?}", start_time.elapsed());
         start_time = Instant::now();
 
+        // Only retain the metadata of nodes which are part of the graph
+        let node_meta = node_meta
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .filter(|(node, _)| graph.contains_node(*node))
+            .collect();
+
         let mut hash = FxHashMap::default();
         let filtered = {
             nodes
@@ -136,16 +179,59 @@ impl OsmGraph {
             fixed_start_time.elapsed().as_millis()
         );
 
-        Ok(Graph {
+        let mut graph = Graph {
             graph,
             hash,
 
             meta: meta.into_inner().unwrap(),
+            node_meta,
 
             index: tree,
             index_edge: tree_edge,
 
-            cache: Arc::new(Mutex::new(PredicateCache::default())),
-        })
+            cache: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        start_time = Instant::now();
+        graph.resolve_countries(&CoarseBoundaries);
+        debug!("Country resolution took: {:?}", start_time.elapsed());
+
+        Ok(graph)
+    }
+
+    /// Resolves the country of each way from the position of its first resolvable edge,
+    /// upon which its implicit speed limit depends.
+    ///
+    /// Ingestion resolves countries from the built-in [`CoarseBoundaries`], which leaves
+    /// ways near a border unresolved. Call this with [`CountryPolygons`](codec::osm::country::CountryPolygons)
+    /// to resolve them precisely, including after [applying](Self::apply) a change.
+    pub fn resolve_countries(&mut self, resolver: &impl CountryResolver) {
+        let mut resolved = FxHashSet::default();
+
+        for edge in self.index_edge.iter() {
+            if let Some(meta) = self.meta.get_mut(&edge.id.index())
+                && !resolved.contains(&edge.id.index())
+            {
+                let country = resolver.country(edge.source.position);
+                if country.is_some() {
+                    resolved.insert(edge.id.index());
+                }
+
+                meta.locate(country);
+            }
+        }
+
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+impl OsmGraph {
+    /// The number of traffic signals passed along the route.
+    pub fn traffic_signals(&self, route: &[Node<OsmEntryId>]) -> usize {
+        route
+            .iter()
+            .filter_map(|node| self.node_metadata(&node.id))
+            .filter(|meta| meta.traffic_signals)
+            .count()
     }
 }
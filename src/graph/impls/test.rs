@@ -0,0 +1,314 @@
+use crate::graph::traits::util::build_graph;
+use crate::graph::{Graph, Route};
+use crate::impls::osm::OsmGraph;
+use crate::impls::update::ChangeSummary;
+
+use codec::osm::element::Tags;
+use codec::osm::primitives::{Directionality, RoadClass};
+use codec::osm::query::TagRule;
+use codec::osm::{OsmChange, OsmEntryId, OsmMetadata, OsmNodeMetadata, TraversalConditions};
+use codec::{Entry, Metadata};
+use geo::Point;
+use rstar::AABB;
+use std::sync::LazyLock;
+
+/// A start and finish joined by a primary road (weight 10) and a secondary road (14).
+fn graph() -> OsmGraph {
+    build_graph(
+        &[
+            (1, 0.0, 0.0),
+            (2, 0.01, 0.0),
+            (3, 0.02, 0.0),
+            (4, 0.01, 0.005),
+        ],
+        &[
+            (100, &[1, 2, 3], &[("highway", "primary")]),
+            (101, &[1, 4, 3], &[("highway", "secondary")]),
+        ],
+    )
+}
+
+fn route(graph: &OsmGraph) -> Option<(u32, Vec<i64>)> {
+    let (start, finish) = (OsmEntryId::node(1), OsmEntryId::node(3));
+    let (weight, route) = graph.route_nodes(start, finish, &TraversalConditions::default())?;
+
+    Some((
+        weight,
+        route.iter().map(|node| node.id.identifier()).collect(),
+    ))
+}
+
+fn change(body: &str) -> OsmChange {
+    format!(r#"<osmChange version="0.6">{body}</osmChange>"#)
+        .parse()
+        .expect("could not read change")
+}
+
+#[test]
+fn delete_way() {
+    let mut graph = graph();
+    assert_eq!(route(&graph), Some((10, vec![1, 2, 3])));
+
+    let summary = graph.apply(&change(r#"<delete><way id="100"/><node id="2"/></delete>"#));
+
+    assert_eq!(summary.ways, 1);
+    assert_eq!(route(&graph), Some((14, vec![1, 4, 3])));
+
+    // The orphaned node, and the edges of the way, are no longer indexed.
+    assert_eq!(graph.size(), 3);
+    assert_eq!(graph.index().size(), 3);
+    assert_eq!(graph.index_edge().size(), 4);
+    assert_eq!(graph.get_position(&OsmEntryId::node(2)), None);
+}
+
+#[test]
+fn create_way() {
+    let mut graph = graph();
+
+    let summary = graph.apply(&change(
+        r#"<create>
+            <node id="5" lat="-0.002" lon="0.01"><tag k="highway" v="traffic_signals"/></node>
+            <way id="102">
+                <nd ref="1"/><nd ref="5"/><nd ref="3"/>
+                <tag k="highway" v="motorway"/>
+            </way>
+            <way id="103"><nd ref="1"/><nd ref="99"/><tag k="highway" v="primary"/></way>
+        </create>"#,
+    ));
+
+    assert_eq!(
+        summary,
+        ChangeSummary {
+            ways: 1,
+            nodes: 0,
+            skipped: 1
+        }
+    );
+
+    let (weight, nodes) = route(&graph).unwrap();
+    assert_eq!(nodes, vec![1, 5, 3]);
+    assert!(weight < 10);
+
+    assert!(graph.node_metadata(&OsmEntryId::node(5)).is_some());
+    assert_eq!(graph.index_edge().size(), 12);
+}
+
+#[test]
+fn modify_way_and_node() {
+    let mut graph = graph();
+
+    // The primary road is downgraded, and its middle node moved.
+    let summary = graph.apply(&change(
+        r#"<modify>
+            <node id="2" lat="0.001" lon="0.011"/>
+            <way id="100"><nd ref="1"/><nd ref="2"/><nd ref="3"/><tag k="highway" v="residential"/></way>
+        </modify>"#,
+    ));
+
+    assert_eq!(summary.ways, 1);
+    assert_eq!(summary.nodes, 1);
+    assert_eq!(route(&graph), Some((14, vec![1, 4, 3])));
+
+    let moved = Point::new(0.011, 0.001);
+    assert_eq!(graph.get_position(&OsmEntryId::node(2)), Some(moved));
+
+    let nearest = graph.index().nearest_neighbor(&moved).unwrap();
+    assert_eq!(nearest.id, OsmEntryId::node(2));
+
+    // The edges of the moved node are re-indexed at its new position.
+    let edges = graph
+        .index_edge()
+        .locate_in_envelope_intersecting(&AABB::from_point(moved))
+        .filter(|edge| edge.source.position == moved || edge.target.position == moved)
+        .count();
+    assert_eq!(edges, 4);
+    assert_eq!(graph.index_edge().size(), 8);
+
+    // Modifying the way such that it is no longer a road removes it.
+    graph.apply(&change(
+        r#"<modify><way id="100"><nd ref="1"/><nd ref="2"/><nd ref="3"/></way></modify>"#,
+    ));
+
+    assert_eq!(graph.size(), 3);
+    assert_eq!(graph.index_edge().size(), 4);
+}
+
+#[test]
+fn ingest_xml() {
+    let network = r#"<?xml version='1.0' encoding='UTF-8'?>
+<osm version='0.6' generator='JOSM'>
+  <node id='1' lat='0.0' lon='0.0' />
+  <node id='2' lat='0.0' lon='0.01'><tag k='highway' v='traffic_signals' /></node>
+  <node id='3' lat='0.0' lon='0.02' />
+  <node id='4' lat='0.005' lon='0.01' />
+  <way id='100'>
+    <nd ref='1' /><nd ref='2' /><nd ref='3' />
+    <tag k='highway' v='primary' />
+  </way>
+  <way id='101'>
+    <nd ref='1' /><nd ref='4' />
+    <tag k='waterway' v='stream' />
+  </way>
+</osm>"#;
+
+    let path = std::env::temp_dir().join("routers_ingest_xml.osm");
+    std::fs::write(&path, network).unwrap();
+
+    let graph = OsmGraph::new(path.into_os_string()).expect("could not ingest");
+
+    // The stream is not a road, so its node is not part of the graph.
+    assert_eq!(graph.size(), 3);
+    assert_eq!(route(&graph).map(|(_, nodes)| nodes), Some(vec![1, 2, 3]));
+    assert!(graph.node_metadata(&OsmEntryId::node(2)).is_some());
+}
+
+/// Metadata extracted declaratively, which prefers lit roads.
+#[derive(Debug, Clone)]
+struct LitMetadata {
+    road_class: Option<RoadClass>,
+    lit: bool,
+}
+
+static ROAD_CLASS: LazyLock<TagRule<RoadClass>> = LazyLock::new(|| TagRule::new("highway"));
+static LIT: LazyLock<TagRule<bool>> = LazyLock::new(|| {
+    TagRule::values("lit", [("yes", true), ("24/7", true), ("no", false)]).default(false)
+});
+
+impl Metadata for LitMetadata {
+    type Raw<'a> = &'a Tags;
+    type Runtime = TraversalConditions;
+    type Node = OsmNodeMetadata;
+
+    fn pick(raw: Self::Raw<'_>) -> Self {
+        Self {
+            road_class: ROAD_CLASS.value(raw),
+            lit: LIT.value(raw).unwrap_or_default(),
+        }
+    }
+
+    fn accessible(&self, _: &Self::Runtime, _: Directionality) -> bool {
+        true
+    }
+
+    fn penalty(&self, _: &Self::Runtime) -> f64 {
+        if self.lit { 1.0 } else { 2.0 }
+    }
+}
+
+impl OsmMetadata for LitMetadata {
+    fn weighting(&self, _: usize) -> Option<u32> {
+        self.road_class.map(|class| class.weighting())
+    }
+}
+
+#[test]
+fn ingest_custom_metadata() {
+    let network = r#"<?xml version='1.0' encoding='UTF-8'?>
+<osm version='0.6' generator='JOSM'>
+  <node id='1' lat='0.0' lon='0.0' />
+  <node id='2' lat='0.0' lon='0.01' />
+  <node id='3' lat='0.0' lon='0.02' />
+  <node id='4' lat='0.005' lon='0.01' />
+  <way id='100'>
+    <nd ref='1' /><nd ref='2' /><nd ref='3' />
+    <tag k='highway' v='primary' />
+  </way>
+  <way id='101'>
+    <nd ref='1' /><nd ref='4' /><nd ref='3' />
+    <tag k='highway' v='secondary' />
+    <tag k='lit' v='yes' />
+  </way>
+</osm>"#;
+
+    let path = std::env::temp_dir().join("routers_ingest_custom_metadata.osm");
+    std::fs::write(&path, network).unwrap();
+
+    let graph =
+        Graph::<OsmEntryId, LitMetadata>::load(path.into_os_string()).expect("could not ingest");
+
+    // The unlit primary road (10) is penalised beyond the lit secondary road (14).
+    let (start, finish) = (OsmEntryId::node(1), OsmEntryId::node(3));
+    let (weight, route) = graph
+        .route_nodes(start, finish, &TraversalConditions::default())
+        .unwrap();
+
+    assert_eq!(weight, 14);
+    assert_eq!(
+        route
+            .iter()
+            .map(|node| node.id.identifier())
+            .collect::<Vec<_>>(),
+        vec![1, 4, 3]
+    );
+}
+
+#[test]
+fn runtime_caches() {
+    use codec::osm::{TripContext, VehicleProfile};
+    use std::sync::Arc;
+
+    let graph = graph();
+    let car = TraversalConditions::default();
+    let cached = graph.cache(&car);
+    assert!(Arc::ptr_eq(&cached, &graph.cache(&car)));
+
+    // A trip of known circumstances is not shared, nor held once traversed.
+    let timed = car.clone().trip(TripContext::default().occupants(2));
+    assert!(!Arc::ptr_eq(&graph.cache(&timed), &graph.cache(&timed)));
+    assert_eq!(graph.cache.lock().unwrap().len(), 1);
+
+    // Only the most recently used caches are held.
+    for height in 1..=20 {
+        let vehicle = VehicleProfile::default().height(height as f64);
+        graph.cache(&car.clone().vehicle(vehicle));
+    }
+
+    assert_eq!(graph.cache.lock().unwrap().len(), 8);
+    assert!(!Arc::ptr_eq(&cached, &graph.cache(&car)));
+}
+
+#[test]
+fn modify_node_barrier() {
+    use crate::transition::{Candidates, RoutingContext};
+
+    let mut graph = graph();
+    let car = TraversalConditions::default();
+    let candidates = Candidates::default();
+
+    // Whether the cached predicates of the start reach the middle of the primary road.
+    let reaches = |graph: &OsmGraph| {
+        let context = RoutingContext {
+            candidates: &candidates,
+            map: graph,
+            runtime: &car,
+        };
+
+        graph
+            .cache(&car)
+            .lock()
+            .unwrap()
+            .query(&context, OsmEntryId::node(1))
+            .contains_key(&OsmEntryId::node(2))
+    };
+
+    assert!(reaches(&graph));
+
+    // A bollard is placed on the primary road, without moving its node.
+    let summary = graph.apply(&change(
+        r#"<modify>
+            <node id="2" lat="0.0" lon="0.01"><tag k="barrier" v="bollard"/></node>
+        </modify>"#,
+    ));
+
+    assert_eq!(summary.nodes, 0);
+    assert!(!reaches(&graph));
+    assert_eq!(route(&graph), Some((14, vec![1, 4, 3])));
+
+    // Once lifted, the primary road may be driven again.
+    graph.apply(&change(
+        r#"<modify><node id="2" lat="0.0" lon="0.01"/></modify>"#,
+    ));
+
+    assert!(reaches(&graph));
+    assert_eq!(route(&graph), Some((10, vec![1, 2, 3])));
+}
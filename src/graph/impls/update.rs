@@ -0,0 +1,296 @@
+//! Incremental updates of an [`OsmGraph`](crate::impls::osm::OsmGraph) from OsmChange (`.osc`) files,
+//! such that the graph may follow replication diffs rather than be rebuilt.
+
+use crate::Graph;
+use crate::graph::Weight;
+use crate::{DirectionAwareEdgeId, FatEdge};
+
+use codec::osm::country::{CoarseBoundaries, CountryResolver};
+use codec::osm::element::variants::Way;
+use codec::osm::meta::OsmMetadata;
+use codec::osm::{ChangeAction, OsmChange, OsmEntryId};
+use codec::{Node, NodeMetadata};
+
+use geo::Point;
+use log::warn;
+use petgraph::Direction;
+use rstar::{AABB, Envelope, SelectionFunction};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::Entry;
+
+/// The outcome of applying an [`OsmChange`] to a graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangeSummary {
+    /// The number of ways created, modified or deleted within the graph.
+    pub ways: usize,
+    /// The number of nodes within the graph which were moved.
+    pub nodes: usize,
+    /// The number of ways which could not be added, as the position
+    /// of a node they reference is neither in the graph nor the change.
+    pub skipped: usize,
+}
+
+/// Selects the fat edge between two nodes, given the envelope it occupies.
+struct SelectEdge {
+    envelope: AABB<Point>,
+    source: OsmEntryId,
+    target: OsmEntryId,
+}
+
+impl SelectEdge {
+    fn new(source: &Node<OsmEntryId>, target: &Node<OsmEntryId>) -> Self {
+        SelectEdge {
+            envelope: AABB::from_corners(target.position, source.position),
+            source: source.id,
+            target: target.id,
+        }
+    }
+}
+
+impl SelectionFunction<FatEdge<OsmEntryId>> for SelectEdge {
+    fn should_unpack_parent(&self, envelope: &AABB<Point>) -> bool {
+        envelope.contains_envelope(&self.envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &FatEdge<OsmEntryId>) -> bool {
+        leaf.source.id == self.source && leaf.target.id == self.target
+    }
+}
+
+impl<M> Graph<OsmEntryId, M>
+where
+    M: OsmMetadata,
+{
+    /// Applies the created, modified and deleted nodes and ways of the change to the graph,
+    /// updating its edges, metadata and indices, and invalidating the cached predicates
+    /// of any node whose surroundings changed.
+    ///
+    /// As with ingestion, only roads and ferry routes are added. Since nodes which are
+    /// not part of a way within the graph are not retained, a created or modified way may
+    /// only be added if the positions of its nodes are in the graph or the change itself.
+    pub fn apply(&mut self, change: &OsmChange) -> ChangeSummary {
+        let mut summary = ChangeSummary::default();
+        let mut affected = FxHashSet::default();
+
+        // The positions of nodes not (yet) within the graph.
+        let mut positions = FxHashMap::default();
+        let mut deleted = vec![];
+
+        for (action, node, tags) in change.nodes() {
+            if action == ChangeAction::Delete {
+                deleted.push(node.id);
+                continue;
+            }
+
+            let previous = match M::Node::pick(tags) {
+                Some(metadata) => self.node_meta.insert(node.id, metadata),
+                None => self.node_meta.remove(&node.id),
+            };
+
+            // A barrier placed or lifted changes which edges lead through the node,
+            // and so the successors of the nodes leading into it.
+            if previous.as_ref() != self.node_meta.get(&node.id)
+                && self.graph.contains_node(node.id)
+            {
+                affected.insert(node.id);
+                affected.extend(self.graph.neighbors_directed(node.id, Direction::Incoming));
+            }
+
+            match self.hash.get(&node.id) {
+                Some(existing) if existing.position != node.position => {
+                    self.relocate(*node, &mut affected);
+                    summary.nodes += 1;
+                }
+                Some(_) => {}
+                None => {
+                    positions.insert(node.id, *node);
+                }
+            }
+        }
+
+        // Modified ways are removed, and re-added in their new form.
+        let removed = change
+            .ways()
+            .filter(|(action, _)| *action != ChangeAction::Create)
+            .map(|(_, way)| way.id())
+            .collect::<FxHashSet<_>>();
+
+        if !removed.is_empty() {
+            let edges = self
+                .graph
+                .all_edges()
+                .filter(|(_, _, (_, id))| removed.contains(&id.index()))
+                .map(|(source, target, _)| (source, target))
+                .collect::<Vec<_>>();
+
+            for (source, target) in edges {
+                self.remove_edge(source, target);
+                affected.extend([source, target]);
+            }
+
+            for way in &removed {
+                self.meta.remove(way);
+            }
+        }
+
+        for (action, way) in change.ways() {
+            match action {
+                ChangeAction::Delete => summary.ways += 1,
+                _ => match self.insert_way(way, &positions, &mut affected) {
+                    true => summary.ways += 1,
+                    false => summary.skipped += 1,
+                },
+            }
+        }
+
+        // Nodes left without edges are no longer part of the graph.
+        let orphaned = affected
+            .iter()
+            .chain(&deleted)
+            .copied()
+            .filter(|node| {
+                self.graph.contains_node(*node)
+                    && self
+                        .graph
+                        .neighbors_directed(*node, Direction::Outgoing)
+                        .next()
+                        .is_none()
+                    && self
+                        .graph
+                        .neighbors_directed(*node, Direction::Incoming)
+                        .next()
+                        .is_none()
+            })
+            .collect::<Vec<_>>();
+
+        for node in orphaned {
+            self.graph.remove_node(node);
+            if let Some(node) = self.hash.remove(&node) {
+                self.index.remove(&node);
+            }
+        }
+
+        let graph = &self.graph;
+        self.node_meta.retain(|node, _| graph.contains_node(*node));
+
+        for (_, cache) in self.cache.lock().unwrap().iter() {
+            cache.lock().unwrap().invalidate_nodes(&affected);
+        }
+
+        summary
+    }
+
+    /// Moves the node within the graph, and the edges which it joins.
+    fn relocate(&mut self, node: Node<OsmEntryId>, affected: &mut FxHashSet<OsmEntryId>) {
+        let edges = self
+            .graph
+            .edges_directed(node.id, Direction::Outgoing)
+            .chain(self.graph.edges_directed(node.id, Direction::Incoming))
+            .map(|(source, target, weight)| (source, target, *weight))
+            .collect::<Vec<_>>();
+
+        for (source, target, _) in &edges {
+            self.remove_fat_edge(*source, *target);
+            affected.extend([*source, *target]);
+        }
+
+        if let Some(previous) = self.hash.insert(node.id, node) {
+            self.index.remove(&previous);
+        }
+
+        self.index.insert(node);
+        affected.insert(node.id);
+
+        for (source, target, weight) in edges {
+            self.insert_fat_edge(source, target, weight);
+        }
+    }
+
+    /// Adds the edges of the way to the graph, returning `false` should the way
+    /// reference a node of unknown position, in which case it is not added.
+    ///
+    /// Ways which are neither roads nor ferry routes are not added, but are not considered skipped.
+    fn insert_way(
+        &mut self,
+        way: &Way,
+        positions: &FxHashMap<OsmEntryId, Node<OsmEntryId>>,
+        affected: &mut FxHashSet<OsmEntryId>,
+    ) -> bool {
+        let mut metadata = M::pick(way.tags());
+        let Some(weight) = metadata.weighting(way.refs().len().saturating_sub(1)) else {
+            return true;
+        };
+
+        let Some(nodes) = way
+            .refs()
+            .iter()
+            .map(|node| self.hash.get(&node.id).or(positions.get(&node.id)).copied())
+            .collect::<Option<Vec<_>>>()
+        else {
+            warn!("Way {:?} references a node of unknown position", way.id());
+            return false;
+        };
+
+        if nodes.len() < 2 {
+            return true;
+        }
+
+        let id = DirectionAwareEdgeId::new(way.id());
+
+        // As on ingestion, the country is resolved from the coarse boundaries.
+        metadata.locate(CoarseBoundaries.country(nodes[0].position));
+        self.meta.insert(way.id(), metadata);
+
+        for node in &nodes {
+            if let Entry::Vacant(entry) = self.hash.entry(node.id) {
+                entry.insert(*node);
+                self.index.insert(*node);
+            }
+        }
+
+        for pair in nodes.windows(2) {
+            let (a, b) = (pair[0].id, pair[1].id);
+
+            // Replace any existing edge, as ingestion would with the last way drawn.
+            for (source, target, id) in [(a, b, id.forward()), (b, a, id.backward())] {
+                self.remove_edge(source, target);
+                self.graph.add_edge(source, target, (weight, id));
+                self.insert_fat_edge(source, target, (weight, id));
+            }
+
+            affected.extend([a, b]);
+        }
+
+        true
+    }
+
+    /// Removes the edge, should it exist, from the graph and the edge index.
+    fn remove_edge(&mut self, source: OsmEntryId, target: OsmEntryId) {
+        if self.graph.remove_edge(source, target).is_some() {
+            self.remove_fat_edge(source, target);
+        }
+    }
+
+    fn remove_fat_edge(&mut self, source: OsmEntryId, target: OsmEntryId) {
+        if let (Some(source), Some(target)) = (self.hash.get(&source), self.hash.get(&target)) {
+            let selection = SelectEdge::new(source, target);
+            self.index_edge.remove_with_selection_function(selection);
+        }
+    }
+
+    fn insert_fat_edge(
+        &mut self,
+        source: OsmEntryId,
+        target: OsmEntryId,
+        (weight, id): (Weight, DirectionAwareEdgeId<OsmEntryId>),
+    ) {
+        if let (Some(source), Some(target)) = (self.hash.get(&source), self.hash.get(&target)) {
+            self.index_edge.insert(FatEdge {
+                source: *source,
+                target: *target,
+                weight,
+                id,
+            });
+        }
+    }
+}
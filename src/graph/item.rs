@@ -1,10 +1,10 @@
 use crate::{DirectionAwareEdgeId, FatEdge, PredicateCache};
-use codec::primitive::{Entry, Metadata, Node};
+use codec::primitive::{Entry, Metadata, Node, NodeMetadata};
 
 use geo::Point;
 use petgraph::prelude::DiGraphMap;
 use rstar::RTree;
-use rustc_hash::{FxHashMap, FxHasher};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
 use std::fmt::{Debug, Formatter};
 use std::hash::BuildHasherDefault;
@@ -19,6 +19,17 @@ pub type GraphStructure<E> =
 
 pub(crate) const MAX_WEIGHT: Weight = u32::MAX as Weight;
 
+/// The number of runtime conditions for which predicate caches are held.
+const MAX_RUNTIME_CACHES: usize = 8;
+
+/// The predicate caches of a graph, one for each set of runtime conditions
+/// the graph has been traversed under, since the edges which are accessible
+/// (and therefore the predicates) depend on these conditions.
+///
+/// The caches are ordered from the most recently used, such that the least
+/// recently used is evicted once [`MAX_RUNTIME_CACHES`] are held.
+pub type RuntimeCaches<E, M> = Vec<(<M as Metadata>::Runtime, Arc<Mutex<PredicateCache<E, M>>>)>;
+
 /// Routing graph.
 ///
 /// TODO: ... can be ingested from an `.osm.pbf` file, and can be actioned upon using `route(start, end)`.
@@ -30,11 +41,12 @@ where
     pub(crate) graph: GraphStructure<E>,
     pub(crate) hash: FxHashMap<E, Node<E>>,
     pub(crate) meta: FxHashMap<E, M>,
+    pub(crate) node_meta: FxHashMap<E, M::Node>,
 
     pub(crate) index: RTree<Node<E>>,
     pub(crate) index_edge: RTree<FatEdge<E>>,
 
-    pub(crate) cache: Arc<Mutex<PredicateCache<E, M>>>,
+    pub(crate) cache: Arc<Mutex<RuntimeCaches<E, M>>>,
 }
 
 impl<E, M> Debug for Graph<E, M>
@@ -69,6 +81,103 @@ where
         self.hash.get(node_index).map(|point| point.position)
     }
 
+    /// Determines if the direction-aware edge may be traversed under the runtime conditions.
+    #[inline]
+    pub fn accessible(&self, edge: &DirectionAwareEdgeId<E>, runtime: &M::Runtime) -> bool {
+        self.meta
+            .get(&edge.index())
+            .is_some_and(|meta| meta.accessible(runtime, edge.directionality()))
+    }
+
+    /// Determines if the node may be passed through under the runtime conditions,
+    /// such that the edges leading into an inaccessible node are not traversed.
+    #[inline]
+    pub fn node_accessible(&self, node: &E, runtime: &M::Runtime) -> bool {
+        self.node_meta
+            .get(node)
+            .is_none_or(|meta| meta.accessible(runtime))
+    }
+
+    /// Determines if any of the `targets` may be reached from any of the `sources`,
+    /// following the edges of the graph regardless of whether they are accessible.
+    pub(crate) fn reaches(
+        &self,
+        sources: impl IntoIterator<Item = E>,
+        targets: &FxHashSet<E>,
+    ) -> bool {
+        let mut stack = sources.into_iter().collect::<Vec<_>>();
+        let mut visited = FxHashSet::default();
+
+        while let Some(node) = stack.pop() {
+            if targets.contains(&node) {
+                return true;
+            }
+
+            if visited.insert(node) {
+                stack.extend(self.graph.neighbors(node));
+            }
+        }
+
+        false
+    }
+
+    /// The direction-aware edge from the `source` node to the adjacent
+    /// `target` node, and the metadata of the underlying edge.
+    #[inline]
+    pub fn edge_between(&self, source: E, target: E) -> Option<(&DirectionAwareEdgeId<E>, &M)> {
+        let (_, edge) = self.graph.edge_weight(source, target)?;
+        Some((edge, self.meta.get(&edge.index())?))
+    }
+
+    /// The metadata of the node, should it carry any, such as a barrier or traffic signal.
+    #[inline]
+    pub fn node_metadata(&self, node: &E) -> Option<&M::Node> {
+        self.node_meta.get(node)
+    }
+
+    /// The weight of traversing the direction-aware edge into the `target` node under the
+    /// runtime conditions, being its base weight multiplied by the [penalty](Metadata::penalty)
+    /// of the edge, plus the fixed [cost](NodeMetadata::cost) of the target node.
+    #[inline]
+    pub fn weight(
+        &self,
+        weight: Weight,
+        edge: &DirectionAwareEdgeId<E>,
+        target: &E,
+        runtime: &M::Runtime,
+    ) -> Weight {
+        let edge = self
+            .meta
+            .get(&edge.index())
+            .map_or(1.0, |meta| meta.penalty(runtime));
+
+        let node = self
+            .node_meta
+            .get(target)
+            .map_or(0, |meta| meta.cost(runtime));
+
+        (weight as f64 * edge + node as f64).min(MAX_WEIGHT as f64) as Weight
+    }
+
+    /// The predicate cache for the given runtime conditions, shared between traversals
+    /// under the same [cacheable](Metadata::cache_key) conditions. Those which are not
+    /// are given a cache of their own, which lives only as long as the traversal.
+    pub(crate) fn cache(&self, runtime: &M::Runtime) -> Arc<Mutex<PredicateCache<E, M>>> {
+        let Some(key) = M::cache_key(runtime) else {
+            return Arc::default();
+        };
+
+        let mut caches = self.cache.lock().unwrap();
+        let cache = match caches.iter().position(|(runtime, _)| *runtime == key) {
+            Some(index) => caches.remove(index).1,
+            None => Arc::default(),
+        };
+
+        caches.insert(0, (key, Arc::clone(&cache)));
+        caches.truncate(MAX_RUNTIME_CACHES);
+        cache
+    }
+
     #[inline]
     pub fn get_line(&self, nodes: &[E]) -> Vec<Point<f64>> {
         nodes
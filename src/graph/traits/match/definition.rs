@@ -14,10 +14,21 @@ where
     /// using the [`Transition`](crate::Transition) module
     /// to collapse the given input onto the map, finding
     /// appropriate matching for each input value.
-    fn r#match(&self, linestring: LineString) -> Result<RoutedPath<E, M>, MatchError>;
+    ///
+    /// Only edges accessible under the given runtime conditions,
+    /// such as the transport mode of the trip, are considered.
+    fn r#match(
+        &self,
+        linestring: LineString,
+        runtime: &M::Runtime,
+    ) -> Result<RoutedPath<E, M>, MatchError>;
 
     /// Snaps a given linestring against the map.
     ///
     /// TODO: Docs
-    fn snap(&self, linestring: LineString) -> Result<RoutedPath<E, M>, MatchError>;
+    fn snap(
+        &self,
+        linestring: LineString,
+        runtime: &M::Runtime,
+    ) -> Result<RoutedPath<E, M>, MatchError>;
 }
@@ -5,7 +5,6 @@ use crate::transition::*;
 use codec::{Entry, Metadata};
 use geo::LineString;
 use log::info;
-use std::sync::Arc;
 
 impl<E, M> Match<E, M> for Graph<E, M>
 where
@@ -13,16 +12,20 @@ where
     M: Metadata,
 {
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, level = Level::INFO))]
-    fn r#match(&self, linestring: LineString) -> Result<RoutedPath<E, M>, MatchError> {
+    fn r#match(
+        &self,
+        linestring: LineString,
+        runtime: &M::Runtime,
+    ) -> Result<RoutedPath<E, M>, MatchError> {
         info!("Finding matched route for {} positions", linestring.0.len());
         let costing = CostingStrategies::default();
 
         // Create our hidden markov model solver
-        let transition = Transition::new(self, linestring, costing);
+        let transition = Transition::new(self, linestring, costing, runtime);
 
         // Yield the transition layers of each level
         // & Collapse the layers into a final vector
-        let cache = Arc::clone(&self.cache);
+        let cache = self.cache(runtime);
         let solver = SelectiveForwardSolver::default().use_cache(cache);
 
         transition
@@ -31,7 +34,11 @@ where
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, level = Level::INFO))]
-    fn snap(&self, _linestring: LineString) -> Result<RoutedPath<E, M>, MatchError> {
+    fn snap(
+        &self,
+        _linestring: LineString,
+        _runtime: &M::Runtime,
+    ) -> Result<RoutedPath<E, M>, MatchError> {
         unimplemented!()
     }
 }
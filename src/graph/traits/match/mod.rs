@@ -2,3 +2,6 @@ pub mod definition;
 mod implementation;
 
 pub use definition::Match;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,149 @@
+use crate::graph::traits::util::build_graph;
+use crate::impls::osm::OsmGraph;
+use crate::transition::{
+    CollapseError, CollapsedPath, CostingStrategies, LegalityError, MatchError,
+    SelectiveForwardSolver,
+};
+use crate::{Match, Transition};
+
+use codec::osm::primitives::TransportMode;
+use codec::osm::{OsmEntryId, TraversalConditions};
+use geo::LineString;
+
+/// A road drawn from west to east, with the given tags.
+fn road(tags: &[(&str, &str)]) -> OsmGraph {
+    let nodes = (1..=11)
+        .map(|id| (id, 0.002 * (id - 1) as f64, 0.0))
+        .collect::<Vec<_>>();
+
+    build_graph(&nodes, &[(100, &(1..=11).collect::<Vec<_>>(), tags)])
+}
+
+/// A trace travelling east along the road, at the given offsets from it.
+fn eastward(offsets: &[f64]) -> LineString {
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(index, offset)| (0.002 + 0.004 * index as f64, 0.0001 + offset))
+        .collect()
+}
+
+fn solve(
+    graph: &OsmGraph,
+    trace: LineString,
+    runtime: &TraversalConditions,
+) -> Result<CollapsedPath<OsmEntryId>, MatchError> {
+    let transition = Transition::new(graph, trace, CostingStrategies::default(), runtime);
+    transition.solve(SelectiveForwardSolver::default())
+}
+
+fn r#match(graph: &OsmGraph, trace: LineString, mode: TransportMode) -> Result<(), MatchError> {
+    graph
+        .r#match(trace, &TraversalConditions::new(mode))
+        .map(|_| ())
+}
+
+#[test]
+fn matches_trace() {
+    let graph = road(&[("highway", "primary")]);
+    let car = TraversalConditions::new(TransportMode::Motorcar);
+    let collapsed = solve(&graph, eastward(&[0.0; 5]), &car).unwrap();
+
+    assert_eq!(collapsed.matched().len(), 5);
+    assert!(collapsed.validate(&graph, &car).is_ok());
+}
+
+#[test]
+fn validate_illegal_traversal() {
+    let graph = road(&[("highway", "motorway")]);
+    let car = TraversalConditions::new(TransportMode::Motorcar);
+    let collapsed = solve(&graph, eastward(&[0.0; 5]), &car).unwrap();
+
+    // The path matched by car may not be walked, since pedestrians may not use a motorway.
+    let foot = TraversalConditions::new(TransportMode::Foot);
+    assert!(matches!(
+        collapsed.validate(&graph, &foot),
+        Err(LegalityError::IllegalTraversal(100))
+    ));
+}
+
+#[test]
+fn against_one_way() {
+    let graph = road(&[("highway", "primary"), ("oneway", "-1")]);
+
+    // The road may only be driven westward, so an eastward trace has no legal match.
+    let result = r#match(&graph, eastward(&[0.0; 5]), TransportMode::Motorcar);
+    assert!(matches!(
+        result,
+        Err(MatchError::NoLegalMatch(LegalityError::NoLegalPath))
+    ));
+
+    // Whereas it may be walked in either direction.
+    assert!(r#match(&graph, eastward(&[0.0; 5]), TransportMode::Foot).is_ok());
+}
+
+#[test]
+fn disconnected_roads() {
+    // Two parallel roads which never meet, so no path joins them regardless of the transport mode.
+    let nodes = (1..=11)
+        .map(|id| (id, 0.002 * (id - 1) as f64, 0.0))
+        .chain((12..=22).map(|id| (id, 0.002 * (id - 12) as f64, 0.02)))
+        .collect::<Vec<_>>();
+
+    let tags = [("highway", "primary")];
+    let graph = build_graph(
+        &nodes,
+        &[
+            (100, &(1..=11).collect::<Vec<_>>(), &tags),
+            (200, &(12..=22).collect::<Vec<_>>(), &tags),
+        ],
+    );
+
+    // The trace jumps from the southern road onto the northern one.
+    let result = r#match(
+        &graph,
+        eastward(&[0.0, 0.0, 0.02, 0.02]),
+        TransportMode::Motorcar,
+    );
+    assert!(matches!(
+        result,
+        Err(MatchError::CollapseFailure(CollapseError::NoPathFound))
+    ));
+}
+
+#[test]
+fn unmatched_point() {
+    let graph = road(&[("highway", "primary")]);
+
+    // The third point lies far beyond the reach of the road.
+    let trace = eastward(&[0.0, 0.0, 0.05, 0.0, 0.0]);
+    let car = TraversalConditions::new(TransportMode::Motorcar);
+    let collapsed = solve(&graph, trace, &car).unwrap();
+
+    let layers = collapsed
+        .matched()
+        .iter()
+        .map(|candidate| candidate.location.layer_id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(layers, vec![0, 1, 3, 4]);
+}
+
+#[test]
+fn no_candidates() {
+    let graph = road(&[("highway", "primary")]);
+
+    let result = r#match(&graph, eastward(&[0.05; 3]), TransportMode::Motorcar);
+    assert!(matches!(
+        result,
+        Err(MatchError::NoLegalMatch(LegalityError::NoCandidates))
+    ));
+
+    // A motorway has no candidates for pedestrians, as it is not accessible to them.
+    let graph = road(&[("highway", "motorway")]);
+    let result = r#match(&graph, eastward(&[0.0; 3]), TransportMode::Foot);
+    assert!(matches!(
+        result,
+        Err(MatchError::NoLegalMatch(LegalityError::NoCandidates))
+    ));
+}
@@ -4,17 +4,26 @@ mod route;
 
 pub use r#match::Match;
 pub use proximity::Scan;
-pub use route::Route;
+pub use route::{AlternativeMethod, AlternativeOptions, Route};
 
 #[cfg(test)]
-mod util {
-    use crate::graph::Graph;
+pub(crate) mod util {
+    use crate::graph::{Graph, GraphStructure};
     use crate::impls::osm::OsmGraph;
+    use crate::{DirectionAwareEdgeId, FatEdge};
 
+    use codec::osm::OsmEntryId;
+    use codec::osm::element::Tags;
+    use codec::osm::meta::{OsmEdgeMetadata, OsmMetadata};
+    use codec::{Metadata, Node};
+    use geo::Point;
     use routers_fixtures::fixture_path;
+    use rstar::RTree;
+    use rustc_hash::FxHashMap;
 
     use std::error::Error;
     use std::path::Path;
+    use std::sync::{Arc, Mutex};
     use std::time::Instant;
 
     pub(crate) fn init_graph(file: &str) -> Result<OsmGraph, Box<dyn Error>> {
@@ -27,4 +36,53 @@ mod util {
         println!("Graph Init Took: {:?}", time.elapsed());
         Ok(graph)
     }
+
+    /// A way, as its id, the ids of the nodes it references, and its tags.
+    pub(crate) type TestWay<'a> = (i64, &'a [i64], &'a [(&'a str, &'a str)]);
+
+    /// Builds a graph from the given nodes, as `(id, x, y)`, and ways.
+    pub(crate) fn build_graph(nodes: &[(i64, f64, f64)], ways: &[TestWay]) -> OsmGraph {
+        let mut graph = GraphStructure::new();
+        let mut meta = FxHashMap::default();
+        let mut edges = vec![];
+
+        let hash = nodes
+            .iter()
+            .map(|(id, x, y)| {
+                let id = OsmEntryId::node(*id);
+                (id, Node::new(Point::new(*x, *y), id))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        for (id, refs, tags) in ways {
+            let metadata = OsmEdgeMetadata::pick(&tags.iter().copied().collect::<Tags>());
+            let weight = metadata.weighting(refs.len() - 1).unwrap();
+            let id = DirectionAwareEdgeId::new(OsmEntryId::way(*id));
+            meta.insert(id.index(), metadata);
+
+            for pair in refs.windows(2) {
+                let (a, b) = (OsmEntryId::node(pair[0]), OsmEntryId::node(pair[1]));
+
+                for (source, target, id) in [(a, b, id.forward()), (b, a, id.backward())] {
+                    graph.add_edge(source, target, (weight, id));
+                    edges.push(FatEdge {
+                        source: hash[&source],
+                        target: hash[&target],
+                        id,
+                        weight,
+                    });
+                }
+            }
+        }
+
+        Graph {
+            graph,
+            index: RTree::bulk_load(hash.values().copied().collect()),
+            index_edge: RTree::bulk_load(edges),
+            hash,
+            meta,
+            node_meta: FxHashMap::default(),
+            cache: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
 }
@@ -0,0 +1,392 @@
+use crate::graph::{Graph, Weight};
+
+use codec::{Entry, Metadata};
+use petgraph::Direction;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+const DEFAULT_COUNT: usize = 2;
+const DEFAULT_MAX_OVERLAP: f64 = 0.6;
+const DEFAULT_MAX_STRETCH: f64 = 1.4;
+const DEFAULT_PENALTY: f64 = 1.5;
+
+/// The maximum number of via-nodes evaluated by the [plateau](AlternativeMethod::Plateau) method.
+const MAX_VIA_CANDIDATES: usize = 512;
+
+/// The maximum number of searches performed per requested alternative
+/// by the [penalty](AlternativeMethod::Penalty) method.
+const MAX_PENALTY_ROUNDS: usize = 4;
+
+/// The method by which alternative routes are generated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlternativeMethod {
+    /// Builds a shortest-path tree from the start and (in reverse) from the finish.
+    /// Each alternative is the concatenation of the optimal routes to and from a
+    /// "via" node, where those with long plateaus (stretches in which both trees
+    /// agree) are preferred, as these represent genuinely distinct corridors.
+    #[default]
+    Plateau,
+
+    /// Repeatedly finds the optimal route, penalising the weight of every edge
+    /// used by a previous route, such that later searches diverge from them.
+    Penalty,
+}
+
+/// Options constraining the alternatives produced by [`Route::route_alternatives`](crate::Route::route_alternatives).
+#[derive(Clone, Debug)]
+pub struct AlternativeOptions {
+    /// The maximum number of alternatives, excluding the optimal route.
+    pub count: usize,
+
+    /// The maximum fraction (in `[0, 1]`) of an alternative's weight which
+    /// may be shared with the optimal route, or any other accepted alternative.
+    pub max_overlap: f64,
+
+    /// The maximum weight of an alternative, relative to the optimal route.
+    /// For example, `1.4` permits alternatives up to 40% costlier.
+    pub max_stretch: f64,
+
+    /// The method by which the alternatives are generated.
+    pub method: AlternativeMethod,
+
+    /// The factor by which the weight of a previously used edge is multiplied
+    /// when using the [penalty](AlternativeMethod::Penalty) method.
+    pub penalty: f64,
+}
+
+impl Default for AlternativeOptions {
+    fn default() -> Self {
+        Self {
+            count: DEFAULT_COUNT,
+            max_overlap: DEFAULT_MAX_OVERLAP,
+            max_stretch: DEFAULT_MAX_STRETCH,
+            method: AlternativeMethod::default(),
+            penalty: DEFAULT_PENALTY,
+        }
+    }
+}
+
+impl AlternativeOptions {
+    pub fn count(self, count: usize) -> Self {
+        Self { count, ..self }
+    }
+
+    pub fn max_overlap(self, max_overlap: f64) -> Self {
+        Self {
+            max_overlap,
+            ..self
+        }
+    }
+
+    pub fn max_stretch(self, max_stretch: f64) -> Self {
+        Self {
+            max_stretch,
+            ..self
+        }
+    }
+
+    pub fn method(self, method: AlternativeMethod) -> Self {
+        Self { method, ..self }
+    }
+
+    pub fn penalty(self, penalty: f64) -> Self {
+        Self { penalty, ..self }
+    }
+}
+
+/// A route through the graph, as the nodes visited and its total weight.
+#[derive(Clone, Debug)]
+pub(crate) struct Candidate<E> {
+    pub(crate) weight: Weight,
+    pub(crate) path: Vec<E>,
+}
+
+/// A shortest-path tree, holding the distance to each reached node and its parent.
+type Tree<E> = FxHashMap<E, (Weight, Option<E>)>;
+
+impl<E, M> Graph<E, M>
+where
+    E: Entry,
+    M: Metadata,
+{
+    /// The accessible edges adjacent to the node, as the neighbour and the weight to it.
+    ///
+    /// In the [`Direction::Incoming`] direction, the edges leading into the node are given.
+    fn adjacent(
+        &self,
+        node: E,
+        direction: Direction,
+        runtime: &M::Runtime,
+    ) -> impl Iterator<Item = (E, Weight)> {
+        self.graph
+            .edges_directed(node, direction)
+            .filter(move |(_, target, (_, id))| {
+                self.accessible(id, runtime) && self.node_accessible(target, runtime)
+            })
+            .map(move |(source, target, (weight, id))| {
+                let neighbour = match direction {
+                    Direction::Outgoing => target,
+                    Direction::Incoming => source,
+                };
+
+                (neighbour, self.weight(*weight, id, &target, runtime))
+            })
+    }
+
+    /// Builds the shortest-path tree from the root in the given direction,
+    /// exploring only nodes within the `bound` weight of the root.
+    fn tree(&self, root: E, direction: Direction, runtime: &M::Runtime, bound: Weight) -> Tree<E> {
+        let mut tree: Tree<E> = FxHashMap::default();
+        let mut heap = BinaryHeap::from([Reverse((0 as Weight, root, None))]);
+
+        while let Some(Reverse((distance, node, parent))) = heap.pop() {
+            if tree.contains_key(&node) {
+                continue;
+            }
+
+            tree.insert(node, (distance, parent));
+
+            for (next, weight) in self.adjacent(node, direction, runtime) {
+                let next_distance = distance.saturating_add(weight);
+                if next_distance <= bound && !tree.contains_key(&next) {
+                    heap.push(Reverse((next_distance, next, Some(node))));
+                }
+            }
+        }
+
+        tree
+    }
+
+    /// Walks the tree from the node towards its root.
+    fn walk(tree: &Tree<E>, node: E) -> impl Iterator<Item = E> {
+        std::iter::successors(Some(node), |node| {
+            tree.get(node).and_then(|(_, parent)| *parent)
+        })
+    }
+
+    /// The weight of the edge between two adjacent nodes under the runtime conditions.
+    fn edge_weight(&self, source: E, target: E, runtime: &M::Runtime) -> Option<Weight> {
+        let (weight, id) = self.graph.edge_weight(source, target)?;
+        Some(self.weight(*weight, id, &target, runtime))
+    }
+
+    /// The total weight of the route under the runtime conditions.
+    fn path_weight(&self, path: &[E], runtime: &M::Runtime) -> Weight {
+        path.windows(2)
+            .filter_map(|pair| self.edge_weight(pair[0], pair[1], runtime))
+            .sum()
+    }
+
+    /// The weight of the edges shared between the candidate and the route.
+    fn shared_weight(candidate: &FxHashMap<(E, E), Weight>, route: &[E]) -> Weight {
+        route
+            .windows(2)
+            .filter_map(|pair| candidate.get(&(pair[0], pair[1])))
+            .sum()
+    }
+
+    /// Selects the candidates which are within the stretch of the optimal route,
+    /// and overlap no accepted route by more than the maximum overlap, until the
+    /// requested number of alternatives have been accepted.
+    pub(crate) fn select_alternatives(
+        &self,
+        optimal: Candidate<E>,
+        candidates: impl IntoIterator<Item = Candidate<E>>,
+        runtime: &M::Runtime,
+        options: &AlternativeOptions,
+    ) -> Vec<Candidate<E>> {
+        let max_weight = (optimal.weight as f64 * options.max_stretch) as Weight;
+        let mut accepted = vec![optimal];
+
+        for candidate in candidates {
+            if accepted.len() > options.count {
+                break;
+            }
+
+            if candidate.weight > max_weight {
+                continue;
+            }
+
+            let edges = candidate
+                .path
+                .windows(2)
+                .filter_map(|pair| {
+                    let weight = self.edge_weight(pair[0], pair[1], runtime)?;
+                    Some(((pair[0], pair[1]), weight))
+                })
+                .collect::<FxHashMap<_, _>>();
+
+            let distinct = accepted.iter().all(|route| {
+                let shared = Self::shared_weight(&edges, &route.path) as f64;
+                shared <= options.max_overlap * candidate.weight.max(1) as f64
+            });
+
+            if distinct {
+                accepted.push(candidate);
+            }
+        }
+
+        accepted
+    }
+
+    /// Generates candidate alternatives using the [plateau](AlternativeMethod::Plateau) method,
+    /// returning the optimal route and the candidates, ordered by preference.
+    pub(crate) fn plateau_candidates(
+        &self,
+        start: E,
+        finish: E,
+        runtime: &M::Runtime,
+        options: &AlternativeOptions,
+    ) -> Option<(Candidate<E>, Vec<Candidate<E>>)> {
+        let path = self.penalised_route(start, finish, runtime, &FxHashMap::default())?;
+        let optimal = Candidate {
+            weight: self.path_weight(&path, runtime),
+            path,
+        };
+
+        // Only nodes within the stretch of the optimal route may be via-nodes.
+        let bound = (optimal.weight as f64 * options.max_stretch) as Weight;
+        let forward = self.tree(start, Direction::Outgoing, runtime, bound);
+        let backward = self.tree(finish, Direction::Incoming, runtime, bound);
+
+        // Every node reachable within the stretch is a possible via-node,
+        // evaluated in ascending order of the via route weight.
+        let mut vias = forward
+            .iter()
+            .filter_map(|(node, (to, _))| {
+                let (from, _) = backward.get(node)?;
+                let weight = to.saturating_add(*from);
+                (weight <= bound).then_some((weight, *node))
+            })
+            .collect::<Vec<_>>();
+
+        vias.sort_unstable();
+
+        let mut visited: FxHashSet<E> = optimal.path.iter().copied().collect();
+        let mut candidates = vec![];
+
+        for (weight, via) in vias {
+            if candidates.len() >= MAX_VIA_CANDIDATES {
+                break;
+            }
+
+            // Nodes upon an evaluated route share its plateau, and so its route.
+            if visited.contains(&via) {
+                continue;
+            }
+
+            let mut path = Self::walk(&forward, via).collect::<Vec<_>>();
+            path.reverse();
+            path.extend(Self::walk(&backward, via).skip(1));
+
+            // Routes which revisit a node are not simple, and contain a detour.
+            let unique = path.iter().copied().collect::<FxHashSet<_>>();
+            visited.extend(unique.iter().copied());
+            if unique.len() != path.len() {
+                continue;
+            }
+
+            // The plateau is the stretch of the route in which both trees agree,
+            // being the edges (u, v) where u is the parent of v in the forward
+            // tree, and v is the parent of u in the backward tree.
+            let plateau = path
+                .windows(2)
+                .filter(|pair| {
+                    forward
+                        .get(&pair[1])
+                        .is_some_and(|(_, p)| *p == Some(pair[0]))
+                        && backward
+                            .get(&pair[0])
+                            .is_some_and(|(_, p)| *p == Some(pair[1]))
+                })
+                .filter_map(|pair| self.edge_weight(pair[0], pair[1], runtime))
+                .sum::<Weight>();
+
+            candidates.push((weight.saturating_sub(plateau), Candidate { weight, path }));
+        }
+
+        // Prefer candidates with long plateaus relative to their weight.
+        candidates.sort_by_key(|(score, _)| *score);
+
+        Some((
+            optimal,
+            candidates
+                .into_iter()
+                .map(|(_, candidate)| candidate)
+                .collect(),
+        ))
+    }
+
+    /// Finds the optimal route, with each edge's weight multiplied by its penalty.
+    fn penalised_route(
+        &self,
+        start: E,
+        finish: E,
+        runtime: &M::Runtime,
+        penalties: &FxHashMap<(E, E), f64>,
+    ) -> Option<Vec<E>> {
+        let mut tree: Tree<E> = FxHashMap::default();
+        let mut heap = BinaryHeap::from([Reverse((0 as Weight, start, None))]);
+
+        while let Some(Reverse((distance, node, parent))) = heap.pop() {
+            if tree.contains_key(&node) {
+                continue;
+            }
+
+            tree.insert(node, (distance, parent));
+            if node == finish {
+                let mut path = Self::walk(&tree, finish).collect::<Vec<_>>();
+                path.reverse();
+                return Some(path);
+            }
+
+            for (next, weight) in self.adjacent(node, Direction::Outgoing, runtime) {
+                let penalty = penalties.get(&(node, next)).copied().unwrap_or(1.0);
+                let weight = (weight as f64 * penalty) as Weight;
+
+                if !tree.contains_key(&next) {
+                    heap.push(Reverse((distance.saturating_add(weight), next, Some(node))));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Generates candidate alternatives using the [penalty](AlternativeMethod::Penalty) method,
+    /// returning the optimal route and the candidates, ordered by preference.
+    pub(crate) fn penalty_candidates(
+        &self,
+        start: E,
+        finish: E,
+        runtime: &M::Runtime,
+        options: &AlternativeOptions,
+    ) -> Option<(Candidate<E>, Vec<Candidate<E>>)> {
+        let mut penalties: FxHashMap<(E, E), f64> = FxHashMap::default();
+        let mut routes: Vec<Candidate<E>> = vec![];
+
+        for _ in 0..=(options.count * MAX_PENALTY_ROUNDS) {
+            let Some(path) = self.penalised_route(start, finish, runtime, &penalties) else {
+                break;
+            };
+
+            for pair in path.windows(2) {
+                // Penalise both directions, such that the reverse of a used
+                // road is not considered a distinct alternative.
+                for edge in [(pair[0], pair[1]), (pair[1], pair[0])] {
+                    *penalties.entry(edge).or_insert(1.0) *= options.penalty;
+                }
+            }
+
+            let weight = self.path_weight(&path, runtime);
+
+            if !routes.iter().any(|route| route.path == path) {
+                routes.push(Candidate { weight, path });
+            }
+        }
+
+        let mut routes = routes.into_iter();
+        Some((routes.next()?, routes.collect()))
+    }
+}
@@ -1,16 +1,47 @@
 use crate::graph::Weight;
+use crate::graph::traits::route::AlternativeOptions;
 
-use codec::{Entry, Node};
+use codec::{Entry, Metadata, Node};
 use geo::Point;
 
-pub trait Route<E>
+pub trait Route<E, M>
 where
     E: Entry,
+    M: Metadata,
 {
-    /// TODO: Routes ...
-    fn route_nodes(&self, start_node: E, finish_node: E) -> Option<(Weight, Vec<Node<E>>)>;
+    /// Finds the optimal route between a start and finish node.
+    /// Returns the weight and routing node vector.
+    ///
+    /// Only edges accessible under the given runtime conditions,
+    /// such as the transport mode or vehicle profile, are traversed.
+    /// Those the runtime avoids, such as tolls or ferries, are penalised.
+    fn route_nodes(
+        &self,
+        start_node: E,
+        finish_node: E,
+        runtime: &M::Runtime,
+    ) -> Option<(Weight, Vec<Node<E>>)>;
 
     /// Finds the optimal route between a start and end point.
     /// Returns the weight and routing node vector.
-    fn route_points(&self, start: Point, finish: Point) -> Option<(Weight, Vec<Node<E>>)>;
+    fn route_points(
+        &self,
+        start: Point,
+        finish: Point,
+        runtime: &M::Runtime,
+    ) -> Option<(Weight, Vec<Node<E>>)>;
+
+    /// Finds the optimal route between a start and end point, alongside
+    /// meaningfully different alternatives constrained by the [options](AlternativeOptions).
+    ///
+    /// Returns the weight and routing node vector of each route. The optimal
+    /// route is first, followed by the alternatives in order of preference.
+    /// If no route exists, the output is empty.
+    fn route_alternatives(
+        &self,
+        start: Point,
+        finish: Point,
+        runtime: &M::Runtime,
+        options: &AlternativeOptions,
+    ) -> Vec<(Weight, Vec<Node<E>>)>;
 }
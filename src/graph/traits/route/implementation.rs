@@ -1,3 +1,4 @@
+use crate::graph::traits::route::{AlternativeMethod, AlternativeOptions};
 use crate::graph::{Graph, Weight};
 use crate::graph::{Route, Scan};
 
@@ -5,21 +6,31 @@ use codec::{Entry, Metadata, Node};
 
 use geo::Point;
 use log::debug;
-use petgraph::visit::EdgeRef;
+use petgraph::visit::{EdgeFiltered, EdgeRef};
 
-impl<E, M> Route<E> for Graph<E, M>
+impl<E, M> Route<E, M> for Graph<E, M>
 where
     E: Entry,
     M: Metadata,
 {
-    fn route_nodes(&self, start_node: E, finish_node: E) -> Option<(Weight, Vec<Node<E>>)> {
+    fn route_nodes(
+        &self,
+        start_node: E,
+        finish_node: E,
+        runtime: &M::Runtime,
+    ) -> Option<(Weight, Vec<Node<E>>)> {
         debug!("Routing {start_node:?} -> {finish_node:?}");
 
+        // Only traverse edges accessible under the runtime conditions.
+        let graph = EdgeFiltered::from_fn(&self.graph, |e| {
+            self.accessible(&e.weight().1, runtime) && self.node_accessible(&e.target(), runtime)
+        });
+
         let (score, path) = petgraph::algo::astar(
-            &self.graph,
+            &graph,
             start_node,
             |finish| finish == finish_node,
-            |e| e.weight().0,
+            |e| self.weight(e.weight().0, &e.weight().1, &e.target(), runtime),
             |_| 0 as Weight,
         )?;
 
@@ -32,9 +43,58 @@ where
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, level = Level::INFO))]
-    fn route_points(&self, start: Point, finish: Point) -> Option<(Weight, Vec<Node<E>>)> {
+    fn route_points(
+        &self,
+        start: Point,
+        finish: Point,
+        runtime: &M::Runtime,
+    ) -> Option<(Weight, Vec<Node<E>>)> {
         let start_node = self.scan_node(start)?;
         let finish_node = self.scan_node(finish)?;
-        self.route_nodes(start_node.id, finish_node.id)
+        self.route_nodes(start_node.id, finish_node.id, runtime)
+    }
+
+    fn route_alternatives(
+        &self,
+        start: Point,
+        finish: Point,
+        runtime: &M::Runtime,
+        options: &AlternativeOptions,
+    ) -> Vec<(Weight, Vec<Node<E>>)> {
+        let (Some(start_node), Some(finish_node)) = (self.scan_node(start), self.scan_node(finish))
+        else {
+            return vec![];
+        };
+
+        debug!(
+            "Routing alternatives {:?} -> {:?}",
+            start_node.id, finish_node.id
+        );
+
+        let candidates = match options.method {
+            AlternativeMethod::Plateau => {
+                self.plateau_candidates(start_node.id, finish_node.id, runtime, options)
+            }
+            AlternativeMethod::Penalty => {
+                self.penalty_candidates(start_node.id, finish_node.id, runtime, options)
+            }
+        };
+
+        let Some((optimal, candidates)) = candidates else {
+            return vec![];
+        };
+
+        self.select_alternatives(optimal, candidates, runtime, options)
+            .into_iter()
+            .map(|candidate| {
+                let route = candidate
+                    .path
+                    .iter()
+                    .filter_map(|v| self.hash.get(v).copied())
+                    .collect();
+
+                (candidate.weight, route)
+            })
+            .collect()
     }
 }
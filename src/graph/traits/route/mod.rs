@@ -1,6 +1,8 @@
+pub mod alternatives;
 pub mod definition;
 mod implementation;
 
+pub use alternatives::{AlternativeMethod, AlternativeOptions};
 pub use definition::Route;
 
 #[cfg(test)]
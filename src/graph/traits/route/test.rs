@@ -1,7 +1,9 @@
-use crate::graph::Route;
-use crate::graph::traits::util::init_graph;
+use crate::graph::traits::util::{build_graph, init_graph};
+use crate::graph::{AlternativeMethod, AlternativeOptions, Route};
+use crate::impls::osm::OsmGraph;
 use codec::Node;
-use codec::osm::OsmEntryId;
+use codec::osm::primitives::TransportMode;
+use codec::osm::{Avoidance, OsmEntryId, OsmNodeMetadata, TraversalConditions};
 use geo::{LineString, Point, coord};
 use routers_fixtures::DISTRICT_OF_COLUMBIA;
 use std::error::Error;
@@ -25,7 +27,7 @@ fn columbia_mapping() -> Result<(), Box<dyn Error>> {
     let end = coord! { x: -77.03456230592386, y: 38.91772552535467 };
 
     let (weight, route) = graph
-        .route_points(Point(start), Point(end))
+        .route_points(Point(start), Point(end), &TraversalConditions::default())
         .expect("Could not produce route");
 
     println!("Took: {:?}", time.elapsed());
@@ -77,3 +79,174 @@ fn sydney_mapping() -> Result<(), RouteError> {
     assert_eq!(weight, 450, "Incorrect Route Weighting");
     Ok(())
 }
+
+/// A start and finish joined by three corridors, with a primary
+/// road (weight 10), secondary road (14) and residential road (20).
+fn corridors(secondary: &[(&str, &str)]) -> OsmGraph {
+    build_graph(
+        &[
+            (1, 0.0, 0.0),
+            (2, 0.01, 0.0),
+            (3, 0.02, 0.0),
+            (4, 0.01, 0.005),
+            (5, 0.01, -0.01),
+        ],
+        &[
+            (100, &[1, 2, 3], &[("highway", "primary")]),
+            (101, &[1, 4, 3], secondary),
+            (102, &[1, 5, 3], &[("highway", "residential")]),
+        ],
+    )
+}
+
+fn weights(graph: &OsmGraph, options: &AlternativeOptions) -> Vec<u32> {
+    graph
+        .route_alternatives(
+            Point::new(0.0, 0.0),
+            Point::new(0.02, 0.0),
+            &TraversalConditions::default(),
+            options,
+        )
+        .into_iter()
+        .map(|(weight, _)| weight)
+        .collect()
+}
+
+#[test]
+fn plateau_alternatives() {
+    let graph = corridors(&[("highway", "secondary")]);
+
+    let options = AlternativeOptions::default();
+    assert_eq!(weights(&graph, &options), vec![10, 14]);
+
+    let options = AlternativeOptions::default().max_stretch(2.0);
+    assert_eq!(weights(&graph, &options), vec![10, 14, 20]);
+
+    let options = AlternativeOptions::default().max_stretch(2.0).count(1);
+    assert_eq!(weights(&graph, &options), vec![10, 14]);
+}
+
+#[test]
+fn penalty_alternatives() {
+    let graph = corridors(&[("highway", "secondary")]);
+
+    let options = AlternativeOptions::default().method(AlternativeMethod::Penalty);
+    assert_eq!(weights(&graph, &options), vec![10, 14]);
+}
+
+#[test]
+fn alternatives_respect_access() {
+    let graph = corridors(&[("highway", "secondary"), ("oneway", "-1")]);
+
+    let options = AlternativeOptions::default().max_stretch(2.0);
+    assert_eq!(weights(&graph, &options), vec![10, 20]);
+}
+
+#[test]
+fn node_penalties() {
+    let mut graph = corridors(&[("highway", "secondary")]);
+    let (start, finish) = (OsmEntryId::node(1), OsmEntryId::node(3));
+    let runtime = TraversalConditions::default();
+
+    let signals = OsmNodeMetadata {
+        traffic_signals: true,
+        ..OsmNodeMetadata::default()
+    };
+
+    graph.node_meta.insert(OsmEntryId::node(2), signals);
+    let (weight, route) = graph.route_nodes(start, finish, &runtime).unwrap();
+    assert_eq!(weight, 13);
+    assert_eq!(graph.traffic_signals(&route), 1);
+
+    let gate = OsmNodeMetadata {
+        barrier: Some("gate".into()),
+        ..OsmNodeMetadata::default()
+    };
+
+    graph.node_meta.insert(OsmEntryId::node(2), gate);
+    let (weight, route) = graph.route_nodes(start, finish, &runtime).unwrap();
+    assert_eq!(weight, 14);
+    assert_eq!(graph.traffic_signals(&route), 0);
+}
+
+#[test]
+fn node_cost_is_fixed() {
+    let mut graph = corridors(&[("highway", "secondary")]);
+    let runtime = TraversalConditions::default();
+
+    let (source, target) = (OsmEntryId::node(1), OsmEntryId::node(2));
+    let edge = *graph.edge_between(source, target).unwrap().0;
+    let weight = |graph: &OsmGraph, base: u32| graph.weight(base, &edge, &target, &runtime);
+
+    let signals = OsmNodeMetadata {
+        traffic_signals: true,
+        ..OsmNodeMetadata::default()
+    };
+
+    let (short, long) = (weight(&graph, 5), weight(&graph, 500));
+    graph.node_meta.insert(target, signals);
+
+    // The signals cost as much at the end of a long edge as at the end of a short one.
+    let cost = weight(&graph, 5) - short;
+    assert!(cost > 0);
+    assert_eq!(weight(&graph, 500) - long, cost);
+    assert_eq!(weight(&graph, u32::MAX), u32::MAX);
+}
+
+#[test]
+fn node_barriers() {
+    let mut graph = corridors(&[("highway", "secondary")]);
+    let (start, finish) = (OsmEntryId::node(1), OsmEntryId::node(3));
+    let car = TraversalConditions::new(TransportMode::Motorcar);
+    let bicycle = TraversalConditions::new(TransportMode::Bicycle);
+
+    let bollard = OsmNodeMetadata {
+        barrier: Some("bollard".into()),
+        ..OsmNodeMetadata::default()
+    };
+
+    // The bollard diverts cars onto the secondary road, but not bicycles.
+    graph.node_meta.insert(OsmEntryId::node(2), bollard);
+    let (_, route) = graph.route_nodes(start, finish, &car).unwrap();
+    assert_eq!(route[1].id, OsmEntryId::node(4));
+
+    let (_, route) = graph.route_nodes(start, finish, &bicycle).unwrap();
+    assert_eq!(route[1].id, OsmEntryId::node(2));
+}
+
+#[test]
+fn avoidance() {
+    let graph = build_graph(
+        &[
+            (1, 0.0, 0.0),
+            (2, 0.01, 0.0),
+            (3, 0.02, 0.0),
+            (4, 0.01, 0.005),
+            (5, 0.01, -0.01),
+        ],
+        &[
+            (100, &[1, 2, 3], &[("highway", "primary"), ("toll", "yes")]),
+            (
+                101,
+                &[1, 4, 3],
+                &[("route", "ferry"), ("duration", "00:01:30")],
+            ),
+            (102, &[1, 5, 3], &[("highway", "residential")]),
+        ],
+    );
+
+    let (start, finish) = (OsmEntryId::node(1), OsmEntryId::node(3));
+    let weight = |avoid: Avoidance| {
+        let runtime = TraversalConditions::default().avoid(avoid);
+        graph
+            .route_nodes(start, finish, &runtime)
+            .map(|(weight, _)| weight)
+    };
+
+    assert_eq!(weight(Avoidance::default()), Some(10));
+    assert_eq!(weight(Avoidance::default().tolls(true)), Some(16));
+    assert_eq!(
+        weight(Avoidance::default().tolls(true).ferries(true)),
+        Some(20)
+    );
+}
@@ -1,6 +1,6 @@
 use crate::Graph;
-use crate::transition::Reachable;
 use crate::transition::candidate::*;
+use crate::transition::{LegalityError, Reachable};
 use codec::{Entry, Metadata};
 use geo::LineString;
 
@@ -48,7 +48,9 @@ where
     }
 
     /// Returns the vector of [`Candidate`]s involved in a match.
-    /// Each candidate represents the matched position of every input node.
+    /// Each candidate represents the matched position of an input point, of which
+    /// those beyond the reach of any accessible edge are left unmatched, such that
+    /// the [layer](CandidateLocation::layer_id) of each locates its input point.
     ///
     /// This includes further information such as the edge it matched to,
     /// costing and the identifier for the candidate.
@@ -81,6 +83,30 @@ where
             .collect::<LineString>()
     }
 
+    /// Validates that every edge traversed by the collapsed path, including those
+    /// of the matched candidates, may be traversed in the direction matched under
+    /// the given runtime conditions.
+    ///
+    /// Returns the first edge which may not be legally traversed, if any.
+    pub fn validate<M: Metadata>(
+        &self,
+        map: &Graph<E, M>,
+        runtime: &M::Runtime,
+    ) -> Result<(), LegalityError> {
+        self.matched()
+            .into_iter()
+            .map(|candidate| candidate.edge)
+            .chain(
+                self.interpolated
+                    .iter()
+                    .flat_map(|r| r.path.iter().copied()),
+            )
+            .find(|edge| !map.accessible(&edge.id, runtime))
+            .map_or(Ok(()), |edge| {
+                Err(LegalityError::IllegalTraversal(edge.id().identifier()))
+            })
+    }
+
     pub fn edges(self) -> impl Iterator<Item = Edge<E>> {
         self.interpolated
             .into_iter()
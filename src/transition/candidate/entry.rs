@@ -2,6 +2,7 @@ use crate::Graph;
 use crate::graph::Weight;
 use crate::transition::RoutingContext;
 
+use codec::osm::primitives::Directionality;
 use codec::primitive::Node;
 use codec::{Entry, Metadata};
 use geo::{Distance, Haversine, LineLocatePoint, LineString, Point};
@@ -58,6 +59,15 @@ where
             ..self
         }
     }
+
+    /// The direction of travel along the underlying edge,
+    /// relative to how the edge was drawn.
+    pub fn directionality(&self) -> Directionality {
+        match self.direction {
+            Direction::Outgoing => Directionality::Forward,
+            Direction::Incoming => Directionality::Backward,
+        }
+    }
 }
 
 impl<E> Ord for DirectionAwareEdgeId<E>
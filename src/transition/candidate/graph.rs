@@ -2,6 +2,7 @@ use crate::transition::*;
 
 use crate::EndAttachError::{EndsAlreadyAttached, LayerMissing, WriteLockFailed};
 use codec::Entry;
+use itertools::Itertools;
 use pathfinding::num_traits::{ConstZero, Zero};
 use petgraph::algo::astar;
 use petgraph::graph::EdgeReference;
@@ -77,9 +78,10 @@ where
         // to this source, and every entry within the last/final layer to
         // the target.
 
-        // Attach the initial layer
+        // Attach the initial layer, skipping those without candidates
         layers
-            .first()
+            .populated()
+            .next()
             .ok_or(LayerMissing)?
             .nodes
             .iter()
@@ -89,6 +91,7 @@ where
 
         // Attach to the final layer
         layers
+            .populated()
             .last()
             .ok_or(LayerMissing)?
             .nodes
@@ -162,13 +165,14 @@ where
         }
     }
 
-    // TODO: Docs
+    /// Attaches every candidate to each candidate of the next layer, bridging
+    /// over the layers without candidates, such that an input point which lies
+    /// beyond the reach of any accessible edge is left unmatched.
     pub fn weave(&mut self, layers: &Layers) {
-        layers.layers.windows(2).for_each(|layers| {
-            if let [a, b] = layers {
-                a.nodes.iter().for_each(|node| self.attach(*node, b))
-            }
-        });
+        layers
+            .populated()
+            .tuple_windows()
+            .for_each(|(a, b)| a.nodes.iter().for_each(|node| self.attach(*node, b)));
     }
 
     /// TODO: Provide docs
@@ -15,7 +15,9 @@ where
     /// The exactly-routed elements.
     ///
     /// For a map-match request, these are the values which line up with the inputs
-    /// for a one-to-one match. I.e. there is a discretized point for every input point.
+    /// for a one-to-one match. I.e. there is a discretized point for every input point,
+    /// besides those beyond the reach of any accessible edge, which are left unmatched.
+    /// Use [`Path::by_layer`] to align the elements with the input points.
     pub discretized: Path<E, M>,
 
     /// The interpolated elements.
@@ -70,6 +72,26 @@ where
     elements: Vec<PathElement<E, M>>,
 }
 
+impl<E, M> Path<E, M>
+where
+    E: Entry,
+    M: Metadata,
+{
+    /// The element matched within each of the `layers` input points, in order,
+    /// where those left unmatched, as they lie beyond the reach of any accessible
+    /// edge, are `None`.
+    pub fn by_layer(&self, layers: usize) -> Vec<Option<&PathElement<E, M>>> {
+        let mut matched = vec![None; layers];
+        for element in &self.elements {
+            if let Some(slot) = element.layer.and_then(|layer| matched.get_mut(layer)) {
+                *slot = Some(element);
+            }
+        }
+
+        matched
+    }
+}
+
 impl<E, M> FromIterator<PathElement<E, M>> for Path<E, M>
 where
     E: Entry,
@@ -107,6 +129,10 @@ where
     pub edge: FatEdge<E>,
 
     pub metadata: M,
+
+    /// The layer the element was matched within, being the index of its input point,
+    /// or `None` should the element instead be interpolated between input points.
+    pub layer: Option<usize>,
 }
 
 impl<E, M> PathElement<E, M>
@@ -119,6 +145,7 @@ where
             point: candidate.position,
             edge: candidate.edge.fatten(graph)?,
             metadata: graph.meta.get(candidate.edge.id())?.clone(),
+            layer: Some(candidate.location.layer_id),
         })
     }
 
@@ -127,6 +154,7 @@ where
             point: edge.source.position,
             metadata: graph.meta.get(edge.id())?.clone(),
             edge,
+            layer: None,
         })
     }
 }
@@ -9,7 +9,8 @@ use codec::{Entry, Metadata};
 /// of the call site.
 ///
 /// Provides access to the base map [`map`](#field.map).
-/// It also provides a reference to the [`candidates`](#field.candidates) chosen in prior stages.
+/// It also provides a reference to the [`candidates`](#field.candidates) chosen in prior stages,
+/// and the [`runtime`](#field.runtime) conditions under which the map is traversed.
 #[derive(Clone, Copy, Debug)]
 pub struct RoutingContext<'a, E, M>
 where
@@ -18,6 +19,7 @@ where
 {
     pub candidates: &'a Candidates<E>,
     pub map: &'a Graph<E, M>,
+    pub runtime: &'a M::Runtime,
 }
 
 impl<E, M> RoutingContext<'_, E, M>
@@ -30,9 +32,13 @@ where
         self.candidates.candidate(candidate)
     }
 
-    /// Obtain the [edge](Edge), should it exist, between two [nodes](NodeIx) (specified as ids)
+    /// Obtain the [edge](Edge), should it exist, between two [nodes](NodeIx) (specified as ids).
+    ///
+    /// The weight of the edge includes any penalty under the runtime conditions.
     pub fn edge(&self, a: &E, b: &E) -> Option<Edge<E>> {
-        let edge = self.map.graph.edge_weight(*a, *b)?;
-        Some(Edge::from((*a, *b, edge)))
+        let (weight, id) = self.map.graph.edge_weight(*a, *b)?;
+        let weight = self.map.weight(*weight, id, b, self.runtime);
+
+        Some(Edge::from((*a, *b, &(weight, *id))))
     }
 }
@@ -4,6 +4,8 @@ use crate::transition::*;
 use codec::Metadata;
 use codec::primitive::Entry;
 use geo::LineString;
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
 
 type LayerId = usize;
 type NodeId = usize;
@@ -22,19 +24,26 @@ type NodeId = usize;
 ///
 /// ```rust
 /// use geo::LineString;
-/// use codec::osm::element::Tags;
-/// use codec::osm::OsmEntryId;
+/// use codec::osm::meta::OsmEdgeMetadata;
+/// use codec::osm::{OsmEntryId, TraversalConditions};
 /// use routers::{Graph, Transition};
 /// use routers::transition::{CostingStrategies, SelectiveForwardSolver};
 ///
 /// // An example function to find the interpolated path of a trip.
-/// fn match_trip(map: &Graph<OsmEntryId, Tags>, route: LineString) -> Option<LineString> {
+/// fn match_trip(
+///     map: &Graph<OsmEntryId, OsmEdgeMetadata>,
+///     route: LineString,
+/// ) -> Option<LineString> {
 ///     // Use the default costing strategies
 ///     let costing = CostingStrategies::default();
 ///
+///     // Match the trip as if it were driven by car.
+///     let conditions = TraversalConditions::default();
+///
 ///     // Create our transition graph, supplying our map for context,
-///     // and the route we wish to load as the layer data.
-///     let transition = Transition::new(&map, route, costing);
+///     // the route we wish to load as the layer data, and the conditions
+///     // under which the map may be traversed.
+///     let transition = Transition::new(&map, route, costing, &conditions);
 ///
 ///     // For example, let's choose the selective-forward solver.
 ///     let solver = SelectiveForwardSolver::default();
@@ -54,6 +63,7 @@ where
     Transition: TransitionStrategy<E, M>,
 {
     pub(crate) map: &'a Graph<E, M>,
+    pub(crate) runtime: &'a M::Runtime,
     pub(crate) heuristics: CostingStrategies<Emission, Transition, E, M>,
 
     pub(crate) candidates: Candidates<E>,
@@ -81,15 +91,17 @@ where
         map: &'a Graph<E, M>,
         linestring: LineString,
         heuristics: CostingStrategies<Emmis, Trans, E, M>,
+        runtime: &'a M::Runtime,
     ) -> Transition<'a, Emmis, Trans, E, M> {
         let points = linestring.into_points();
-        let generator = LayerGenerator::new(map, &heuristics);
+        let generator = LayerGenerator::new(map, runtime, &heuristics);
 
         // Generate the layers and candidates.
         let (layers, candidates) = generator.with_points(&points);
 
         Transition {
             map,
+            runtime,
             candidates,
             layers,
             heuristics,
@@ -97,17 +109,81 @@ where
     }
 
     /// Converts the transition graph into a [`RoutingContext`].
-    pub fn context(&self) -> RoutingContext<E, M> {
+    pub fn context(&self) -> RoutingContext<'_, E, M> {
         RoutingContext {
             candidates: &self.candidates,
             map: self.map,
+            runtime: self.runtime,
         }
     }
 
     /// Solves the transition graph, using the provided [`Solver`].
+    ///
+    /// The solution is validated to only traverse edges in directions which are
+    /// legal under the runtime conditions, otherwise [`MatchError::NoLegalMatch`]
+    /// is returned.
+    ///
+    /// Input points which lie beyond the reach of any accessible edge have no candidates,
+    /// and are left unmatched, with the path bridging between the points around them.
+    /// Should no point have any candidates, [`LegalityError::NoCandidates`] is returned,
+    /// whereas should no path between the candidates be legal, such as one travelling
+    /// against a one-way road, [`LegalityError::NoLegalPath`] is. Should no path exist
+    /// even when disregarding the runtime conditions, such as between disconnected
+    /// roads, the failure to collapse the transition graph is returned as is.
     pub fn solve(self, solver: impl Solver<E, M>) -> Result<CollapsedPath<E>, MatchError> {
-        // Indirection to call.
-        solver.solve(self)
+        if self.layers.populated().next().is_none() {
+            return Err(MatchError::NoLegalMatch(LegalityError::NoCandidates));
+        }
+
+        let (map, runtime) = (self.map, self.runtime);
+        let layers = self.candidate_edges();
+
+        let collapsed = solver.solve(self).map_err(|error| match error {
+            // The candidates only lie upon accessible edges, and are only joined by
+            // legal paths, so should they be joined regardless of the runtime
+            // conditions, no path exists which is legal.
+            MatchError::CollapseFailure(CollapseError::NoPathFound)
+                if Self::unrestricted(map, &layers) =>
+            {
+                MatchError::NoLegalMatch(LegalityError::NoLegalPath)
+            }
+            error => error,
+        })?;
+
+        collapsed
+            .validate(map, runtime)
+            .map_err(MatchError::NoLegalMatch)?;
+
+        Ok(collapsed)
+    }
+
+    /// The edges of the candidates within each populated layer, in order.
+    fn candidate_edges(&self) -> Vec<Vec<Edge<E>>> {
+        self.layers
+            .populated()
+            .map(|layer| {
+                layer
+                    .nodes
+                    .iter()
+                    .filter_map(|node| self.candidates.candidate(node))
+                    .map(|candidate| candidate.edge)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Determines if a path joins a candidate of every layer to one of the next,
+    /// disregarding whether the edges along it are accessible under the runtime conditions.
+    fn unrestricted(map: &Graph<E, M>, layers: &[Vec<Edge<E>>]) -> bool {
+        layers.iter().tuple_windows().all(|(from, to)| {
+            let sources = to.iter().map(|edge| edge.source).collect::<FxHashSet<_>>();
+            let shared = from.iter().any(|a| {
+                to.iter()
+                    .any(|b| (a.source, a.target) == (b.source, b.target))
+            });
+
+            shared || map.reaches(from.iter().map(|edge| edge.target), &sources)
+        })
     }
 
     /// Collapses the Hidden Markov Model (See [HMM]) into a
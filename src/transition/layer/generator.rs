@@ -21,6 +21,12 @@ impl Layers {
     pub fn first(&self) -> Option<&Layer> {
         self.layers.first()
     }
+
+    /// The layers which have candidates, in order, skipping those whose
+    /// point lies beyond the reach of any accessible edge.
+    pub fn populated(&self) -> impl Iterator<Item = &Layer> {
+        self.layers.iter().filter(|layer| !layer.nodes.is_empty())
+    }
 }
 
 impl FromParallelIterator<Layer> for Layers {
@@ -71,6 +77,10 @@ where
 
     /// The routing map used to pull candidates from, and provide layout context.
     map: &'a Graph<E, M>,
+
+    /// The runtime conditions under which the map is traversed.
+    /// Candidates are only generated upon edges accessible under these conditions.
+    runtime: &'a M::Runtime,
 }
 
 impl<'a, Emmis, Trans, E, M> LayerGenerator<'a, Emmis, Trans, E, M>
@@ -83,10 +93,12 @@ where
     /// Creates a [`LayerGenerator`] from a map and costing heuristics.
     pub fn new(
         map: &'a Graph<E, M>,
+        runtime: &'a M::Runtime,
         heuristics: &'a CostingStrategies<Emmis, Trans, E, M>,
     ) -> Self {
         LayerGenerator {
             map,
+            runtime,
             heuristics,
 
             search_distance: DEFAULT_SEARCH_DISTANCE,
@@ -114,6 +126,7 @@ where
                     self.map
                         // We'll do a best-effort search (square) radius
                         .scan_nodes_projected(origin, self.search_distance)
+                        .filter(|(_, edge)| self.map.accessible(&edge.id, self.runtime))
                         .filter_map(|(point, edge)| {
                             let distance = Haversine.distance(point, *origin);
 
@@ -9,6 +9,7 @@ pub mod entity;
 pub mod layer;
 pub mod primitives;
 pub mod solver;
+pub mod trace;
 pub mod trip;
 
 // Re-Exports
@@ -23,4 +24,5 @@ pub use solver::*;
 
 pub use entity::*;
 pub use layer::*;
+pub use trace::*;
 pub use trip::*;
@@ -1,7 +1,7 @@
 use crate::transition::RoutingContext;
 use codec::{Entry, Metadata};
 use geo::Distance;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -55,6 +55,20 @@ where
     }
 }
 
+impl<K, V, M, Meta> CacheMap<K, V, M, Meta>
+where
+    M: Metadata,
+    K: CacheKey,
+    V: Debug,
+    Meta: Debug,
+{
+    /// Removes the cached values for which the predicate holds,
+    /// such that they are recalculated upon their next query.
+    pub(crate) fn invalidate(&mut self, predicate: impl Fn(&K, &V) -> bool) {
+        self.map.retain(|key, value| !predicate(key, value));
+    }
+}
+
 impl<K, V, M, Meta> Default for CacheMap<K, V, M, Meta>
 where
     K: CacheKey,
@@ -118,7 +132,13 @@ mod successor {
             ctx.map
                 .graph
                 .edges_directed(key, Direction::Outgoing)
-                .map(|(_, next, (w, _))| {
+                .filter(|(_, next, (_, edge))| {
+                    ctx.map.accessible(edge, ctx.runtime)
+                        && ctx.map.node_accessible(next, ctx.runtime)
+                })
+                .map(|(_, next, (w, edge))| {
+                    let w = ctx.map.weight(*w, edge, &next, ctx.runtime);
+
                     (
                         next,
                         if key != next {
@@ -127,7 +147,7 @@ mod successor {
                             // In centimeters (1m = 100cm)
                             WeightAndDistance(
                                 CumulativeFraction {
-                                    numerator: *w,
+                                    numerator: w,
                                     denominator: 1,
                                 },
                                 (Haversine.distance(source, target) * 100f64) as u32,
@@ -136,7 +156,7 @@ mod successor {
                             // Total accrued distance
                             WeightAndDistance(
                                 CumulativeFraction {
-                                    numerator: *w,
+                                    numerator: w,
                                     denominator: 1,
                                 },
                                 0,
@@ -196,6 +216,20 @@ mod predicate {
     /// made from a [`NodeIx`] key, cached on first calculation and read thereafter.
     pub type PredicateCache<E, M> = CacheMap<E, Predicates<E>, M, PredicateMetadata<E, M>>;
 
+    impl<E: CacheKey, M: Metadata> PredicateCache<E, M> {
+        /// Invalidates the predicates of, or reaching, any of the given nodes,
+        /// alongside the successors of the nodes, as their edges have changed.
+        pub(crate) fn invalidate_nodes(&mut self, nodes: &FxHashSet<E>) {
+            self.metadata
+                .successors
+                .invalidate(|node, _| nodes.contains(node));
+
+            self.invalidate(|node, predicates| {
+                nodes.contains(node) || predicates.keys().any(|reached| nodes.contains(reached))
+            });
+        }
+    }
+
     impl<E: CacheKey, M: Metadata> Calculable<E, M, Predicates<E>> for PredicateCache<E, M> {
         #[inline]
         fn calculate(&mut self, ctx: &RoutingContext<E, M>, key: E) -> Predicates<E> {
@@ -10,6 +10,21 @@ pub enum MatchError {
 
     #[error("failed to attach ends in transition graph: {0}")]
     EndAttachFailure(EndAttachError),
+
+    #[error("no legal match exists under the runtime conditions: {0}")]
+    NoLegalMatch(LegalityError),
+}
+
+#[derive(Error, Debug)]
+pub enum LegalityError {
+    #[error("no input point lies within reach of an accessible edge")]
+    NoCandidates,
+
+    #[error("no path through the candidates may be legally traversed")]
+    NoLegalPath,
+
+    #[error("edge {0} cannot be traversed in the matched direction")]
+    IllegalTraversal(i64),
 }
 
 #[derive(Error, Debug)]
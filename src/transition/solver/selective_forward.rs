@@ -218,6 +218,8 @@ where
                             let source_percentage = source_candidate.percentage(ctx.map)?;
                             let target_percentage = candidate.percentage(ctx.map)?;
 
+                            // Candidates are only generated upon edges which may be traversed
+                            // in their direction, so moving forward along one is always legal.
                             return if tracking_forward && source_percentage <= target_percentage {
                                 // We are moving forward, it is simply the distance between the nodes
                                 Some(Reachable::new(*source, *target, vec![]).distance_only())
@@ -248,6 +250,14 @@ where
                         })
                         .collect::<Vec<_>>();
 
+                    // Reject any path which traverses an edge illegally.
+                    if !path
+                        .iter()
+                        .all(|edge| ctx.map.accessible(&edge.id, ctx.runtime))
+                    {
+                        return None;
+                    }
+
                     Some(Reachable::new(*source, *target, path))
                 })
                 .collect::<Vec<_>>()
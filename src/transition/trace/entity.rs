@@ -0,0 +1,279 @@
+use geo::{Distance, Haversine, LineString, Point};
+use log::debug;
+use std::time::Duration;
+
+const DEFAULT_MAX_SPEED: f64 = 70.0; // 70m/s (~250km/h)
+const DEFAULT_MIN_DISTANCE: f64 = 5.0; // 5m
+
+/// Pre-processes a raw input trace before it is handed to the
+/// [`Transition`](crate::Transition) graph.
+///
+/// Raw traces commonly contain outliers (teleports), duplicate
+/// observations and over-dense sampling. Each of these inflates the
+/// number of layers in the transition graph without adding information,
+/// which becomes expensive for long inputs.
+///
+/// The cleaner removes these points, and returns a [`CleanedTrace`]
+/// which remembers which original point each retained point represents,
+/// such that results can be reported back against the original input.
+///
+/// ### Example
+/// ```rust
+/// use routers::transition::TraceCleaner;
+/// use geo::wkt;
+///
+/// let linestring = wkt! {
+///     LINESTRING (0.0 0.0, 0.0 0.0, 0.0 0.001, 0.0 0.002)
+/// };
+///
+/// let cleaned = TraceCleaner::default().clean(linestring, None);
+///
+/// // The duplicate point was removed, but still maps onto the first point.
+/// assert_eq!(cleaned.linestring.0.len(), 3);
+/// assert_eq!(cleaned.lookup(), vec![0, 0, 1, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TraceCleaner {
+    /// The maximum plausible speed (in m/s) between two observations.
+    ///
+    /// A point which can only be reached, and left, at a speed above
+    /// this threshold is considered an outlier and removed. This is only
+    /// applied when timestamps are provided.
+    pub max_speed: f64,
+
+    /// The minimum distance (in m) between two retained points.
+    ///
+    /// Points closer than this to the previously retained point are
+    /// dropped, downsampling over-dense traces.
+    pub min_distance: f64,
+
+    /// The minimum interval between two retained points.
+    ///
+    /// If set, points observed within this interval of the previously
+    /// retained point are dropped. This is only applied when timestamps
+    /// are provided.
+    pub min_interval: Option<Duration>,
+}
+
+impl Default for TraceCleaner {
+    fn default() -> Self {
+        Self {
+            max_speed: DEFAULT_MAX_SPEED,
+            min_distance: DEFAULT_MIN_DISTANCE,
+            min_interval: None,
+        }
+    }
+}
+
+impl TraceCleaner {
+    pub fn max_speed(self, max_speed: f64) -> Self {
+        Self { max_speed, ..self }
+    }
+
+    pub fn min_distance(self, min_distance: f64) -> Self {
+        Self {
+            min_distance,
+            ..self
+        }
+    }
+
+    pub fn min_interval(self, min_interval: Duration) -> Self {
+        Self {
+            min_interval: Some(min_interval),
+            ..self
+        }
+    }
+
+    /// The speed (in m/s) required to travel between two observations,
+    /// or `None` if the observations are not separated in time.
+    fn speed(&self, (a, at): (Point, Duration), (b, bt): (Point, Duration)) -> Option<f64> {
+        let elapsed = bt.checked_sub(at)?.as_secs_f64();
+        (elapsed > 0.0).then(|| Haversine.distance(a, b) / elapsed)
+    }
+
+    /// Determines if the observation at `index` is a speed-implausible outlier.
+    ///
+    /// This is the case when the point is reached from the previously retained
+    /// point, and left towards the next point, only at implausible speeds.
+    /// Requiring both legs to be implausible ensures the point *after* a
+    /// teleport is not penalised for the teleport itself.
+    ///
+    /// The first point has no incoming leg, so it is instead considered an
+    /// outlier if it is implausible to leave, but the two points which follow
+    /// it are plausibly connected.
+    fn is_outlier(
+        &self,
+        points: &[Point],
+        timestamps: &[Duration],
+        previous: Option<usize>,
+        index: usize,
+    ) -> bool {
+        let observation = |index: usize| Some((*points.get(index)?, *timestamps.get(index)?));
+        let implausible = |a: usize, b: usize| {
+            observation(a)
+                .zip(observation(b))
+                .and_then(|(a, b)| self.speed(a, b))
+                .map(|speed| speed > self.max_speed)
+        };
+
+        let outgoing = implausible(index, index + 1);
+        match previous {
+            Some(prev) => match (implausible(prev, index), outgoing) {
+                (Some(incoming), Some(outgoing)) => incoming && outgoing,
+                (Some(incoming), None) => incoming && index + 1 == points.len(),
+                _ => false,
+            },
+            None => outgoing == Some(true) && implausible(index + 1, index + 2) == Some(false),
+        }
+    }
+
+    /// Cleans the given linestring, optionally using the timestamps of each point.
+    ///
+    /// The timestamps, if provided, are expected to be the same length as the
+    /// linestring, and are treated as an offset from a shared epoch. If the
+    /// lengths differ, the timestamps are ignored.
+    pub fn clean(&self, linestring: LineString, timestamps: Option<&[Duration]>) -> CleanedTrace {
+        let points = linestring.into_points();
+        let timestamps = timestamps.filter(|times| times.len() == points.len());
+
+        let mut indices: Vec<usize> = Vec::with_capacity(points.len());
+
+        for (index, point) in points.iter().enumerate() {
+            let previous = indices.last().copied();
+            let is_last = index + 1 == points.len();
+
+            let Some(prev) = previous else {
+                // The first point can only be removed as an outlier.
+                if !timestamps.is_some_and(|times| self.is_outlier(&points, times, None, index)) {
+                    indices.push(index);
+                }
+                continue;
+            };
+
+            // Duplicate observations, in time or position, carry no information.
+            let distance = Haversine.distance(points[prev], *point);
+            let duplicate =
+                distance == 0.0 || timestamps.is_some_and(|times| times[index] <= times[prev]);
+
+            if duplicate {
+                continue;
+            }
+
+            if timestamps.is_some_and(|times| self.is_outlier(&points, times, previous, index)) {
+                continue;
+            }
+
+            // The final point is always retained so the trace keeps its extent.
+            let too_close = distance < self.min_distance
+                || self
+                    .min_interval
+                    .zip(timestamps)
+                    .is_some_and(|(interval, times)| {
+                        times[index].saturating_sub(times[prev]) < interval
+                    });
+
+            if too_close && !is_last {
+                continue;
+            }
+
+            indices.push(index);
+        }
+
+        debug!(
+            "Cleaned trace from {} to {} points",
+            points.len(),
+            indices.len()
+        );
+
+        CleanedTrace {
+            linestring: indices.iter().map(|index| points[*index]).collect(),
+            indices,
+            original_len: points.len(),
+        }
+    }
+}
+
+/// The output of a [`TraceCleaner`].
+///
+/// Contains the cleaned linestring to be matched, alongside the
+/// mapping between the retained points and the original input.
+#[derive(Clone, Debug)]
+pub struct CleanedTrace {
+    /// The retained points of the trace.
+    pub linestring: LineString,
+
+    /// The original index of every retained point, in order.
+    indices: Vec<usize>,
+
+    /// The number of points in the original trace.
+    original_len: usize,
+}
+
+impl From<LineString> for CleanedTrace {
+    /// A trace which retains every point of the linestring, as given.
+    fn from(linestring: LineString) -> Self {
+        let original_len = linestring.0.len();
+
+        CleanedTrace {
+            linestring,
+            indices: (0..original_len).collect(),
+            original_len,
+        }
+    }
+}
+
+impl CleanedTrace {
+    /// The original indices of every retained point.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The original index of the retained point at `index`.
+    pub fn original_index(&self, index: usize) -> Option<usize> {
+        self.indices.get(index).copied()
+    }
+
+    /// Returns, for every point in the original trace, the index of the
+    /// retained point which represents it.
+    ///
+    /// Removed points are represented by the most recently retained point
+    /// before them, or the first retained point if none precede them.
+    /// If no points were retained, the output is empty.
+    pub fn lookup(&self) -> Vec<usize> {
+        if self.indices.is_empty() {
+            return vec![];
+        }
+
+        let mut retained = 0;
+        (0..self.original_len)
+            .map(|original| {
+                while self
+                    .indices
+                    .get(retained + 1)
+                    .is_some_and(|next| *next <= original)
+                {
+                    retained += 1;
+                }
+
+                retained
+            })
+            .collect()
+    }
+
+    /// Expands values produced for each retained point (such as the
+    /// discretized output of a match) into a value per original point.
+    ///
+    /// Returns `None` if there is not exactly one value per retained point.
+    pub fn expand<T: Clone>(&self, values: &[T]) -> Option<Vec<T>> {
+        if values.len() != self.indices.len() {
+            return None;
+        }
+
+        Some(
+            self.lookup()
+                .into_iter()
+                .map(|index| values[index].clone())
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,8 @@
+#[doc(hidden)]
+pub mod entity;
+#[doc(inline)]
+pub use entity::*;
+
+#[doc(hidden)]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,130 @@
+use crate::transition::{CleanedTrace, TraceCleaner};
+
+use geo::{LineString, wkt};
+use std::time::Duration;
+
+fn seconds(values: &[u64]) -> Vec<Duration> {
+    values.iter().copied().map(Duration::from_secs).collect()
+}
+
+#[test]
+fn removes_duplicates() {
+    // The second point shares the position of the first,
+    // and the fourth shares the timestamp of the third.
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.0, 0.0 0.001, 0.0 0.0011, 0.0 0.002)
+    };
+
+    let timestamps = seconds(&[0, 5, 10, 10, 20]);
+    let cleaned = TraceCleaner::default().clean(linestring, Some(&timestamps));
+
+    assert_eq!(cleaned.indices(), &[0, 2, 4]);
+    assert_eq!(cleaned.lookup(), vec![0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn removes_teleports() {
+    // The third point is ~111km away, observed only 10s apart.
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.001, 1.0 1.0, 0.0 0.002, 0.0 0.003)
+    };
+
+    let timestamps = seconds(&[0, 10, 20, 30, 40]);
+    let cleaned = TraceCleaner::default().clean(linestring, Some(&timestamps));
+
+    assert_eq!(cleaned.indices(), &[0, 1, 3, 4]);
+    assert_eq!(cleaned.lookup(), vec![0, 1, 1, 2, 3]);
+}
+
+#[test]
+fn removes_leading_teleport() {
+    let linestring = wkt! {
+        LINESTRING (1.0 1.0, 0.0 0.0, 0.0 0.001, 0.0 0.002)
+    };
+
+    let timestamps = seconds(&[0, 10, 20, 30]);
+    let cleaned = TraceCleaner::default().clean(linestring, Some(&timestamps));
+
+    assert_eq!(cleaned.indices(), &[1, 2, 3]);
+    assert_eq!(cleaned.lookup(), vec![0, 0, 1, 2]);
+}
+
+#[test]
+fn keeps_teleports_without_timestamps() {
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.001, 1.0 1.0, 0.0 0.002)
+    };
+
+    let cleaned = TraceCleaner::default().clean(linestring, None);
+    assert_eq!(cleaned.indices(), &[0, 1, 2, 3]);
+}
+
+#[test]
+fn downsamples_by_distance() {
+    // Points are ~11m apart, downsampled to at-least 20m.
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.0001, 0.0 0.0002, 0.0 0.0003, 0.0 0.0004, 0.0 0.00045)
+    };
+
+    let cleaned = TraceCleaner::default()
+        .min_distance(20.0)
+        .clean(linestring, None);
+
+    // The final point is always retained.
+    assert_eq!(cleaned.indices(), &[0, 2, 4, 5]);
+    assert_eq!(
+        cleaned.expand(&["a", "b", "c", "d"]),
+        Some(vec!["a", "a", "b", "b", "c", "d"])
+    );
+}
+
+#[test]
+fn downsamples_by_interval() {
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.001, 0.0 0.002, 0.0 0.003, 0.0 0.004)
+    };
+
+    let timestamps = seconds(&[0, 5, 10, 15, 20]);
+    let cleaned = TraceCleaner::default()
+        .min_interval(Duration::from_secs(10))
+        .clean(linestring, Some(&timestamps));
+
+    assert_eq!(cleaned.indices(), &[0, 2, 4]);
+}
+
+#[test]
+fn empty_trace() {
+    let cleaned = TraceCleaner::default().clean(LineString::new(vec![]), None);
+
+    assert!(cleaned.indices().is_empty());
+    assert!(cleaned.lookup().is_empty());
+    assert_eq!(cleaned.expand::<usize>(&[]), Some(vec![]));
+}
+
+#[test]
+fn uncleaned_trace() {
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.0, 0.0 0.00001)
+    };
+
+    let trace = CleanedTrace::from(linestring.clone());
+
+    assert_eq!(trace.linestring, linestring);
+    assert_eq!(trace.indices(), &[0, 1, 2]);
+    assert_eq!(trace.expand(&['a', 'b', 'c']), Some(vec!['a', 'b', 'c']));
+}
+
+#[test]
+fn expands_mismatched_values() {
+    let linestring = wkt! {
+        LINESTRING (0.0 0.0, 0.0 0.0, 0.0 0.001)
+    };
+
+    let cleaned = TraceCleaner::default().clean(linestring, None);
+    assert_eq!(cleaned.indices(), &[0, 2]);
+
+    // Values must be given for every retained point, and no more.
+    assert_eq!(cleaned.expand(&['a']), None);
+    assert_eq!(cleaned.expand(&['a', 'b', 'c']), None);
+    assert_eq!(cleaned.expand(&['a', 'b']), Some(vec!['a', 'a', 'b']));
+}